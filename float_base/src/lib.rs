@@ -1,8 +1,10 @@
-use lexical::{
-    NumberFormatBuilder, ParseFloatOptions, ParseIntegerOptions, WriteFloatOptions,
-    WriteIntegerOptions,
-};
+#![cfg_attr(feature = "dynamic", feature(f16))]
+#[cfg(any(feature = "f16", feature = "f32", feature = "f64", feature = "f128"))]
+use lexical::{FromLexicalWithOptions, ParseFloatOptions, ToLexicalWithOptions, WriteFloatOptions};
+use lexical::{NumberFormatBuilder, ParseIntegerOptions, WriteIntegerOptions};
 use std::fmt::Display;
+#[cfg(feature = "dynamic")]
+use std::num::NonZeroUsize;
 #[cfg(feature = "f16")]
 pub type F = f16;
 #[cfg(feature = "f32")]
@@ -11,13 +13,23 @@ pub type F = f32;
 pub type F = f64;
 #[cfg(feature = "f128")]
 pub type F = f128;
-pub fn parse_radix(src: &str, base: u8) -> Option<F> {
+#[cfg(feature = "fastnum")]
+pub type F = fastnum::D1024;
+#[cfg(feature = "fastnum")]
+use fastnum::decimal::Context;
+#[cfg(feature = "fastnum")]
+use std::fmt;
+#[cfg(any(feature = "f16", feature = "f32", feature = "f64", feature = "f128"))]
+pub fn parse_radix<T>(src: &str, base: u8) -> Option<T>
+where
+    T: FromLexicalWithOptions<Options = ParseFloatOptions>,
+{
     let options = ParseFloatOptions::from_radix(base);
     macro_rules! parses {
             ($($n:ident = $nu:expr),*) => {
                 match base {
                     $(
-                        $nu => lexical::parse_with_options::<F, _, $n>(src, &options).ok(),
+                        $nu => lexical::parse_with_options::<T, _, $n>(src, &options).ok(),
                     )*
                     _ => unreachable!()
                 }
@@ -61,13 +73,23 @@ pub fn parse_radix(src: &str, base: u8) -> Option<F> {
         F36 = 36
     )
 }
-pub fn to_string_radix(value: f64, base: u8) -> impl Display {
-    let options = WriteFloatOptions::from_radix(base);
+#[cfg(any(feature = "f16", feature = "f32", feature = "f64", feature = "f128"))]
+pub fn to_string_radix<T>(value: T, base: u8) -> impl Display
+where
+    T: ToLexicalWithOptions<Options = WriteFloatOptions>,
+{
+    to_string_radix_with(value, base, &WriteFloatOptions::from_radix(base))
+}
+#[cfg(any(feature = "f16", feature = "f32", feature = "f64", feature = "f128"))]
+fn to_string_radix_with<T>(value: T, base: u8, options: &WriteFloatOptions) -> String
+where
+    T: ToLexicalWithOptions<Options = WriteFloatOptions>,
+{
     macro_rules! strings {
             ($($n:ident = $nu:expr),*) => {
                 match base {
                     $(
-                        $nu => lexical::to_string_with_options::<F, $n>(value, &options),
+                        $nu => lexical::to_string_with_options::<T, $n>(value, options),
                     )*
                     _ => unreachable!()
                 }
@@ -111,6 +133,101 @@ pub fn to_string_radix(value: f64, base: u8) -> impl Display {
         F36 = 36
     )
 }
+#[cfg(feature = "dynamic")]
+pub fn parse_radix_bits(src: &str, base: u8, bits: u32) -> Option<F> {
+    match bits {
+        16 if base == 10 => src.parse::<f16>().ok().map(F::from),
+        16 => parse_radix::<F>(src, base).map(|v| v as f16 as F),
+        32 => parse_radix::<f32>(src, base).map(F::from),
+        _ => parse_radix(src, base),
+    }
+}
+#[cfg(feature = "dynamic")]
+pub fn to_string_radix_bits(value: F, base: u8, bits: u32) -> impl Display {
+    match bits {
+        16 => (1..=11)
+            .map(|digits| {
+                let options = WriteFloatOptions::from_radix(base)
+                    .rebuild()
+                    .max_significant_digits(NonZeroUsize::new(digits))
+                    .build_unchecked();
+                to_string_radix_with(value, base, &options)
+            })
+            .find(|s| parse_radix::<F>(s, base).map(|v| v as f16 as F) == Some(value))
+            .unwrap_or_else(|| to_string_radix(value, base).to_string()),
+        32 => to_string_radix(value as f32, base).to_string(),
+        _ => to_string_radix(value, base).to_string(),
+    }
+}
+#[cfg(feature = "fastnum")]
+pub fn parse_radix(src: &str, base: u8) -> Option<F> {
+    if base == 10 {
+        return F::from_str(src, Context::default()).ok();
+    }
+    let (negative, src) = src
+        .strip_prefix('-')
+        .map(|s| (true, s))
+        .unwrap_or((false, src));
+    let (int, fract) = src.split_once('.').unwrap_or((src, ""));
+    if int.is_empty() && fract.is_empty() {
+        return None;
+    }
+    let radix = F::from(base);
+    let mut value = F::ZERO;
+    for c in int.chars() {
+        value = value * radix + F::from(c.to_digit(base as u32)?);
+    }
+    let mut scale = F::ONE;
+    for c in fract.chars() {
+        scale /= radix;
+        value += F::from(c.to_digit(base as u32)?) * scale;
+    }
+    Some(if negative { -value } else { value })
+}
+#[cfg(feature = "fastnum")]
+pub fn to_string_radix(value: F, base: u8) -> impl Display {
+    fmt::from_fn(move |fmt| {
+        if base == 10 {
+            return write!(fmt, "{}", value.to_string().replace('E', "e"));
+        }
+        if value.is_nan() {
+            return write!(fmt, "NaN");
+        }
+        if value.is_sign_negative() && !value.is_zero() {
+            write!(fmt, "-")?;
+        }
+        if value.is_infinite() {
+            return write!(fmt, "inf");
+        }
+        let radix = F::from(base);
+        let digit = |d: F| char::from_digit(u32::try_from(d).unwrap_or(0), base as u32).unwrap();
+        let mut int = value.abs().trunc();
+        let mut fract = value.abs() - int;
+        let mut digits = Vec::new();
+        while !int.is_zero() {
+            digits.push(digit(int % radix));
+            int = (int / radix).trunc();
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        digits.reverse();
+        let max = (1024.0 / (base as f64).log2()) as usize;
+        let mut fraction = String::new();
+        while !fract.is_zero() && digits.len() + fraction.len() < max {
+            fract *= radix;
+            let d = fract.trunc();
+            fraction.push(digit(d));
+            fract -= d;
+        }
+        let fraction = fraction.trim_end_matches('0');
+        write!(fmt, "{}", digits.into_iter().collect::<String>())?;
+        if !fraction.is_empty() {
+            write!(fmt, ".{fraction}")?;
+        }
+        Ok(())
+    })
+}
 pub fn parse_radix_usize(src: &str, base: u8) -> Option<usize> {
     let options = ParseIntegerOptions::from_radix(base);
     macro_rules! parses {
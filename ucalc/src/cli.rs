@@ -1,6 +1,8 @@
 use crate::colors::{Colors, ToColor};
 use crate::complete::Complete;
-use crate::shared::{Options, process_line, to_alt};
+#[cfg(any(feature = "rug", feature = "dynamic"))]
+use crate::shared::change_precision;
+use crate::shared::{Options, process_line, simplify_line, to_alt};
 use readchar::crossterm::cursor::MoveTo;
 use readchar::crossterm::terminal::{Clear, ClearType};
 use readchar::{History, ReadChar, Return};
@@ -9,7 +11,9 @@ use std::hint::black_box;
 use std::io::Write;
 use std::io::{BufRead, IsTerminal, stdin, stdout};
 use std::process::exit;
-use ucalc_lib::{Compute, Functions, Number, ParseReturn, Tokens, Variable, Variables, Volatility};
+use ucalc_lib::{
+    Compute, EvalError, Functions, Number, ParseReturn, Tokens, Variable, Variables, Volatility,
+};
 #[cfg(feature = "float_rand")]
 use ucalc_lib::{Rand, rng};
 use ucalc_numbers::{FloatTrait, RealTrait};
@@ -136,8 +140,8 @@ fn run_line(
         options.benchmark_simplify = true;
         return;
     }
-    let mut get = |s: &str| -> isize {
-        let tokens = Tokens::parse(
+    let mut get = |s: &str| -> Result<isize, String> {
+        match Tokens::parse(
             s,
             vars,
             funs,
@@ -148,31 +152,100 @@ fn run_line(
             options.rpn,
             #[cfg(feature = "float_rand")]
             rand,
-        )
-        .unwrap()
-        .tokens();
-        let num = tokens.compute(
-            &[],
-            funs,
-            vars,
-            #[cfg(feature = "float_rand")]
-            rand,
-        );
-        num.to_real().into_isize()
+        ) {
+            Ok(ParseReturn::Tokens(tokens)) => tokens
+                .compute(
+                    &[],
+                    funs,
+                    vars,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )
+                .map(|num| num.to_real().into_isize())
+                .map_err(|e| e.to_string()),
+            Ok(_) => Err(EvalError::NonInteger.to_string()),
+            Err(e) => Err(e.render(s)),
+        }
     };
     if let Some(s) = line.strip_prefix("--base_input=") {
-        options.base_input = get(s).try_into().unwrap();
+        match get(s).map(u8::try_from) {
+            Ok(Ok(base @ 2..=36)) => options.base_input = base,
+            Ok(_) => {
+                *ret = 1;
+                println!("invalid base")
+            }
+            Err(e) => {
+                *ret = 1;
+                println!("{e}")
+            }
+        }
         return;
     }
     if let Some(s) = line.strip_prefix("--base_output=") {
-        options.base_output = get(s).try_into().unwrap();
+        match get(s).map(u8::try_from) {
+            Ok(Ok(base @ 2..=36)) => options.base_output = base,
+            Ok(_) => {
+                *ret = 1;
+                println!("invalid base")
+            }
+            Err(e) => {
+                *ret = 1;
+                println!("{e}")
+            }
+        }
         return;
     }
     if let Some(s) = line.strip_prefix("--benchmark=") {
-        options.benchmark = get(s).try_into().unwrap();
+        match get(s).map(usize::try_from) {
+            Ok(Ok(n)) => options.benchmark = n,
+            Ok(Err(e)) => {
+                *ret = 1;
+                println!("{e}")
+            }
+            Err(e) => {
+                *ret = 1;
+                println!("{e}")
+            }
+        }
+        return;
+    }
+    #[cfg(any(feature = "rug", feature = "dynamic"))]
+    if let Some(s) = line
+        .strip_prefix("--precision=")
+        .or_else(|| line.strip_prefix(":prec "))
+    {
+        match get(s).map(u32::try_from) {
+            Ok(Ok(prec)) if change_precision(prec, vars) => {}
+            Ok(_) => {
+                *ret = 1;
+                println!("invalid precision")
+            }
+            Err(e) => {
+                *ret = 1;
+                println!("{e}")
+            }
+        }
         return;
     }
     *quit = true;
+    if let Some(arg) = line.strip_prefix("simplify ") {
+        let mut str = String::new();
+        if !simplify_line(
+            arg,
+            vars,
+            funs,
+            *options,
+            &mut str,
+            #[cfg(feature = "float_rand")]
+            rand,
+        )
+        .unwrap()
+        {
+            *ret = 1;
+        }
+        println!("{str}");
+        return;
+    }
     match tmr(
         || {
             Tokens::parse(
@@ -191,7 +264,7 @@ fn run_line(
         options.perf,
     ) {
         Ok(ParseReturn::Tokens(tokens)) => {
-            let compute = tmr(
+            let compute = match tmr(
                 || {
                     tokens.compute(
                         &[],
@@ -202,21 +275,32 @@ fn run_line(
                     )
                 },
                 options.perf,
-            );
+            ) {
+                Ok(compute) => compute,
+                Err(e) => {
+                    *ret = 1;
+                    println!("{e}");
+                    return;
+                }
+            };
             print!("{}", compute.get_closest_fraction(options.base_output));
             println!("{}", compute.to_string_radix(options.base_output));
             if options.benchmark > 0 {
-                if options.benchmark_simplify {
-                    benchmark(
+                if options.benchmark_simplify
+                    && let Err(e) = benchmark(
                         tokens,
                         options.benchmark,
                         vars,
                         funs,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )
+                {
+                    *ret = 1;
+                    println!("{e}");
+                    return;
                 }
-                let tokens = Tokens::parse(
+                let tokens = match Tokens::parse(
                     line,
                     vars,
                     funs,
@@ -227,26 +311,40 @@ fn run_line(
                     options.rpn,
                     #[cfg(feature = "float_rand")]
                     rand,
-                )
-                .unwrap()
-                .tokens();
-                benchmark(
+                ) {
+                    Ok(tokens) => tokens.tokens(),
+                    Err(e) => {
+                        *ret = 1;
+                        println!("{}", e.render(line));
+                        return;
+                    }
+                };
+                if let Err(e) = benchmark(
                     tokens,
                     options.benchmark,
                     vars,
                     funs,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                ) {
+                    *ret = 1;
+                    println!("{e}")
+                }
             }
         }
+        Ok(ParseReturn::Expr(tokens)) => println!(
+            "{}",
+            tokens[..]
+                .simplify_algebraic(funs)
+                .get_infix(vars, funs, &["x", "y"])
+        ),
         Ok(ParseReturn::Graph(_, _)) => {
             todo!()
         }
         Ok(ParseReturn::Var) => {}
         Err(e) => {
             *ret = 1;
-            println!("{e:?}")
+            println!("{}", e.render(line))
         }
     }
 }
@@ -256,7 +354,7 @@ fn benchmark(
     vars: &mut Variables,
     funs: &mut Functions,
     #[cfg(feature = "float_rand")] rand: &mut Rand,
-) {
+) -> Result<(), EvalError> {
     let cap = tokens.len() + funs.iter().map(|c| c.tokens.len()).sum::<usize>();
     let mut inner_vars = Vec::with_capacity(cap);
     let mut stack = Vec::with_capacity(cap);
@@ -267,7 +365,7 @@ fn benchmark(
             &mut stack,
             #[cfg(feature = "float_rand")]
             rand,
-        ));
+        ))?;
     }
     let tmr = std::time::Instant::now();
     for _ in 0..n {
@@ -276,11 +374,12 @@ fn benchmark(
             &mut stack,
             #[cfg(feature = "float_rand")]
             rand,
-        ));
+        ))?;
     }
     let time = tmr.elapsed().as_nanos();
     let mean = time as f64 / n as f64;
-    println!("Total: {time}ns, Mean: {mean}ns")
+    println!("Total: {time}ns, Mean: {mean}ns");
+    Ok(())
 }
 fn tmr<T, W>(fun: T, perf: bool) -> W
 where
@@ -5,6 +5,10 @@ use std::fmt::Write;
 use ucalc_lib::Rand;
 use ucalc_lib::{Functions, Number, ParseReturn, Tokens, Variables, get_help};
 use ucalc_numbers::FloatTrait;
+#[cfg(feature = "dynamic")]
+use ucalc_numbers::dynamic::{precision, set_precision};
+#[cfg(feature = "rug")]
+use ucalc_numbers::rug::{precision, set_precision};
 #[derive(Clone, Copy)]
 pub struct Options {
     pub rpn: bool,
@@ -51,6 +55,28 @@ pub fn process_line(
             write!(str, "{}", color_brackets(get_help(arg), colors))?;
             None
         }
+        #[cfg(any(feature = "rug", feature = "dynamic"))]
+        _ if let Some(arg) = line.strip_prefix(":prec") => {
+            let arg = arg.trim();
+            if arg.is_empty() || arg.parse().is_ok_and(|prec| change_precision(prec, vars)) {
+                write!(str, "{}", precision())?;
+            } else {
+                write!(str, "invalid precision")?;
+            }
+            None
+        }
+        _ if let Some(arg) = line.strip_prefix("simplify ") => {
+            simplify_line(
+                arg,
+                vars,
+                funs,
+                options,
+                str,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            None
+        }
         _ => {
             match tmr_write(
                 || {
@@ -71,7 +97,7 @@ pub fn process_line(
                 options.perf,
             ) {
                 Ok(ParseReturn::Tokens(tokens)) => {
-                    let compute = tmr_write(
+                    match tmr_write(
                         || {
                             tokens.compute(
                                 &[],
@@ -83,21 +109,87 @@ pub fn process_line(
                         },
                         str,
                         options.perf,
-                    );
-                    write!(str, "{}", compute.get_closest_fraction(options.base_output))?;
-                    write!(str, "{}", compute.to_string_radix(options.base_output))?;
-                    Some(compute)
+                    ) {
+                        Ok(compute) => {
+                            write!(str, "{}", compute.get_closest_fraction(options.base_output))?;
+                            write!(str, "{}", compute.to_string_radix(options.base_output))?;
+                            Some(compute)
+                        }
+                        Err(e) => {
+                            write!(str, "{e}")?;
+                            None
+                        }
+                    }
+                }
+                Ok(ParseReturn::Expr(tokens)) => {
+                    write!(
+                        str,
+                        "{}",
+                        tokens[..]
+                            .simplify_algebraic(funs)
+                            .get_infix(vars, funs, &["x", "y"])
+                    )?;
+                    None
                 }
                 Ok(ParseReturn::Graph(_, _)) => todo!(),
                 Ok(ParseReturn::Var) => None,
                 Err(e) => {
-                    write!(str, "{e:?}")?;
+                    write!(str, "{}", e.render(line))?;
                     None
                 }
             }
         }
     })
 }
+pub fn simplify_line(
+    line: &str,
+    vars: &mut Variables,
+    funs: &mut Functions,
+    options: Options,
+    str: &mut String,
+    #[cfg(feature = "float_rand")] rand: &mut Rand,
+) -> Result<bool, fmt::Error> {
+    match Tokens::parse(
+        line,
+        vars,
+        funs,
+        &["x", "y"],
+        false,
+        true,
+        options.base_input,
+        options.rpn,
+        #[cfg(feature = "float_rand")]
+        rand,
+    ) {
+        Ok(
+            ParseReturn::Tokens(tokens) | ParseReturn::Graph(tokens, _) | ParseReturn::Expr(tokens),
+        ) => write!(
+            str,
+            "{}",
+            tokens[..]
+                .simplify_algebraic(funs)
+                .get_infix(vars, funs, &["x", "y"])
+        )?,
+        Ok(ParseReturn::Var) => {}
+        Err(e) => {
+            write!(str, "{}", e.render(line))?;
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+#[cfg(any(feature = "rug", feature = "dynamic"))]
+pub fn change_precision(prec: u32, vars: &mut Variables) -> bool {
+    if !set_precision(prec) {
+        return false;
+    }
+    for var in Variables::default().0 {
+        if let Some(name) = &var.name {
+            vars.get_mut(name).value = var.value
+        }
+    }
+    true
+}
 pub fn to_alt(c: char) -> Option<char> {
     Some(match c {
         'a' => 'α',
@@ -1,11 +1,38 @@
 use crate::derivative::DiffToken;
-use crate::parse::{Token, Tokens, TokensSlice};
+use crate::parse::{Derivative, Token, Tokens, TokensSlice};
 use crate::polynomial::Polynomial;
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
 use crate::{FunctionVar, Number, Variable};
 use std::array;
+use std::fmt::{self, Display, Formatter};
 use ucalc_numbers::{Constant, FloatTrait};
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvalError {
+    DerivativeUnsupported,
+    MultiInputDerivative,
+    NonInteger,
+    TooManySolutions,
+    NoSolution,
+    TooLarge,
+}
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DerivativeUnsupported => {
+                write!(f, "derivative or integral notation is not supported here")
+            }
+            Self::MultiInputDerivative => write!(
+                f,
+                "can not take the derivative or integral of a function with several inputs"
+            ),
+            Self::NonInteger => write!(f, "expected an integer"),
+            Self::TooManySolutions => write!(f, "too many solutions to list"),
+            Self::NoSolution => write!(f, "the system has no unique solution"),
+            Self::TooLarge => write!(f, "input is too large to compute exactly"),
+        }
+    }
+}
 #[derive(Clone, Debug)]
 pub struct Compute<'a> {
     pub(crate) tokens: &'a TokensSlice,
@@ -21,7 +48,7 @@ impl Tokens {
         custom_funs: &[FunctionVar],
         custom_vars: &[Variable],
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         let cap = self.len() + custom_funs.iter().map(|c| c.tokens.len()).sum::<usize>();
         let mut inner_vars = Vec::with_capacity(cap);
         let mut stack = Vec::with_capacity(cap);
@@ -42,7 +69,7 @@ impl Tokens {
         custom_vars: &[Variable],
         drain: impl Iterator<Item = Number>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         let cap = self.len() + custom_funs.iter().map(|c| c.tokens.len()).sum::<usize>();
         let mut inner_vars = Vec::with_capacity(cap);
         inner_vars.extend(drain);
@@ -65,7 +92,7 @@ impl Tokens {
         custom_vars: &[Variable],
         stack: &mut Vec<StackToken>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         self.compute_buffer_with(
             inner_vars,
             graph_vars,
@@ -87,7 +114,7 @@ impl Tokens {
         stack: &mut Vec<StackToken>,
         offset: usize,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         Compute::new(&self[..], graph_vars, custom_funs, custom_vars, offset).compute(
             inner_vars,
             stack,
@@ -204,13 +231,34 @@ impl<'a> Compute<'a> {
         inner_vars: &mut Vec<Number>,
         stack: &mut Vec<StackToken>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         let mut tokens = self.tokens.iter().enumerate();
         while let Some((i, token)) = tokens.next() {
             match token {
                 &Token::Function(fun, d) => {
                     if d.get() != 0 {
-                        todo!()
+                        if fun.has_inner_fn() {
+                            return Err(EvalError::DerivativeUnsupported);
+                        }
+                        let end = inner_vars.len();
+                        let tokens = Tokens(vec![Token::InnerVar(0), fun.into()]);
+                        Compute::new(
+                            &tokens[..],
+                            self.graph_vars,
+                            self.custom_funs,
+                            self.custom_vars,
+                            end,
+                        )
+                        .compute_derivative(
+                            inner_vars,
+                            stack,
+                            d,
+                            fun.inputs().get() as usize,
+                            #[cfg(feature = "float_rand")]
+                            rand,
+                        )?;
+                        inner_vars.truncate(end);
+                        continue;
                     }
                     let inputs = fun.inputs();
                     if fun.has_inner_fn() {
@@ -220,7 +268,7 @@ impl<'a> Compute<'a> {
                             inner_vars,
                             #[cfg(feature = "float_rand")]
                             rand,
-                        )
+                        )?
                     } else if fun.is_chainable() {
                         let chain = if self.tokens.get(i + 1).is_some_and(|o| {
                             if let Token::Function(o, _) = o {
@@ -237,7 +285,7 @@ impl<'a> Compute<'a> {
                             stack,
                             #[cfg(feature = "float_rand")]
                             rand,
-                        );
+                        )?;
                         if let Some(b) = chain {
                             let len = stack.len();
                             let a = stack[len - 1].num_mut();
@@ -252,7 +300,7 @@ impl<'a> Compute<'a> {
                             stack,
                             #[cfg(feature = "float_rand")]
                             rand,
-                        );
+                        )?;
                     }
                 }
                 &Token::CustomVar(index) => {
@@ -266,55 +314,14 @@ impl<'a> Compute<'a> {
                         .offset(end)
                         .tokens(&self.custom_funs[index as usize].tokens[..]);
                     if d.get() != 0 {
-                        if d.is_integral() {
-                            if d.is_integral_twice_input() {
-                                if inputs != 1 {
-                                    todo!()
-                                }
-                                inner_vars.push(Number::default());
-                                let end = stack.pop().unwrap().num();
-                                *stack.last_mut().unwrap().num_mut() = compute
-                                    .numerical_nth_integral(
-                                        inner_vars,
-                                        stack,
-                                        d.get(),
-                                        stack.last().unwrap().num_ref().clone(),
-                                        end,
-                                        (inner_vars.len() - 1) as u16,
-                                        #[cfg(feature = "float_rand")]
-                                        rand,
-                                    );
-                            } else {
-                                if inputs != 1 {
-                                    todo!()
-                                }
-                                inner_vars.push(Number::default());
-                                *stack[len - inputs].num_mut() = compute.numerical_nth_integral(
-                                    inner_vars,
-                                    stack,
-                                    d.get(),
-                                    Number::default(),
-                                    stack[len - inputs].num_ref().clone(),
-                                    (inner_vars.len() - 1) as u16,
-                                    #[cfg(feature = "float_rand")]
-                                    rand,
-                                );
-                            }
-                        } else {
-                            if inputs != 1 {
-                                todo!()
-                            }
-                            inner_vars.push(Number::default());
-                            *stack[len - inputs].num_mut() = compute.numerical_nth_derivative(
-                                inner_vars,
-                                stack,
-                                d.get(),
-                                stack[len - inputs].num_ref().clone(),
-                                (inner_vars.len() - 1) as u16,
-                                #[cfg(feature = "float_rand")]
-                                rand,
-                            );
-                        }
+                        compute.compute_derivative(
+                            inner_vars,
+                            stack,
+                            d,
+                            inputs,
+                            #[cfg(feature = "float_rand")]
+                            rand,
+                        )?;
                     } else {
                         inner_vars.push(stack[len - inputs].num_ref().clone());
                         inner_vars.extend(stack.drain(len + 1 - inputs..).map(|n| n.num()));
@@ -323,7 +330,7 @@ impl<'a> Compute<'a> {
                             stack,
                             #[cfg(feature = "float_rand")]
                             rand,
-                        );
+                        )?;
                     }
                     inner_vars.truncate(end);
                 }
@@ -340,7 +347,73 @@ impl<'a> Compute<'a> {
                 Token::Number(n) => stack.push(n.clone().into()),
             }
         }
-        stack.pop().unwrap().num()
+        Ok(stack.pop().unwrap().num())
+    }
+    fn compute_derivative(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        d: Derivative,
+        inputs: usize,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<(), EvalError> {
+        if inputs != 1 {
+            return Err(EvalError::MultiInputDerivative);
+        }
+        inner_vars.push(Number::default());
+        let var = (inner_vars.len() - 1) as u16;
+        let (start, end) = if d.is_integral_twice_input() {
+            let end = stack.pop().unwrap().num();
+            (stack.last().unwrap().num_ref().clone(), end)
+        } else {
+            (Number::default(), stack.last().unwrap().num_ref().clone())
+        };
+        let ret = if d.is_integral() {
+            self.clone()
+                .numerical_nth_integral(
+                    inner_vars,
+                    stack,
+                    d.get(),
+                    start,
+                    end,
+                    var,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?
+                .0
+        } else if let Some(tokens) = (1..d.get()).try_fold(Tokens(self.tokens.to_vec()), |t, _| {
+            t[..].diff(&Token::InnerVar(0), self.custom_funs)
+        }) && let Some(ret) = {
+            inner_vars[var as usize] = end.clone();
+            Compute::new(
+                &tokens[..],
+                self.graph_vars,
+                self.custom_funs,
+                self.custom_vars,
+                self.offset,
+            )
+            .derivative(
+                inner_vars,
+                stack,
+                var,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?
+        } {
+            ret
+        } else {
+            self.numerical_nth_derivative(
+                inner_vars,
+                stack,
+                d.get(),
+                end,
+                var,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?
+        };
+        *stack.last_mut().unwrap().num_mut() = ret;
+        Ok(())
     }
 }
 #[derive(Debug)]
@@ -1,7 +1,9 @@
 #[cfg(feature = "float_rand")]
 use crate::Rand;
-use crate::compute::StackToken;
-use crate::{Compute, Function, Number, Token};
+use crate::compute::{EvalError, StackToken};
+use crate::parse::{Tokens, TokensSlice};
+use crate::{Compute, Function, FunctionVar, Number, Token};
+use std::mem;
 #[cfg(feature = "complex")]
 use ucalc_numbers::ComplexTrait;
 use ucalc_numbers::{
@@ -47,7 +49,7 @@ pub enum Derivative {
 impl Derivative {
     pub fn compute_on_1(self, a: &mut Number) -> Result<(), ()> {
         match self {
-            Self::Negate => a.neg_assign(),
+            Self::Negate => *a = Number::from(-1),
             Self::Sin => a.cos_mut(),
             Self::Cos => {
                 a.sin_mut();
@@ -179,6 +181,7 @@ impl Derivative {
                     a.ln_mut();
                     *a *= l;
                     *a /= b.clone() * b;
+                    a.neg_assign();
                 }
             }
             Self::Mod => {
@@ -198,6 +201,257 @@ impl Derivative {
         Ok(())
     }
 }
+impl Derivative {
+    fn tokens_on_1(self, a: &[Token]) -> Vec<Token> {
+        match self {
+            Self::Negate => num(-1),
+            Self::Sin => apply(Function::Cos, &[a]),
+            Self::Cos => neg(apply(Function::Sin, &[a])),
+            Self::Tan => div(num(1), pow(apply(Function::Cos, &[a]), num(2))),
+            Self::Ln => div(num(1), a.to_vec()),
+            Self::Exp => apply(Function::Exp, &[a]),
+            Self::Sqrt => div(num(1), mul(num(2), apply(Function::Sqrt, &[a]))),
+            Self::Cbrt => div(
+                num(1),
+                mul(num(3), pow(apply(Function::Cbrt, &[a]), num(2))),
+            ),
+            Self::Abs => div(a.to_vec(), apply(Function::Abs, &[a])),
+            Self::Recip => neg(div(num(1), pow(a.to_vec(), num(2)))),
+            Self::Ceil | Self::Floor | Self::Round | Self::Trunc => num(0),
+            Self::Fract => num(1),
+            #[cfg(feature = "complex")]
+            Self::Arg => neg(div(
+                apply(Function::Imag, &[a]),
+                pow(apply(Function::Abs, &[a]), num(2)),
+            )),
+            #[cfg(feature = "complex")]
+            Self::Conj | Self::Real => num(1),
+            #[cfg(feature = "complex")]
+            Self::Imag => num(0),
+            _ => unreachable!(),
+        }
+    }
+    fn tokens_on_2<const N: usize>(self, a: &[Token], b: &[Token]) -> Vec<Token> {
+        match self {
+            Self::Add => num(1),
+            #[cfg(feature = "complex")]
+            Self::Addi => {
+                if N == 0 {
+                    num(1)
+                } else {
+                    num((0, 1))
+                }
+            }
+            Self::Sub => num(if N == 0 { 1 } else { -1 }),
+            Self::Mul => {
+                if N == 0 {
+                    b.to_vec()
+                } else {
+                    a.to_vec()
+                }
+            }
+            Self::Div => {
+                if N == 0 {
+                    div(num(1), b.to_vec())
+                } else {
+                    neg(div(a.to_vec(), pow(b.to_vec(), num(2))))
+                }
+            }
+            #[cfg(feature = "units")]
+            Self::Convert => {
+                if N == 0 {
+                    div(num(1), b.to_vec())
+                } else {
+                    neg(div(a.to_vec(), pow(b.to_vec(), num(2))))
+                }
+            }
+            Self::Pow => {
+                if N == 0 {
+                    mul(b.to_vec(), pow(a.to_vec(), sub(b.to_vec(), num(1))))
+                } else {
+                    mul(apply(Function::Pow, &[a, b]), apply(Function::Ln, &[a]))
+                }
+            }
+            Self::Root => {
+                let r = div(num(1), b.to_vec());
+                if N == 0 {
+                    div(pow(a.to_vec(), sub(r, num(1))), b.to_vec())
+                } else {
+                    neg(div(
+                        mul(apply(Function::Root, &[a, b]), apply(Function::Ln, &[a])),
+                        pow(b.to_vec(), num(2)),
+                    ))
+                }
+            }
+            Self::Mod => {
+                if N == 0 {
+                    num(1)
+                } else {
+                    neg(apply(Function::Floor, &[&div(a.to_vec(), b.to_vec())]))
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+pub(crate) fn num<T>(n: T) -> Vec<Token>
+where
+    Number: From<T>,
+{
+    vec![Number::from(n).into()]
+}
+pub(crate) fn get_num(a: &[Token]) -> Option<&Number> {
+    if let [Token::Number(n)] = a {
+        Some(n)
+    } else {
+        None
+    }
+}
+pub(crate) fn is_num(a: &[Token], n: isize) -> bool {
+    get_num(a).is_some_and(|a| *a == Number::from(n))
+}
+pub(crate) fn apply(fun: Function, args: &[&[Token]]) -> Vec<Token> {
+    let mut ret = args.concat();
+    ret.push(fun.into());
+    ret
+}
+pub(crate) fn add(a: Vec<Token>, b: Vec<Token>) -> Vec<Token> {
+    if is_num(&a, 0) {
+        b
+    } else if is_num(&b, 0) {
+        a
+    } else if let (Some(x), Some(y)) = (get_num(&a), get_num(&b)) {
+        vec![(x.clone() + y).into()]
+    } else {
+        apply(Function::Add, &[&a, &b])
+    }
+}
+pub(crate) fn sub(a: Vec<Token>, b: Vec<Token>) -> Vec<Token> {
+    if is_num(&b, 0) {
+        a
+    } else if is_num(&a, 0) {
+        neg(b)
+    } else if let (Some(x), Some(y)) = (get_num(&a), get_num(&b)) {
+        vec![(x.clone() - y).into()]
+    } else {
+        apply(Function::Sub, &[&a, &b])
+    }
+}
+pub(crate) fn mul(a: Vec<Token>, b: Vec<Token>) -> Vec<Token> {
+    if is_num(&a, 0) || is_num(&b, 0) {
+        num(0)
+    } else if is_num(&a, 1) {
+        b
+    } else if is_num(&b, 1) {
+        a
+    } else if is_num(&a, -1) {
+        neg(b)
+    } else if is_num(&b, -1) {
+        neg(a)
+    } else if let (Some(x), Some(y)) = (get_num(&a), get_num(&b)) {
+        vec![(x.clone() * y).into()]
+    } else {
+        apply(Function::Mul, &[&a, &b])
+    }
+}
+pub(crate) fn div(a: Vec<Token>, b: Vec<Token>) -> Vec<Token> {
+    if is_num(&a, 0) || is_num(&b, 1) {
+        a
+    } else {
+        apply(Function::Div, &[&a, &b])
+    }
+}
+pub(crate) fn pow(a: Vec<Token>, b: Vec<Token>) -> Vec<Token> {
+    if is_num(&b, 1) {
+        a
+    } else if is_num(&b, 0) {
+        num(1)
+    } else {
+        apply(Function::Pow, &[&a, &b])
+    }
+}
+pub(crate) fn neg(a: Vec<Token>) -> Vec<Token> {
+    if let Some(x) = get_num(&a) {
+        let mut x = x.clone();
+        x.neg_assign();
+        vec![x.into()]
+    } else if let [a @ .., Token::Function(Function::Negate, d)] = &a[..]
+        && d.get() == 0
+    {
+        a.to_vec()
+    } else {
+        apply(Function::Negate, &[&a])
+    }
+}
+pub(crate) fn substitute(tokens: &[Token], args: &[Vec<Token>]) -> Vec<Token> {
+    let mut ret = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        match token {
+            &Token::InnerVar(i) if (i as usize) < args.len() => {
+                ret.extend_from_slice(&args[i as usize])
+            }
+            t => ret.push(t.clone()),
+        }
+    }
+    ret
+}
+impl TokensSlice {
+    pub fn diff(&self, var: &Token, custom_funs: &[FunctionVar]) -> Option<Tokens> {
+        let mut stack: Vec<(Vec<Token>, Vec<Token>)> = Vec::new();
+        for token in self.iter() {
+            match token {
+                &Token::Function(fun, d) => {
+                    if d.get() != 0 {
+                        return None;
+                    }
+                    let derivative = Derivative::try_from(fun).ok()?;
+                    match fun.inputs().get() {
+                        1 => {
+                            let (g, dg) = stack.last_mut().unwrap();
+                            let d = mul(derivative.tokens_on_1(g), mem::take(dg));
+                            g.push(token.clone());
+                            *dg = d;
+                        }
+                        2 => {
+                            let (h, dh) = stack.pop().unwrap();
+                            let (g, dg) = stack.last_mut().unwrap();
+                            let d = add(
+                                mul(derivative.tokens_on_2::<0>(g, &h), mem::take(dg)),
+                                mul(derivative.tokens_on_2::<1>(g, &h), dh),
+                            );
+                            g.extend(h);
+                            g.push(token.clone());
+                            *dg = d;
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                &Token::CustomFun(n, d) => {
+                    if d.get() != 0 {
+                        return None;
+                    }
+                    let fun = &custom_funs[n as usize];
+                    let args = stack.split_off(stack.len() - fun.inputs.get() as usize);
+                    let (args, dargs): (Vec<_>, Vec<_>) = args.into_iter().unzip();
+                    let mut d = num(0);
+                    for (i, dg) in dargs.into_iter().enumerate() {
+                        if is_num(&dg, 0) {
+                            continue;
+                        }
+                        let df = fun.tokens[..].diff(&Token::InnerVar(i as u16), custom_funs)?;
+                        d = add(d, mul(substitute(&df, &args), dg));
+                    }
+                    let mut g = args.concat();
+                    g.push(token.clone());
+                    stack.push((g, d));
+                }
+                Token::Skip(_) => return None,
+                t => stack.push((vec![t.clone()], num(if t == var { 1 } else { 0 }))),
+            }
+        }
+        stack.pop().map(|(_, d)| Tokens(d))
+    }
+}
 impl TryFrom<Function> for Derivative {
     type Error = ();
     fn try_from(value: Function) -> Result<Self, Self::Error> {
@@ -257,15 +511,15 @@ impl Compute<'_> {
         stack: &mut Vec<StackToken>,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Option<Number> {
+    ) -> Result<Option<Number>, EvalError> {
         let stack_end = stack.len();
-        let res = try {
+        let res: Result<_, Option<EvalError>> = try {
             let mut tokens = self.tokens.iter().enumerate();
             while let Some((i, token)) = tokens.next() {
                 match token {
                     &Token::CustomFun(n, d) => {
                         if d.get() != 0 {
-                            todo!()
+                            Err(Some(EvalError::DerivativeUnsupported))?
                         }
                         let end = inner_vars.len();
                         let len = stack.len();
@@ -277,28 +531,36 @@ impl Compute<'_> {
                                 .iter()
                                 .map(|a| a.diff_ref().value.clone()),
                         );
-                        stack[len - inputs].diff_mut().value = compute.compute(
-                            inner_vars,
-                            stack,
-                            #[cfg(feature = "float_rand")]
-                            rand,
-                        );
-                        let d = compute.derivative(
-                            inner_vars,
-                            stack,
-                            end as u16,
-                            #[cfg(feature = "float_rand")]
-                            rand,
-                        )?;
-                        stack[len - inputs].diff_mut().derivative *= d;
-                        for i in 1..inputs {
-                            let mut d = compute.derivative(
+                        stack[len - inputs].diff_mut().value = compute
+                            .compute(
+                                inner_vars,
+                                stack,
+                                #[cfg(feature = "float_rand")]
+                                rand,
+                            )
+                            .map_err(Some)?;
+                        let d = compute
+                            .derivative(
                                 inner_vars,
                                 stack,
-                                (end + i) as u16,
+                                end as u16,
                                 #[cfg(feature = "float_rand")]
                                 rand,
-                            )?;
+                            )
+                            .map_err(Some)?
+                            .ok_or(None)?;
+                        stack[len - inputs].diff_mut().derivative *= d;
+                        for i in 1..inputs {
+                            let mut d = compute
+                                .derivative(
+                                    inner_vars,
+                                    stack,
+                                    (end + i) as u16,
+                                    #[cfg(feature = "float_rand")]
+                                    rand,
+                                )
+                                .map_err(Some)?
+                                .ok_or(None)?;
                             d *= &stack[len - inputs + i].diff_ref().derivative;
                             stack[len - inputs].diff_mut().derivative += d;
                         }
@@ -307,14 +569,14 @@ impl Compute<'_> {
                     }
                     &Token::Function(fun, d) => {
                         if d.get() != 0 {
-                            todo!()
+                            Err(Some(EvalError::DerivativeUnsupported))?
                         }
-                        let derivative = Derivative::try_from(fun).ok()?;
+                        let derivative = Derivative::try_from(fun).map_err(|_| None)?;
                         match fun.inputs().get() {
                             1 => {
                                 let g = stack.last_mut().unwrap().diff_mut();
                                 let mut f_g = g.value.clone();
-                                derivative.compute_on_1(&mut f_g).ok()?;
+                                derivative.compute_on_1(&mut f_g).map_err(|_| None)?;
                                 fun.compute_on_1(&mut g.value);
                                 g.derivative *= f_g;
                             }
@@ -323,8 +585,12 @@ impl Compute<'_> {
                                 let g = stack.last_mut().unwrap().diff_mut();
                                 let mut d1 = g.value.clone();
                                 let mut d2 = g.value.clone();
-                                derivative.compute_on_2::<0>(&mut d1, &h.value).ok()?;
-                                derivative.compute_on_2::<1>(&mut d2, &h.value).ok()?;
+                                derivative
+                                    .compute_on_2::<0>(&mut d1, &h.value)
+                                    .map_err(|_| None)?;
+                                derivative
+                                    .compute_on_2::<1>(&mut d2, &h.value)
+                                    .map_err(|_| None)?;
                                 fun.compute_on_2(
                                     &mut g.value,
                                     h.value,
@@ -370,11 +636,9 @@ impl Compute<'_> {
             }
             stack.pop().unwrap().diff().derivative
         };
-        if let Some(res) = res {
-            Some(res)
-        } else {
+        res.map(Some).or_else(|e| {
             stack.truncate(stack_end);
-            None
-        }
+            e.map_or(Ok(None), Err)
+        })
     }
 }
@@ -0,0 +1,511 @@
+#[cfg(feature = "float_rand")]
+use crate::rand::Rand;
+use std::fmt::{Display, Formatter};
+use ucalc_numbers::{Constant, Float, FloatFunctions, FloatTrait};
+const MAX_ITER: usize = 1024;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Normal,
+    StudentT,
+    ChiSquared,
+    Binomial,
+    Poisson,
+    Exponential,
+    Uniform,
+    Gamma,
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistributionFunction {
+    Density,
+    Cdf,
+    Inverse,
+}
+impl TryFrom<&str> for Distribution {
+    type Error = ();
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "normal" => Self::Normal,
+            "student_t" => Self::StudentT,
+            "chi_squared" => Self::ChiSquared,
+            "binomial" => Self::Binomial,
+            "poisson" => Self::Poisson,
+            "exponential" => Self::Exponential,
+            "uniform" => Self::Uniform,
+            "gamma" => Self::Gamma,
+            _ => return Err(()),
+        })
+    }
+}
+impl Display for Distribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Normal => "normal",
+                Self::StudentT => "student_t",
+                Self::ChiSquared => "chi_squared",
+                Self::Binomial => "binomial",
+                Self::Poisson => "poisson",
+                Self::Exponential => "exponential",
+                Self::Uniform => "uniform",
+                Self::Gamma => "gamma",
+            }
+        )
+    }
+}
+impl Distribution {
+    pub fn params(self) -> u8 {
+        match self {
+            Self::Normal | Self::Binomial | Self::Uniform | Self::Gamma => 2,
+            Self::StudentT | Self::ChiSquared | Self::Poisson | Self::Exponential => 1,
+        }
+    }
+    pub fn is_discrete(self) -> bool {
+        matches!(self, Self::Binomial | Self::Poisson)
+    }
+    pub fn function(self, suffix: &str) -> Option<DistributionFunction> {
+        Some(match suffix {
+            "pdf" if !self.is_discrete() => DistributionFunction::Density,
+            "pmf" if self.is_discrete() => DistributionFunction::Density,
+            "cdf" => DistributionFunction::Cdf,
+            "inv" => DistributionFunction::Inverse,
+            _ => return None,
+        })
+    }
+    pub fn suffix(self, function: DistributionFunction) -> &'static str {
+        match function {
+            DistributionFunction::Density if self.is_discrete() => "pmf",
+            DistributionFunction::Density => "pdf",
+            DistributionFunction::Cdf => "cdf",
+            DistributionFunction::Inverse => "inv",
+        }
+    }
+    pub fn compute(self, function: DistributionFunction, x: Float, params: &[Float]) -> Float {
+        if !self.is_valid(params) {
+            return nan();
+        }
+        match function {
+            DistributionFunction::Density => self.density(x, params),
+            DistributionFunction::Cdf => self.cdf(x, params),
+            DistributionFunction::Inverse if x >= zero() && x <= one() => self.inverse(x, params),
+            DistributionFunction::Inverse => nan(),
+        }
+    }
+    #[cfg(feature = "float_rand")]
+    pub fn sample(self, params: &[Float], rand: &mut Rand) -> Float {
+        let mut u = zero();
+        u.random_range_mut(one(), rand);
+        self.compute(DistributionFunction::Inverse, u, params)
+    }
+    fn is_valid(self, params: &[Float]) -> bool {
+        let positive = |n: &Float| *n > zero();
+        match (self, params) {
+            (Self::Normal, [_, s]) => positive(s),
+            (Self::StudentT | Self::ChiSquared | Self::Poisson | Self::Exponential, [a]) => {
+                positive(a)
+            }
+            (Self::Binomial, [n, p]) => {
+                *n >= zero() && is_integer(n) && *p >= zero() && *p <= one()
+            }
+            (Self::Uniform, [a, b]) => a < b,
+            (Self::Gamma, [k, t]) => positive(k) && positive(t),
+            _ => false,
+        }
+    }
+    fn density(self, x: Float, params: &[Float]) -> Float {
+        match (self, params) {
+            (Self::Normal, [m, s]) => {
+                let z = (x - m) / s;
+                (-(z.clone() * z) / Float::from(2)).exp()
+                    / (s.clone() * Float::from(Constant::Tau).sqrt())
+            }
+            (Self::StudentT, [v]) => {
+                let half = (v.clone() + one()) / Float::from(2);
+                (ln_gamma(&half)
+                    - ln_gamma(&(v.clone() / Float::from(2)))
+                    - half * (one() + x.clone() * x / v).ln())
+                .exp()
+                    / (v.clone() * Float::from(Constant::Pi)).sqrt()
+            }
+            (Self::ChiSquared, [k]) => gamma_density(x, &(k.clone() / Float::from(2)), &two()),
+            (Self::Gamma, [k, t]) => gamma_density(x, k, t),
+            (Self::Binomial, [n, p]) => {
+                if x < zero() || x > *n || !is_integer(&x) {
+                    return zero();
+                }
+                let rest = n.clone() - &x;
+                (ln_gamma(&(n.clone() + one()))
+                    - ln_gamma(&(x.clone() + one()))
+                    - ln_gamma(&(rest.clone() + one()))
+                    + xlny(x, p.clone())
+                    + xlny(rest, one() - p))
+                .exp()
+            }
+            (Self::Poisson, [l]) => {
+                if x < zero() || !is_integer(&x) {
+                    return zero();
+                }
+                (xlny(x.clone(), l.clone()) - l - ln_gamma(&(x + one()))).exp()
+            }
+            (Self::Exponential, [l]) => {
+                if x < zero() {
+                    return zero();
+                }
+                l.clone() * (-(l.clone() * x)).exp()
+            }
+            (Self::Uniform, [a, b]) => {
+                if x < *a || x > *b {
+                    return zero();
+                }
+                one() / (b.clone() - a)
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn cdf(self, x: Float, params: &[Float]) -> Float {
+        match (self, params) {
+            (Self::Normal, [m, s]) => {
+                (-(x - m) / (s.clone() * two().sqrt())).erfc() / Float::from(2)
+            }
+            (Self::StudentT, [v]) => {
+                let t = v.clone() / (v.clone() + x.clone() * x.clone());
+                let tail = beta_inc(&(v.clone() / Float::from(2)), &half(), &t) / two();
+                if x < zero() { tail } else { one() - tail }
+            }
+            (Self::ChiSquared, [k]) => {
+                gamma_pq(&(k.clone() / Float::from(2)), &(x / Float::from(2))).0
+            }
+            (Self::Gamma, [k, t]) => gamma_pq(k, &(x / t)).0,
+            (Self::Binomial, [n, p]) => {
+                if x < zero() {
+                    return zero();
+                }
+                if x >= *n {
+                    return one();
+                }
+                let k = x.floor();
+                beta_inc(&(n.clone() - &k), &(k + one()), &(one() - p))
+            }
+            (Self::Poisson, [l]) => {
+                if x < zero() {
+                    return zero();
+                }
+                gamma_pq(&(x.floor() + one()), l).1
+            }
+            (Self::Exponential, [l]) => {
+                if x <= zero() {
+                    return zero();
+                }
+                one() - (-(l.clone() * x)).exp()
+            }
+            (Self::Uniform, [a, b]) => {
+                if x <= *a {
+                    zero()
+                } else if x >= *b {
+                    one()
+                } else {
+                    (x - a) / (b.clone() - a)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn inverse(self, p: Float, params: &[Float]) -> Float {
+        let cdf = |x: &Float| self.cdf(x.clone(), params);
+        match (self, params) {
+            (Self::Normal, [m, s]) => m.clone() + s.clone() * normal_quantile(p),
+            (Self::StudentT, _) => {
+                if p < half() {
+                    -self.inverse(one() - p, params)
+                } else if p == one() {
+                    Float::from(Constant::Infinity)
+                } else {
+                    bisect(&p, one(), cdf)
+                }
+            }
+            (Self::ChiSquared, [k]) => bisect(&p, k.clone(), cdf),
+            (Self::Gamma, [k, t]) => bisect(&p, k.clone() * t, cdf),
+            (Self::Binomial, [n, _]) => search_discrete(&p, n, cdf),
+            (Self::Poisson, _) => search_discrete(&p, &Float::from(Constant::Infinity), cdf),
+            (Self::Exponential, [l]) => -(one() - p).ln() / l,
+            (Self::Uniform, [a, b]) => a.clone() + p * (b.clone() - a),
+            _ => unreachable!(),
+        }
+    }
+}
+fn zero() -> Float {
+    Float::from(0)
+}
+fn one() -> Float {
+    Float::from(1)
+}
+fn two() -> Float {
+    Float::from(2)
+}
+fn half() -> Float {
+    Float::from(0.5)
+}
+fn nan() -> Float {
+    Float::from(Constant::Nan)
+}
+fn is_integer(n: &Float) -> bool {
+    n.clone().fract().is_zero()
+}
+fn epsilon() -> Float {
+    let mut eps = one();
+    for _ in 0..64 {
+        let next = eps.clone() / two();
+        if one() + &next == one() {
+            break;
+        }
+        eps = next;
+    }
+    eps
+}
+fn xlny(x: Float, y: Float) -> Float {
+    if x.is_zero() { zero() } else { x * y.ln() }
+}
+fn poly(coefficients: &[f64], x: &Float) -> Float {
+    coefficients
+        .iter()
+        .fold(zero(), |acc, &c| acc * x + Float::from(c))
+}
+fn ln_gamma(x: &Float) -> Float {
+    if *x <= Float::from(20) {
+        return x.clone().gamma().abs().ln();
+    }
+    let inv = one() / x;
+    let series = poly(
+        &[
+            1.0 / 156.0,
+            -691.0 / 360360.0,
+            1.0 / 1188.0,
+            -1.0 / 1680.0,
+            1.0 / 1260.0,
+            -1.0 / 360.0,
+            1.0 / 12.0,
+        ],
+        &(inv.clone() * &inv),
+    ) * inv;
+    (x.clone() - half()) * x.clone().ln() - x + Float::from(Constant::Tau).ln() / two() + series
+}
+fn gamma_density(x: Float, k: &Float, t: &Float) -> Float {
+    if x < zero() {
+        return zero();
+    }
+    if x.is_zero() {
+        return match k.partial_cmp(&one()) {
+            Some(std::cmp::Ordering::Less) => Float::from(Constant::Infinity),
+            Some(std::cmp::Ordering::Equal) => one() / t,
+            _ => zero(),
+        };
+    }
+    ((k.clone() - one()) * x.clone().ln() - x / t - ln_gamma(k) - k.clone() * t.clone().ln()).exp()
+}
+fn gamma_pq(a: &Float, x: &Float) -> (Float, Float) {
+    if *x <= zero() {
+        return (zero(), one());
+    }
+    if *x == Float::from(Constant::Infinity) {
+        return (one(), zero());
+    }
+    let eps = epsilon();
+    let front = (a.clone() * x.clone().ln() - x - ln_gamma(a)).exp();
+    if *x < a.clone() + one() {
+        let mut ap = a.clone();
+        let mut del = one() / a;
+        let mut sum = del.clone();
+        for _ in 0..MAX_ITER {
+            ap += one();
+            del *= x.clone() / &ap;
+            sum += &del;
+            if del.clone().abs() < sum.clone().abs() * &eps {
+                break;
+            }
+        }
+        let p = sum * front;
+        (p.clone(), one() - p)
+    } else {
+        let tiny = eps.clone() * &eps * &eps;
+        let clamp = |n: Float| {
+            if n.clone().abs() < tiny {
+                tiny.clone()
+            } else {
+                n
+            }
+        };
+        let mut b = x.clone() + one() - a;
+        let mut c = one() / &tiny;
+        let mut d = one() / &b;
+        let mut h = d.clone();
+        for i in 1..MAX_ITER {
+            let i = Float::from(i);
+            let an = -(i.clone() * (i - a));
+            b += two();
+            d = one() / clamp(an.clone() * d + &b);
+            c = clamp(b.clone() + an / c);
+            let del = d.clone() * &c;
+            h *= &del;
+            if (del - one()).abs() < eps {
+                break;
+            }
+        }
+        let q = front * h;
+        (one() - &q, q)
+    }
+}
+fn beta_inc(a: &Float, b: &Float, x: &Float) -> Float {
+    if *x <= zero() {
+        return zero();
+    }
+    if *x >= one() {
+        return one();
+    }
+    let y = one() - x;
+    let front = (ln_gamma(&(a.clone() + b)) - ln_gamma(a) - ln_gamma(b)
+        + a.clone() * x.clone().ln()
+        + b.clone() * y.clone().ln())
+    .exp();
+    if *x < (a.clone() + one()) / (a.clone() + b + two()) {
+        front * beta_cf(a, b, x) / a
+    } else {
+        one() - front * beta_cf(b, a, &y) / b
+    }
+}
+fn beta_cf(a: &Float, b: &Float, x: &Float) -> Float {
+    let eps = epsilon();
+    let tiny = eps.clone() * &eps * &eps;
+    let clamp = |n: Float| {
+        if n.clone().abs() < tiny {
+            tiny.clone()
+        } else {
+            n
+        }
+    };
+    let qab = a.clone() + b;
+    let qap = a.clone() + one();
+    let qam = a.clone() - one();
+    let mut c = one();
+    let mut d = one() / clamp(one() - qab.clone() * x / &qap);
+    let mut h = d.clone();
+    for m in 1..MAX_ITER {
+        let m = Float::from(m);
+        let m2 = m.clone() * two();
+        let aa = m.clone() * (b.clone() - &m) * x / ((qam.clone() + &m2) * (a.clone() + &m2));
+        d = one() / clamp(one() + aa.clone() * d);
+        c = clamp(one() + aa / c);
+        h *= d.clone() * &c;
+        let aa = -((a.clone() + &m) * (qab.clone() + &m) * x)
+            / ((a.clone() + &m2) * (qap.clone() + &m2));
+        d = one() / clamp(one() + aa.clone() * d);
+        c = clamp(one() + aa / c);
+        let del = d.clone() * &c;
+        h *= &del;
+        if (del - one()).abs() < eps {
+            break;
+        }
+    }
+    h
+}
+fn normal_quantile(p: Float) -> Float {
+    if p.is_zero() {
+        return Float::from(Constant::NegInfinity);
+    }
+    if p == one() {
+        return Float::from(Constant::Infinity);
+    }
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 6] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+        1.0,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 5] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+        1.0,
+    ];
+    let low = Float::from(0.02425);
+    let mut x = if p < low {
+        let q = (-two() * p.clone().ln()).sqrt();
+        poly(&C, &q) / poly(&D, &q)
+    } else if p > one() - &low {
+        let q = (-two() * (one() - &p).ln()).sqrt();
+        -poly(&C, &q) / poly(&D, &q)
+    } else {
+        let q = p.clone() - half();
+        let r = q.clone() * &q;
+        poly(&A, &r) * q / poly(&B, &r)
+    };
+    for _ in 0..2 {
+        let e = (-x.clone() / two().sqrt()).erfc() / two() - &p;
+        let u = e * Float::from(Constant::Tau).sqrt() * (x.clone() * &x / two()).exp();
+        x -= u.clone() / (one() + x.clone() * u / two());
+    }
+    x
+}
+fn bisect(p: &Float, mut hi: Float, cdf: impl Fn(&Float) -> Float) -> Float {
+    if p.is_zero() {
+        return zero();
+    }
+    if *p == one() {
+        return Float::from(Constant::Infinity);
+    }
+    let mut lo = zero();
+    for _ in 0..MAX_ITER {
+        if cdf(&hi) >= *p {
+            break;
+        }
+        lo = hi.clone();
+        hi *= two();
+    }
+    let eps = epsilon();
+    for _ in 0..MAX_ITER {
+        let mid = (lo.clone() + &hi) / two();
+        if hi.clone() - &lo <= eps.clone() * mid.clone().abs() || mid == lo || mid == hi {
+            break;
+        }
+        if cdf(&mid) < *p { lo = mid } else { hi = mid }
+    }
+    (lo + hi) / two()
+}
+fn search_discrete(p: &Float, max: &Float, cdf: impl Fn(&Float) -> Float) -> Float {
+    if *p == one() && *max == Float::from(Constant::Infinity) {
+        return max.clone();
+    }
+    let mut lo = -one();
+    let mut hi = one();
+    while hi < *max && cdf(&hi) < *p {
+        lo = hi.clone();
+        hi *= two();
+    }
+    if hi > *max {
+        hi = max.clone();
+    }
+    while hi.clone() - &lo > one() {
+        let mid = ((lo.clone() + &hi) / two()).floor();
+        if cdf(&mid) < *p { lo = mid } else { hi = mid }
+    }
+    hi
+}
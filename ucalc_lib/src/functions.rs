@@ -1,17 +1,26 @@
-use crate::compute::{Compute, StackToken};
+use crate::compute::{Compute, EvalError, StackToken};
+use crate::distributions::{Distribution, DistributionFunction};
 use crate::polynomial::PolyRef;
+#[cfg(feature = "list")]
+use crate::polynomial::root_list;
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
 use crate::{Number, Volatility};
+#[cfg(feature = "list")]
+use crate::{lists, statistics};
 use std::fmt::{Display, Formatter};
-use std::mem;
 use std::num::NonZeroU8;
 use std::ops::Deref;
+use std::{iter, mem};
 #[cfg(feature = "complex")]
 use ucalc_numbers::{ComplexFunctions, ComplexFunctionsMut, ComplexTrait};
 use ucalc_numbers::{
-    Constant, Float, FloatFunctions, FloatFunctionsMut, FloatTrait, NegAssign, PowAssign, RealTrait,
+    Constant, Float, FloatFunctions, FloatFunctionsMut, FloatTrait, Integer, NegAssign, PowAssign,
+    RealTrait,
 };
+#[cfg(feature = "matrix")]
+use ucalc_numbers::{HalfUsize, Matrix};
+const MAX_FALLING: usize = 1 << 12;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AtanInputs {
     One,
@@ -60,8 +69,8 @@ pub enum Function {
     Ln,
     Exp,
     Atan(AtanInputs),
-    Max,
-    Min,
+    Max(NonZeroU8),
+    Min(NonZeroU8),
     Quadratic,
     #[cfg(feature = "complex")]
     Cubic,
@@ -92,6 +101,69 @@ pub enum Function {
     #[cfg(feature = "units")]
     Convert,
     Fract,
+    Gcd,
+    Lcm,
+    IsPrime,
+    #[cfg(feature = "list")]
+    Factor,
+    NextPrime,
+    ModPow,
+    ModInv,
+    Totient,
+    #[cfg(feature = "list")]
+    Divisors,
+    Sigma,
+    Binomial,
+    Perm,
+    Multinomial(NonZeroU8),
+    StirlingFirst,
+    StirlingSecond,
+    Bell,
+    Catalan,
+    Partitions,
+    #[cfg(feature = "list")]
+    Len,
+    #[cfg(feature = "list")]
+    Mean,
+    #[cfg(feature = "list")]
+    Median,
+    #[cfg(feature = "list")]
+    Mode,
+    #[cfg(feature = "list")]
+    Var,
+    #[cfg(feature = "list")]
+    SampleVar,
+    #[cfg(feature = "list")]
+    Stddev,
+    #[cfg(feature = "list")]
+    SampleStddev,
+    #[cfg(feature = "list")]
+    Percentile,
+    #[cfg(feature = "list")]
+    Covariance,
+    #[cfg(feature = "list")]
+    Correlation,
+    #[cfg(feature = "list")]
+    Linreg,
+    #[cfg(feature = "list")]
+    Range(ModifyInputs),
+    #[cfg(feature = "list")]
+    Sort,
+    #[cfg(feature = "list")]
+    Reverse,
+    #[cfg(feature = "list")]
+    Concat,
+    #[cfg(feature = "list")]
+    Zip,
+    #[cfg(feature = "list")]
+    Slice(ModifyInputs),
+    #[cfg(feature = "list")]
+    Map,
+    #[cfg(feature = "list")]
+    Filter,
+    Distribution(Distribution, DistributionFunction),
+    #[cfg(feature = "float_rand")]
+    RandDistribution(Distribution),
     #[cfg(feature = "complex")]
     Real,
     #[cfg(feature = "complex")]
@@ -104,14 +176,42 @@ pub enum Function {
     Exprs(NonZeroU8),
     Solve,
     Derivative,
+    Diff,
     NumericalDerivative,
     NumericalDifferential,
+    #[cfg(feature = "list")]
+    NumericalTrajectory,
     NumericalIntegral,
+    IntegralError,
     NumericalSolve,
+    NumericalBracket,
+    #[cfg(feature = "list")]
+    NumericalRoots,
+    #[cfg(feature = "list")]
+    SolveRange,
+    #[cfg(feature = "vector")]
+    Vector(NonZeroU8),
+    #[cfg(any(feature = "vector", feature = "list"))]
+    Index(ModifyInputs),
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    System(NonZeroU8),
 }
 impl TryFrom<&str> for Function {
     type Error = ();
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some((d, f)) = value.rsplit_once('_')
+            && let Ok(d) = Distribution::try_from(d)
+            && let Some(f) = d.function(f)
+        {
+            return Ok(Self::Distribution(d, f));
+        }
+        #[cfg(feature = "float_rand")]
+        if let Some(d) = value.strip_prefix("rand_")
+            && let Ok(d) = Distribution::try_from(d)
+            && d != Distribution::Uniform
+        {
+            return Ok(Self::RandDistribution(d));
+        }
         Ok(match value {
             "exp" => Self::Exp,
             "asin" => Self::Asin,
@@ -119,8 +219,8 @@ impl TryFrom<&str> for Function {
             "asinh" => Self::Asinh,
             "acosh" => Self::Acosh,
             "ln" => Self::Ln,
-            "min" => Self::Min,
-            "max" => Self::Max,
+            "min" => Self::Min(NonZeroU8::new(2).unwrap()),
+            "max" => Self::Max(NonZeroU8::new(2).unwrap()),
             "sin" => Self::Sin,
             "cos" => Self::Cos,
             "sinh" => Self::Sinh,
@@ -157,6 +257,66 @@ impl TryFrom<&str> for Function {
             "round" => Self::Round,
             "trunc" => Self::Trunc,
             "fract" => Self::Fract,
+            "gcd" => Self::Gcd,
+            "lcm" => Self::Lcm,
+            "isprime" => Self::IsPrime,
+            #[cfg(feature = "list")]
+            "factor" => Self::Factor,
+            "nextprime" => Self::NextPrime,
+            "modpow" => Self::ModPow,
+            "modinv" => Self::ModInv,
+            "totient" => Self::Totient,
+            #[cfg(feature = "list")]
+            "divisors" => Self::Divisors,
+            "sigma" => Self::Sigma,
+            "binomial" | "choose" => Self::Binomial,
+            "perm" => Self::Perm,
+            "multinomial" => Self::Multinomial(NonZeroU8::new(1).unwrap()),
+            "stirling_first" => Self::StirlingFirst,
+            "stirling_second" => Self::StirlingSecond,
+            "bell" => Self::Bell,
+            "catalan" => Self::Catalan,
+            "partitions" => Self::Partitions,
+            #[cfg(feature = "list")]
+            "len" => Self::Len,
+            #[cfg(feature = "list")]
+            "mean" => Self::Mean,
+            #[cfg(feature = "list")]
+            "median" => Self::Median,
+            #[cfg(feature = "list")]
+            "mode" => Self::Mode,
+            #[cfg(feature = "list")]
+            "var" => Self::Var,
+            #[cfg(feature = "list")]
+            "sample_var" => Self::SampleVar,
+            #[cfg(feature = "list")]
+            "stddev" => Self::Stddev,
+            #[cfg(feature = "list")]
+            "sample_stddev" => Self::SampleStddev,
+            #[cfg(feature = "list")]
+            "percentile" => Self::Percentile,
+            #[cfg(feature = "list")]
+            "covariance" => Self::Covariance,
+            #[cfg(feature = "list")]
+            "correlation" => Self::Correlation,
+            #[cfg(feature = "list")]
+            "linreg" => Self::Linreg,
+            #[cfg(feature = "list")]
+            "range" => Self::Range(ModifyInputs::Two),
+            #[cfg(feature = "list")]
+            "sort" => Self::Sort,
+            #[cfg(feature = "list")]
+            "reverse" => Self::Reverse,
+            #[cfg(feature = "list")]
+            "concat" => Self::Concat,
+            #[cfg(feature = "list")]
+            "zip" => Self::Zip,
+            #[cfg(feature = "list")]
+            "slice" => Self::Slice(ModifyInputs::Two),
+            #[cfg(feature = "list")]
+            "map" => Self::Map,
+            #[cfg(feature = "list")]
+            "filter" => Self::Filter,
             #[cfg(feature = "complex")]
             "real" => Self::Real,
             #[cfg(feature = "complex")]
@@ -178,10 +338,19 @@ impl TryFrom<&str> for Function {
             #[cfg(feature = "units")]
             "convert" => Self::Convert,
             "numerical_differential" => Self::NumericalDifferential,
+            #[cfg(feature = "list")]
+            "numerical_trajectory" => Self::NumericalTrajectory,
             "numerical_derivative" => Self::NumericalDerivative,
             "derivative" => Self::Derivative,
+            "diff" => Self::Diff,
             "numerical_integral" => Self::NumericalIntegral,
+            "integral_error" => Self::IntegralError,
             "numerical_solve" => Self::NumericalSolve,
+            "numerical_bracket" => Self::NumericalBracket,
+            #[cfg(feature = "list")]
+            "numerical_roots" => Self::NumericalRoots,
+            #[cfg(feature = "list")]
+            "solve_range" => Self::SolveRange,
             "tetration" => Self::Tetration,
             "root" => Self::Root,
             "rem" => Self::Mod,
@@ -197,12 +366,23 @@ impl TryFrom<&str> for Function {
             "and" => Self::And,
             "or" => Self::Or,
             "not" => Self::Not,
+            #[cfg(feature = "vector")]
+            "vector" => Self::Vector(NonZeroU8::new(1).unwrap()),
+            #[cfg(any(feature = "vector", feature = "list"))]
+            "index" => Self::Index(ModifyInputs::Two),
             _ => return Err(()),
         })
     }
 }
 impl Display for Function {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Self::Distribution(d, function) = self {
+            return write!(f, "{d}_{}", d.suffix(*function));
+        }
+        #[cfg(feature = "float_rand")]
+        if let Self::RandDistribution(d) = self {
+            return write!(f, "rand_{d}");
+        }
         write!(
             f,
             "{}",
@@ -213,8 +393,8 @@ impl Display for Function {
                 Self::Asinh => "asinh",
                 Self::Acosh => "acosh",
                 Self::Ln => "ln",
-                Self::Min => "min",
-                Self::Max => "max",
+                Self::Min(_) => "min",
+                Self::Max(_) => "max",
                 Self::Sin => "sin",
                 Self::Cos => "cos",
                 Self::Sinh => "sinh",
@@ -251,13 +431,85 @@ impl Display for Function {
                 Self::Round => "round",
                 Self::Trunc => "trunc",
                 Self::Fract => "fract",
+                Self::Gcd => "gcd",
+                Self::Lcm => "lcm",
+                Self::IsPrime => "isprime",
+                #[cfg(feature = "list")]
+                Self::Factor => "factor",
+                Self::NextPrime => "nextprime",
+                Self::ModPow => "modpow",
+                Self::ModInv => "modinv",
+                Self::Totient => "totient",
+                #[cfg(feature = "list")]
+                Self::Divisors => "divisors",
+                Self::Sigma => "sigma",
+                Self::Binomial => "binomial",
+                Self::Perm => "perm",
+                Self::Multinomial(_) => "multinomial",
+                Self::StirlingFirst => "stirling_first",
+                Self::StirlingSecond => "stirling_second",
+                Self::Bell => "bell",
+                Self::Catalan => "catalan",
+                Self::Partitions => "partitions",
+                #[cfg(feature = "list")]
+                Self::Len => "len",
+                #[cfg(feature = "list")]
+                Self::Mean => "mean",
+                #[cfg(feature = "list")]
+                Self::Median => "median",
+                #[cfg(feature = "list")]
+                Self::Mode => "mode",
+                #[cfg(feature = "list")]
+                Self::Var => "var",
+                #[cfg(feature = "list")]
+                Self::SampleVar => "sample_var",
+                #[cfg(feature = "list")]
+                Self::Stddev => "stddev",
+                #[cfg(feature = "list")]
+                Self::SampleStddev => "sample_stddev",
+                #[cfg(feature = "list")]
+                Self::Percentile => "percentile",
+                #[cfg(feature = "list")]
+                Self::Covariance => "covariance",
+                #[cfg(feature = "list")]
+                Self::Correlation => "correlation",
+                #[cfg(feature = "list")]
+                Self::Linreg => "linreg",
+                #[cfg(feature = "list")]
+                Self::Range(_) => "range",
+                #[cfg(feature = "list")]
+                Self::Sort => "sort",
+                #[cfg(feature = "list")]
+                Self::Reverse => "reverse",
+                #[cfg(feature = "list")]
+                Self::Concat => "concat",
+                #[cfg(feature = "list")]
+                Self::Zip => "zip",
+                #[cfg(feature = "list")]
+                Self::Slice(_) => "slice",
+                #[cfg(feature = "list")]
+                Self::Map => "map",
+                #[cfg(feature = "list")]
+                Self::Filter => "filter",
+                Self::Distribution(..) => unreachable!(),
+                #[cfg(feature = "float_rand")]
+                Self::RandDistribution(_) => unreachable!(),
                 #[cfg(feature = "units")]
                 Self::Convert => "convert",
                 Self::NumericalDifferential => "numerical_differential",
+                #[cfg(feature = "list")]
+                Self::NumericalTrajectory => "numerical_trajectory",
                 Self::NumericalDerivative => "numerical_derivative",
                 Self::Derivative => "derivative",
+                Self::Diff => "diff",
                 Self::NumericalIntegral => "numerical_integral",
+                Self::IntegralError => "integral_error",
                 Self::NumericalSolve => "numerical_solve",
+                Self::NumericalBracket => "numerical_bracket",
+                #[cfg(feature = "list")]
+                Self::NumericalRoots => "numerical_roots",
+                #[cfg(feature = "list")]
+                Self::SolveRange => "solve_range",
                 #[cfg(feature = "complex")]
                 Self::Real => "real",
                 #[cfg(feature = "complex")]
@@ -291,6 +543,12 @@ impl Display for Function {
                 Self::And => "and",
                 Self::Or => "or",
                 Self::Not => "not",
+                #[cfg(feature = "vector")]
+                Self::Vector(_) => "vector",
+                #[cfg(any(feature = "vector", feature = "list"))]
+                Self::Index(_) => "index",
+                #[cfg(all(feature = "vector", feature = "matrix"))]
+                Self::System(_) => "solve",
             }
         )
     }
@@ -302,10 +560,22 @@ impl Function {
             Self::Modify(a) if inputs.get() == 3 => *a = ModifyInputs::Three,
             Self::While(a) if inputs.get() == 3 => *a = ModifyInputs::Three,
             Self::Exprs(a) => *a = inputs,
+            Self::Multinomial(a) => *a = inputs,
+            Self::Min(a) | Self::Max(a) => *a = inputs,
+            #[cfg(feature = "vector")]
+            Self::Vector(a) => *a = inputs,
+            #[cfg(feature = "list")]
+            Self::Range(a) | Self::Slice(a) if inputs.get() == 3 => *a = ModifyInputs::Three,
+            #[cfg(any(feature = "vector", feature = "list"))]
+            Self::Index(a) if inputs.get() == 3 => *a = ModifyInputs::Three,
             _ => {}
         }
     }
     pub fn is_default_inputs(self) -> bool {
+        #[cfg(feature = "list")]
+        if let Self::Range(ModifyInputs::Three) | Self::Slice(ModifyInputs::Three) = self {
+            return false;
+        }
         !matches!(
             self,
             Self::Atan(AtanInputs::Two)
@@ -316,7 +586,7 @@ impl Function {
     pub fn volatility(self) -> Volatility {
         match self {
             #[cfg(feature = "float_rand")]
-            Self::RandUniform => Volatility::Volatile,
+            Self::RandUniform | Self::RandDistribution(_) => Volatility::Volatile,
             _ => Volatility::Constant,
         }
     }
@@ -354,7 +624,40 @@ impl Function {
             | Self::Round
             | Self::Trunc
             | Self::Fract
+            | Self::IsPrime
+            | Self::NextPrime
+            | Self::Totient
+            | Self::Sigma
+            | Self::Bell
+            | Self::Catalan
+            | Self::Partitions
             | Self::Solve => 1,
+            #[cfg(feature = "list")]
+            Self::Factor | Self::Divisors => 1,
+            #[cfg(feature = "list")]
+            Self::Len
+            | Self::Mean
+            | Self::Median
+            | Self::Mode
+            | Self::Var
+            | Self::SampleVar
+            | Self::Stddev
+            | Self::SampleStddev
+            | Self::Sort
+            | Self::Reverse => 1,
+            #[cfg(feature = "list")]
+            Self::Percentile
+            | Self::Covariance
+            | Self::Correlation
+            | Self::Linreg
+            | Self::Range(ModifyInputs::Two)
+            | Self::Concat
+            | Self::Zip
+            | Self::Slice(ModifyInputs::Two)
+            | Self::Map
+            | Self::Filter => 2,
+            #[cfg(feature = "list")]
+            Self::Range(ModifyInputs::Three) | Self::Slice(ModifyInputs::Three) => 3,
             #[cfg(feature = "complex")]
             Self::Arg | Self::Conj | Self::Real | Self::Imag => 1,
             #[cfg(feature = "complex")]
@@ -376,13 +679,19 @@ impl Function {
             | Self::And
             | Self::Or
             | Self::Atan(AtanInputs::Two)
-            | Self::Max
-            | Self::Min
+            | Self::Gcd
+            | Self::Lcm
+            | Self::ModInv
+            | Self::Binomial
+            | Self::Perm
+            | Self::StirlingFirst
+            | Self::StirlingSecond
             | Self::Set
             | Self::Modify(ModifyInputs::Two)
             | Self::While(ModifyInputs::Two)
             | Self::NumericalDerivative
             | Self::Derivative
+            | Self::Diff
             | Self::NumericalSolve => 2,
             #[cfg(feature = "float_rand")]
             Self::RandUniform => 2,
@@ -393,15 +702,33 @@ impl Function {
             | Self::Prod
             | Self::Iter
             | Self::If
+            | Self::ModPow
             | Self::Modify(ModifyInputs::Three)
             | Self::While(ModifyInputs::Three)
-            | Self::NumericalIntegral => 3,
+            | Self::NumericalIntegral
+            | Self::IntegralError
+            | Self::NumericalBracket => 3,
+            #[cfg(feature = "list")]
+            Self::NumericalRoots | Self::SolveRange => 3,
             Self::Fold | Self::NumericalDifferential => 4,
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory => 4,
             #[cfg(feature = "complex")]
             Self::Cubic => 4,
             #[cfg(feature = "complex")]
             Self::Quartic => 5,
-            Self::Exprs(n) => return n,
+            Self::Exprs(n) | Self::Multinomial(n) | Self::Min(n) | Self::Max(n) => return n,
+            Self::Distribution(d, _) => d.params() + 1,
+            #[cfg(feature = "float_rand")]
+            Self::RandDistribution(d) => d.params(),
+            #[cfg(any(feature = "vector", feature = "list"))]
+            Self::Index(ModifyInputs::Two) => 2,
+            #[cfg(any(feature = "vector", feature = "list"))]
+            Self::Index(ModifyInputs::Three) => 3,
+            #[cfg(feature = "vector")]
+            Self::Vector(n) => return n,
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            Self::System(n) => return n,
         })
         .unwrap()
     }
@@ -420,8 +747,27 @@ impl Function {
         self,
         mut stack: impl Iterator<Item = Number>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
-        match self.inputs().get() {
+    ) -> Result<Number, EvalError> {
+        #[cfg(feature = "vector")]
+        if let Self::Vector(_) = self {
+            return Ok(Self::vector(stack));
+        }
+        if let Self::Min(n) | Self::Max(n) = self
+            && n.get() != 2
+        {
+            return Ok(self.extremum(stack));
+        }
+        if self.is_integer() {
+            return self.compute_integer(stack);
+        }
+        if self.is_distribution() {
+            return Ok(self.compute_distribution(
+                stack,
+                #[cfg(feature = "float_rand")]
+                rand,
+            ));
+        }
+        Ok(match self.inputs().get() {
             1 => {
                 let mut a = stack.next().unwrap();
                 self.compute_on_1(&mut a);
@@ -465,13 +811,44 @@ impl Function {
                 a
             }
             _ => unreachable!(),
-        }
+        })
     }
     pub fn compute(
         self,
         stack: &mut Vec<StackToken>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) {
+    ) -> Result<(), EvalError> {
+        #[cfg(feature = "vector")]
+        if let Self::Vector(n) = self {
+            let len = stack.len() - n.get() as usize;
+            let vector = Self::vector(stack.drain(len..).map(|n| n.num()));
+            stack.push(vector.into());
+            return Ok(());
+        }
+        if let Self::Min(n) | Self::Max(n) = self
+            && n.get() != 2
+        {
+            let len = stack.len() - n.get() as usize;
+            let n = self.extremum(stack.drain(len..).map(|n| n.num()));
+            stack.push(n.into());
+            return Ok(());
+        }
+        if self.is_integer() {
+            let len = stack.len() - self.inputs().get() as usize;
+            let n = self.compute_integer(stack.drain(len..).map(|n| n.num()))?;
+            stack.push(n.into());
+            return Ok(());
+        }
+        if self.is_distribution() {
+            let len = stack.len() - self.inputs().get() as usize;
+            let n = self.compute_distribution(
+                stack.drain(len..).map(|n| n.num()),
+                #[cfg(feature = "float_rand")]
+                rand,
+            );
+            stack.push(n.into());
+            return Ok(());
+        }
         match self.inputs().get() {
             1 => self.compute_on_1(stack.last_mut().unwrap().num_mut()),
             2 => {
@@ -509,6 +886,196 @@ impl Function {
             }
             _ => unreachable!(),
         }
+        Ok(())
+    }
+    fn extremum(self, stack: impl Iterator<Item = Number>) -> Number {
+        let args: Vec<Number> = stack.collect();
+        #[cfg(feature = "list")]
+        let args = if args.len() == 1 {
+            lists::elements(args.into_iter().next().unwrap())
+        } else {
+            args
+        };
+        let mut args = args.into_iter();
+        let mut a = args.next().unwrap_or_else(|| Number::from(Constant::Nan));
+        for b in args {
+            if let Self::Min(_) = self {
+                a.min_mut(&b)
+            } else {
+                a.max_mut(&b)
+            }
+        }
+        a
+    }
+    pub fn is_integer(self) -> bool {
+        match self {
+            Self::Gcd
+            | Self::Lcm
+            | Self::IsPrime
+            | Self::NextPrime
+            | Self::ModPow
+            | Self::ModInv
+            | Self::Totient
+            | Self::Sigma => true,
+            #[cfg(feature = "list")]
+            Self::Factor | Self::Divisors => true,
+            Self::Binomial
+            | Self::Perm
+            | Self::Multinomial(_)
+            | Self::StirlingFirst
+            | Self::StirlingSecond
+            | Self::Bell
+            | Self::Catalan
+            | Self::Partitions => true,
+            _ => false,
+        }
+    }
+    fn compute_integer(self, stack: impl Iterator<Item = Number>) -> Result<Number, EvalError> {
+        let args: Vec<Number> = stack.collect();
+        let ints = args
+            .iter()
+            .cloned()
+            .map(Self::to_integer)
+            .collect::<Result<Vec<_>, _>>();
+        if ints.is_err() && self.has_gamma_form() {
+            return Ok(self.compute_gamma(args));
+        }
+        let mut ints = ints?.into_iter();
+        let mut next = || ints.next().unwrap();
+        let int = |n: Integer| Number::from(Float::from(n));
+        let a = next();
+        Ok(match self {
+            Self::Gcd => int(a.gcd(next())),
+            Self::Lcm => int(a.lcm(next())),
+            Self::IsPrime => Number::from(a.is_prime()),
+            #[cfg(feature = "list")]
+            Self::Factor => Number::List(a.factor().into_iter().map(int).collect()),
+            Self::NextPrime => int(a.next_prime()),
+            Self::ModPow => {
+                let b = next();
+                a.modpow(b, next())
+                    .map_or_else(|| Number::from(Constant::Nan), int)
+            }
+            Self::ModInv => a
+                .modinv(next())
+                .map_or_else(|| Number::from(Constant::Nan), int),
+            Self::Totient => int(a.totient()),
+            #[cfg(feature = "list")]
+            Self::Divisors => Number::List(a.divisors().into_iter().map(int).collect()),
+            Self::Sigma => int(a.sigma()),
+            Self::Binomial => int(a.binomial(next())),
+            Self::Perm => int(a.perm(next())),
+            Self::Multinomial(_) => int(Integer::multinomial(iter::once(a).chain(ints).collect())),
+            Self::StirlingFirst => int(a.stirling_first(next()).ok_or(EvalError::TooLarge)?),
+            Self::StirlingSecond => int(a.stirling_second(next()).ok_or(EvalError::TooLarge)?),
+            Self::Bell => int(a.bell().ok_or(EvalError::TooLarge)?),
+            Self::Catalan => int(a.catalan().ok_or(EvalError::TooLarge)?),
+            Self::Partitions => int(a.partitions().ok_or(EvalError::TooLarge)?),
+            _ => unreachable!(),
+        })
+    }
+    fn has_gamma_form(self) -> bool {
+        matches!(
+            self,
+            Self::Binomial | Self::Perm | Self::Multinomial(_) | Self::Catalan
+        )
+    }
+    fn compute_gamma(self, mut args: Vec<Number>) -> Number {
+        let factorial = |mut a: Number| {
+            a += Float::from(1);
+            a.gamma_mut();
+            a
+        };
+        let pole = |a: &Number| *a.real() < Float::from(0) && Self::to_integer(a.clone()).is_ok();
+        let falling = |n: &Number, k: &Number| {
+            if *k.real() < Float::from(0)
+                || *k.real() > Float::from(MAX_FALLING)
+                || Self::to_integer(k.clone()).is_err()
+            {
+                return None;
+            }
+            Some(
+                (0..k.real().clone().into_usize())
+                    .map(|i| n.clone() - Float::from(i))
+                    .product::<Number>(),
+            )
+        };
+        match self {
+            Self::Binomial => {
+                let k = args.pop().unwrap();
+                let n = args.pop().unwrap();
+                if let Some(p) = falling(&n, &k) {
+                    return p / factorial(k);
+                }
+                if pole(&k) || pole(&(n.clone() - k.clone())) {
+                    return Number::from(0);
+                }
+                factorial(n.clone()) / (factorial(k.clone()) * factorial(n - k))
+            }
+            Self::Perm => {
+                let k = args.pop().unwrap();
+                let n = args.pop().unwrap();
+                if let Some(p) = falling(&n, &k) {
+                    return p;
+                }
+                if pole(&(n.clone() - k.clone())) {
+                    return Number::from(0);
+                }
+                factorial(n.clone()) / factorial(n - k)
+            }
+            Self::Multinomial(_) => {
+                if args.iter().any(pole) {
+                    return Number::from(0);
+                }
+                let n = args.iter().cloned().sum::<Number>();
+                factorial(n) / args.into_iter().map(factorial).product::<Number>()
+            }
+            Self::Catalan => {
+                let n = args.pop().unwrap();
+                factorial(n.clone() * Float::from(2))
+                    / (factorial(n.clone()) * factorial(n + Float::from(1)))
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn is_distribution(self) -> bool {
+        #[cfg(feature = "float_rand")]
+        if let Self::RandDistribution(_) = self {
+            return true;
+        }
+        matches!(self, Self::Distribution(..))
+    }
+    fn compute_distribution(
+        self,
+        stack: impl Iterator<Item = Number>,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Number {
+        let mut args = stack.map(|n| n.to_real());
+        Number::from(match self {
+            Self::Distribution(d, function) => {
+                let x = args.next().unwrap();
+                d.compute(function, x, &args.collect::<Vec<_>>())
+            }
+            #[cfg(feature = "float_rand")]
+            Self::RandDistribution(d) => d.sample(&args.collect::<Vec<_>>(), rand),
+            _ => unreachable!(),
+        })
+    }
+    fn to_integer(n: Number) -> Result<Integer, EvalError> {
+        #[cfg(feature = "complex")]
+        let (n, imag) = n.to_real_imag();
+        #[cfg(feature = "complex")]
+        if !imag.is_zero() {
+            return Err(EvalError::NonInteger);
+        }
+        #[cfg(not(feature = "complex"))]
+        let n = n.to_real();
+        let i = n.to_integer().ok_or(EvalError::NonInteger)?;
+        let one = Float::from(1);
+        if n.clone() + one.clone() - n != one {
+            return Err(EvalError::TooLarge);
+        }
+        Ok(i)
     }
     pub fn compute_on_1(self, a: &mut Number) {
         match self {
@@ -551,6 +1118,26 @@ impl Function {
             Self::Round => a.round_mut(),
             Self::Trunc => a.trunc_mut(),
             Self::Fract => a.fract_mut(),
+            #[cfg(feature = "list")]
+            Self::Len => *a = statistics::len(mem::take(a)),
+            #[cfg(feature = "list")]
+            Self::Mean => *a = statistics::mean(mem::take(a)),
+            #[cfg(feature = "list")]
+            Self::Median => *a = statistics::median(mem::take(a)),
+            #[cfg(feature = "list")]
+            Self::Mode => *a = statistics::mode(mem::take(a)),
+            #[cfg(feature = "list")]
+            Self::Var => *a = statistics::var(mem::take(a), false),
+            #[cfg(feature = "list")]
+            Self::SampleVar => *a = statistics::var(mem::take(a), true),
+            #[cfg(feature = "list")]
+            Self::Stddev => *a = statistics::stddev(mem::take(a), false),
+            #[cfg(feature = "list")]
+            Self::SampleStddev => *a = statistics::stddev(mem::take(a), true),
+            #[cfg(feature = "list")]
+            Self::Sort => *a = lists::sort(mem::take(a)),
+            #[cfg(feature = "list")]
+            Self::Reverse => *a = lists::reverse(mem::take(a)),
             #[cfg(feature = "complex")]
             Self::Real => a.zero_imag(),
             #[cfg(feature = "complex")]
@@ -588,8 +1175,26 @@ impl Function {
             Self::Atan(AtanInputs::Two) => a.atan2_mut(&b),
             #[cfg(feature = "float_rand")]
             Self::RandUniform => a.random_range_mut(b, rand),
-            Self::Max => a.max_mut(&b),
-            Self::Min => a.min_mut(&b),
+            Self::Max(_) => a.max_mut(&b),
+            Self::Min(_) => a.min_mut(&b),
+            #[cfg(feature = "list")]
+            Self::Percentile => *a = statistics::percentile(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Covariance => *a = statistics::covariance(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Correlation => *a = statistics::correlation(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Linreg => *a = statistics::linreg(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Range(_) => *a = lists::range(mem::take(a), b, Number::from(1)),
+            #[cfg(feature = "list")]
+            Self::Concat => *a = lists::concat(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Zip => *a = lists::zip(mem::take(a), b),
+            #[cfg(feature = "list")]
+            Self::Slice(_) => *a = lists::slice(mem::take(a), b, None),
+            #[cfg(any(feature = "vector", feature = "list"))]
+            Self::Index(_) => Self::index(a, b),
             _ => unreachable!(),
         }
     }
@@ -597,20 +1202,89 @@ impl Function {
         match self {
             Self::Quadratic => {
                 let ac = mem::take(a);
-                let mut poly = PolyRef(&[c, b, ac]).quadratic().into_iter();
-                *a = poly.next().unwrap()
+                let poly = PolyRef(&[c, b, ac]).quadratic();
+                #[cfg(feature = "list")]
+                let root = root_list(poly);
+                #[cfg(not(feature = "list"))]
+                let [root, ..] = poly;
+                *a = root
+            }
+            #[cfg(feature = "list")]
+            Self::Range(_) => *a = lists::range(mem::take(a), b, c),
+            #[cfg(feature = "list")]
+            Self::Slice(_) => *a = lists::slice(mem::take(a), b, Some(c)),
+            #[cfg(any(feature = "vector", feature = "list"))]
+            Self::Index(_) => {
+                Self::index(a, b);
+                Self::index(a, c)
             }
             _ => unreachable!(),
         }
     }
+    #[cfg(feature = "vector")]
+    fn vector(stack: impl Iterator<Item = Number>) -> Number {
+        let vec: Vec<Number> = stack.collect();
+        if vec.iter().all(|n| matches!(n, Number::Value(_))) {
+            return Number::Vector(
+                vec.into_iter()
+                    .map(|n| {
+                        let Number::Value(n) = n else { unreachable!() };
+                        n
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+        }
+        #[cfg(feature = "matrix")]
+        if let Some(Number::Vector(first)) = vec.first()
+            && vec
+                .iter()
+                .all(|n| matches!(n, Number::Vector(v) if v.len() == first.len()))
+        {
+            let width = first.len() as HalfUsize;
+            return Number::Matrix(Matrix::new(
+                vec.into_iter()
+                    .flat_map(|n| {
+                        let Number::Vector(v) = n else { unreachable!() };
+                        v.into_vec()
+                    })
+                    .collect(),
+                width,
+            ));
+        }
+        #[cfg(feature = "list")]
+        return Number::List(vec);
+        #[cfg(not(feature = "list"))]
+        Number::from(Constant::Nan)
+    }
+    #[cfg(any(feature = "vector", feature = "list"))]
+    fn index(a: &mut Number, i: Number) {
+        let i = usize::try_from(i.to_real().into_isize()).ok();
+        *a = match (&*a, i) {
+            #[cfg(feature = "vector")]
+            (Number::Vector(v), Some(i)) => v.get(i).cloned().map(Number::Value),
+            #[cfg(feature = "matrix")]
+            (Number::Matrix(m), Some(i)) => {
+                m.rows().nth(i).map(|r| Number::Vector(r.to_vec().into()))
+            }
+            #[cfg(feature = "list")]
+            (Number::List(l), Some(i)) => l.get(i).cloned(),
+            _ => None,
+        }
+        .unwrap_or_else(|| Number::from(Constant::Nan))
+    }
     #[cfg(feature = "complex")]
     pub fn compute_on_4(self, a: &mut Number, b: Number, c: Number, d: Number) {
         match self {
             #[cfg(feature = "complex")]
             Self::Cubic => {
                 let ac = mem::take(a);
-                let mut poly = PolyRef(&[d, c, b, ac]).cubic().into_iter();
-                *a = poly.next().unwrap()
+                let poly = PolyRef(&[d, c, b, ac]).cubic();
+                #[cfg(feature = "list")]
+                let root = root_list(poly);
+                #[cfg(not(feature = "list"))]
+                let [root, ..] = poly;
+                *a = root
             }
             _ => unreachable!(),
         }
@@ -621,8 +1295,12 @@ impl Function {
             #[cfg(feature = "complex")]
             Self::Quartic => {
                 let ac = mem::take(a);
-                let mut poly = PolyRef(&[e, d, c, b, ac]).quartic().into_iter();
-                *a = poly.next().unwrap()
+                let poly = PolyRef(&[e, d, c, b, ac]).quartic();
+                #[cfg(feature = "list")]
+                let root = root_list(poly);
+                #[cfg(not(feature = "list"))]
+                let [root, ..] = poly;
+                *a = root
             }
             _ => unreachable!(),
         }
@@ -637,13 +1315,23 @@ impl Function {
             | Self::Solve
             | Self::NumericalSolve
             | Self::NumericalIntegral
+            | Self::IntegralError
+            | Self::NumericalBracket
             | Self::NumericalDerivative
             | Self::Derivative
             | Self::Modify(ModifyInputs::Two)
             | Self::NumericalDifferential => 1,
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory
+            | Self::NumericalRoots
+            | Self::SolveRange
+            | Self::Map
+            | Self::Filter => 1,
             Self::If | Self::Modify(ModifyInputs::Three) | Self::While(ModifyInputs::Two) => 2,
             Self::While(ModifyInputs::Three) => 3,
             Self::Exprs(n) => n.get(),
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            Self::System(n) => n.get(),
             _ => 0,
         }
     }
@@ -657,8 +1345,16 @@ impl Function {
             | Self::NumericalDerivative
             | Self::Derivative
             | Self::NumericalSolve
-            | Self::NumericalIntegral => 1,
+            | Self::NumericalIntegral
+            | Self::IntegralError
+            | Self::NumericalBracket => 1,
+            #[cfg(feature = "list")]
+            Self::NumericalRoots | Self::SolveRange | Self::Map | Self::Filter => 1,
             Self::Fold | Self::NumericalDifferential => 2,
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory => 2,
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            Self::System(n) => n.get(),
             _ => 0,
         }
     }
@@ -668,8 +1364,19 @@ impl Function {
             Self::Set | Self::NumericalDerivative | Self::Derivative | Self::NumericalSolve => {
                 n.get() == 2
             }
-            Self::Sum | Self::Prod | Self::Iter | Self::NumericalIntegral => n.get() == 3,
+            #[cfg(feature = "list")]
+            Self::Map | Self::Filter => n.get() == 2,
+            Self::Sum
+            | Self::Prod
+            | Self::Iter
+            | Self::NumericalIntegral
+            | Self::IntegralError
+            | Self::NumericalBracket => n.get() == 3,
+            #[cfg(feature = "list")]
+            Self::NumericalRoots | Self::SolveRange => n.get() == 3,
             Self::Fold | Self::NumericalDifferential => matches!(n.get(), 4 | 5),
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory => matches!(n.get(), 4 | 5),
             _ => false,
         }
     }
@@ -679,12 +1386,36 @@ impl Function {
             Self::Set | Self::NumericalDerivative | Self::Derivative | Self::NumericalSolve => {
                 n.get() == 2
             }
-            Self::Sum | Self::Prod | Self::Iter | Self::NumericalIntegral => n.get() == 3,
+            #[cfg(feature = "list")]
+            Self::Map | Self::Filter => n.get() == 2,
+            Self::Sum
+            | Self::Prod
+            | Self::Iter
+            | Self::NumericalIntegral
+            | Self::IntegralError
+            | Self::NumericalBracket => n.get() == 3,
+            #[cfg(feature = "list")]
+            Self::NumericalRoots | Self::SolveRange => n.get() == 3,
             Self::Fold | Self::NumericalDifferential => n.get() == 4,
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory => n.get() == 4,
             _ => false,
         }
     }
     pub fn has_var(self) -> bool {
+        #[cfg(all(feature = "vector", feature = "matrix"))]
+        if let Self::System(_) = self {
+            return true;
+        }
+        #[cfg(feature = "list")]
+        if let Self::NumericalTrajectory
+        | Self::NumericalRoots
+        | Self::SolveRange
+        | Self::Map
+        | Self::Filter = self
+        {
+            return true;
+        }
         matches!(
             self,
             Self::Sum
@@ -694,13 +1425,28 @@ impl Function {
                 | Self::Set
                 | Self::Solve
                 | Self::NumericalIntegral
+                | Self::IntegralError
                 | Self::NumericalDerivative
                 | Self::Derivative
                 | Self::NumericalDifferential
                 | Self::NumericalSolve
+                | Self::NumericalBracket
         )
     }
     pub fn has_inner_fn(self) -> bool {
+        #[cfg(all(feature = "vector", feature = "matrix"))]
+        if let Self::System(_) = self {
+            return true;
+        }
+        #[cfg(feature = "list")]
+        if let Self::NumericalTrajectory
+        | Self::NumericalRoots
+        | Self::SolveRange
+        | Self::Map
+        | Self::Filter = self
+        {
+            return true;
+        }
         matches!(
             self,
             Self::Sum
@@ -714,10 +1460,12 @@ impl Function {
                 | Self::While(_)
                 | Self::Exprs(_)
                 | Self::NumericalIntegral
+                | Self::IntegralError
                 | Self::NumericalDerivative
                 | Self::Derivative
                 | Self::NumericalDifferential
                 | Self::NumericalSolve
+                | Self::NumericalBracket
         )
     }
     pub(crate) fn compute_var(
@@ -726,7 +1474,7 @@ impl Function {
         stack: &mut Vec<StackToken>,
         inner_vars: &mut Vec<Number>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) {
+    ) -> Result<(), EvalError> {
         match self {
             Self::Sum => {
                 let (start, [end], [tokens]) = compute.tokens.get_skip_mut(stack);
@@ -746,7 +1494,7 @@ impl Function {
                         *inner_vars.last_mut().unwrap() += Float::from(1);
                         ret
                     })
-                    .sum();
+                    .sum::<Result<_, _>>()?;
                 inner_vars.pop().unwrap();
             }
             Self::Prod => {
@@ -767,7 +1515,7 @@ impl Function {
                         *inner_vars.last_mut().unwrap() += Float::from(1);
                         ret
                     })
-                    .product();
+                    .product::<Result<_, _>>()?;
                 inner_vars.pop().unwrap();
             }
             Self::Fold => {
@@ -779,18 +1527,42 @@ impl Function {
                 inner_vars.push(Number::from(start));
                 let nl = inner_vars.len();
                 let compute = compute.tokens(tokens);
-                (start..=end).for_each(|_| {
+                for _ in start..=end {
                     inner_vars[nl - 2] = compute.compute(
                         inner_vars,
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )?;
                     *inner_vars.last_mut().unwrap() += Float::from(1);
-                });
+                }
                 inner_vars.pop().unwrap();
                 *stack.last_mut().unwrap().num_mut() = inner_vars.pop().unwrap();
             }
+            #[cfg(feature = "list")]
+            Self::Map | Self::Filter => {
+                let (list, [], [tokens]) = compute.tokens.get_skip_mut(stack);
+                let list = lists::elements(mem::take(list));
+                inner_vars.push(Number::default());
+                let compute = compute.tokens(tokens);
+                let mut ret = Vec::with_capacity(list.len());
+                for n in list {
+                    *inner_vars.last_mut().unwrap() = n;
+                    let value = compute.compute(
+                        inner_vars,
+                        stack,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?;
+                    if self == Self::Map {
+                        ret.push(value)
+                    } else if !value.is_zero() {
+                        ret.push(inner_vars.last().unwrap().clone())
+                    }
+                }
+                inner_vars.pop().unwrap();
+                *stack.last_mut().unwrap().num_mut() = Number::List(ret);
+            }
             Self::Set => {
                 let (value, [], [tokens]) = compute.tokens.get_skip_mut(stack);
                 let value = mem::take(value);
@@ -800,7 +1572,7 @@ impl Function {
                     stack,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
                 inner_vars.pop().unwrap();
             }
             Self::Modify(ModifyInputs::Two) => {
@@ -817,7 +1589,7 @@ impl Function {
                     stack,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
             }
             Self::While(ModifyInputs::Two) => {
                 let [cond, expr] = compute.tokens.get_skip_tokens_keep_one(stack);
@@ -830,7 +1602,7 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )
+                    )?
                     .is_zero()
                 {
                     last = expr.compute(
@@ -838,7 +1610,7 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )?;
                 }
                 *stack.last_mut().unwrap() = last.into();
             }
@@ -852,7 +1624,7 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )
+                    )?
                     .is_zero()
                 {
                     expr.compute(
@@ -860,7 +1632,7 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )?;
                 }
                 *stack.last_mut().unwrap() = compute
                     .tokens(ret)
@@ -869,7 +1641,7 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )
+                    )?
                     .into();
             }
             Self::Exprs(n) => {
@@ -884,14 +1656,14 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )?;
                 }
                 let last = compute.tokens(tokens.next().unwrap()).compute(
                     inner_vars,
                     stack,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
                 *stack.last_mut().unwrap() = last.into();
             }
             Self::Solve => {
@@ -903,24 +1675,43 @@ impl Function {
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )
+                    )?
                     .unwrap_or_else(|| Number::from(Constant::Nan))
                     .into();
             }
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            Self::System(n) => {
+                let tokens = compute
+                    .tokens
+                    .get_skip_tokens_keep_one_vec(stack, n.get() as usize);
+                let var = inner_vars.len();
+                inner_vars.resize(var + n.get() as usize, Number::default());
+                *stack.last_mut().unwrap() = compute
+                    .solve_system(
+                        inner_vars,
+                        stack,
+                        &tokens,
+                        var,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?
+                    .into();
+                inner_vars.truncate(var);
+            }
             Self::Iter => {
                 let (first, [steps], [tokens]) = compute.tokens.get_skip_mut(stack);
                 let first = mem::take(first);
                 inner_vars.push(first);
                 let steps = steps.to_real().into_isize();
                 let compute = compute.tokens(tokens);
-                (0..steps).for_each(|_| {
+                for _ in 0..steps {
                     *inner_vars.last_mut().unwrap() = compute.compute(
                         inner_vars,
                         stack,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
-                });
+                    )?;
+                }
                 *stack.last_mut().unwrap().num_mut() = inner_vars.pop().unwrap();
             }
             Self::If => {
@@ -934,7 +1725,7 @@ impl Function {
                             #[cfg(feature = "float_rand")]
                             rand,
                         )
-                    });
+                    })?;
             }
             Self::Derivative => {
                 let (point, [], [tokens]) = compute.tokens.get_skip_mut(stack);
@@ -948,7 +1739,7 @@ impl Function {
                         (inner_vars.len() - 1) as u16,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )
+                    )?
                     .unwrap_or_else(|| Number::from(Constant::Nan));
                 inner_vars.pop().unwrap();
             }
@@ -956,21 +1747,22 @@ impl Function {
                 let (point, [], [tokens]) = compute.tokens.get_skip_mut(stack);
                 let point = mem::take(point);
                 inner_vars.push(Number::default());
-                *stack.last_mut().unwrap().num_mut() = compute.tokens(tokens).numerical_derivative(
-                    inner_vars,
-                    stack,
-                    point,
-                    (inner_vars.len() - 1) as u16,
-                    #[cfg(feature = "float_rand")]
-                    rand,
-                );
+                *stack.last_mut().unwrap().num_mut() =
+                    compute.tokens(tokens).numerical_derivative(
+                        inner_vars,
+                        stack,
+                        point,
+                        (inner_vars.len() - 1) as u16,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?;
                 inner_vars.pop().unwrap();
             }
-            Self::NumericalIntegral => {
+            Self::NumericalIntegral | Self::IntegralError => {
                 let (start, [end], [tokens]) = compute.tokens.get_skip_mut(stack);
                 let start = mem::take(start);
                 inner_vars.push(Number::default());
-                *stack.last_mut().unwrap().num_mut() = compute.tokens(tokens).numerical_integral(
+                let (value, err) = compute.tokens(tokens).numerical_integral(
                     inner_vars,
                     stack,
                     start,
@@ -978,7 +1770,12 @@ impl Function {
                     (inner_vars.len() - 1) as u16,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
+                *stack.last_mut().unwrap().num_mut() = if self == Self::IntegralError {
+                    Number::from(err)
+                } else {
+                    value
+                };
                 inner_vars.pop().unwrap();
             }
             Self::NumericalDifferential => {
@@ -997,7 +1794,28 @@ impl Function {
                         (inner_vars.len() - 1) as u16,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    );
+                    )?;
+                inner_vars.pop().unwrap();
+                inner_vars.pop().unwrap();
+            }
+            #[cfg(feature = "list")]
+            Self::NumericalTrajectory => {
+                let (x_0, [t_0, t_1], [tokens]) = compute.tokens.get_skip_mut(stack);
+                let x_0 = mem::take(x_0);
+                inner_vars.push(Number::default());
+                inner_vars.push(Number::default());
+                *stack.last_mut().unwrap().num_mut() =
+                    compute.tokens(tokens).numerical_trajectory(
+                        inner_vars,
+                        stack,
+                        x_0,
+                        t_0,
+                        t_1,
+                        (inner_vars.len() - 2) as u16,
+                        (inner_vars.len() - 1) as u16,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?;
                 inner_vars.pop().unwrap();
                 inner_vars.pop().unwrap();
             }
@@ -1012,10 +1830,55 @@ impl Function {
                     (inner_vars.len() - 1) as u16,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
+                inner_vars.pop().unwrap();
+            }
+            Self::NumericalBracket => {
+                let (start, [end], [tokens]) = compute.tokens.get_skip_mut(stack);
+                let start = mem::take(start);
+                inner_vars.push(Number::default());
+                *stack.last_mut().unwrap().num_mut() = compute.tokens(tokens).numerical_bracket(
+                    inner_vars,
+                    stack,
+                    start,
+                    end,
+                    (inner_vars.len() - 1) as u16,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?;
+                inner_vars.pop().unwrap();
+            }
+            #[cfg(feature = "list")]
+            Self::NumericalRoots => {
+                let (start, [end], [tokens]) = compute.tokens.get_skip_mut(stack);
+                let start = mem::take(start);
+                inner_vars.push(Number::default());
+                *stack.last_mut().unwrap().num_mut() = compute.tokens(tokens).numerical_roots(
+                    inner_vars,
+                    stack,
+                    start,
+                    end,
+                    (inner_vars.len() - 1) as u16,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?;
                 inner_vars.pop().unwrap();
             }
+            #[cfg(feature = "list")]
+            Self::SolveRange => {
+                let (start, [end], [tokens]) = compute.tokens.get_skip_mut(stack);
+                let start = mem::take(start);
+                *stack.last_mut().unwrap().num_mut() = compute.tokens(tokens).solve_range(
+                    inner_vars,
+                    stack,
+                    start,
+                    end,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?;
+            }
             _ => {}
         }
+        Ok(())
     }
 }
@@ -33,8 +33,8 @@ pub const FUNCTION_LIST: &[&str] = &[
     "atanh(x)",
     "ln(x)",
     "exp(x)",
-    "min(a,b)",
-    "max(a,b)",
+    "min(a,b,...)",
+    "max(a,b,...)",
     "quadratic(a,b,c)",
     #[cfg(feature = "complex")]
     "cubic(a,b,c,d)",
@@ -61,6 +61,91 @@ pub const FUNCTION_LIST: &[&str] = &[
     "round(x)",
     "trunc(x)",
     "fract(x)",
+    "gcd(a,b)",
+    "lcm(a,b)",
+    "isprime(n)",
+    #[cfg(feature = "list")]
+    "factor(n)",
+    "nextprime(n)",
+    "modpow(a,b,m)",
+    "modinv(a,m)",
+    "totient(n)",
+    #[cfg(feature = "list")]
+    "divisors(n)",
+    "sigma(n)",
+    "binomial(n,k)",
+    "choose(n,k)",
+    "perm(n,k)",
+    "multinomial(a,b,...)",
+    "stirling_first(n,k)",
+    "stirling_second(n,k)",
+    "bell(n)",
+    "catalan(n)",
+    "partitions(n)",
+    #[cfg(feature = "list")]
+    "len(l)",
+    #[cfg(feature = "list")]
+    "mean(l)",
+    #[cfg(feature = "list")]
+    "median(l)",
+    #[cfg(feature = "list")]
+    "mode(l)",
+    #[cfg(feature = "list")]
+    "var(l)",
+    #[cfg(feature = "list")]
+    "sample_var(l)",
+    #[cfg(feature = "list")]
+    "stddev(l)",
+    #[cfg(feature = "list")]
+    "sample_stddev(l)",
+    #[cfg(feature = "list")]
+    "percentile(l,p)",
+    #[cfg(feature = "list")]
+    "covariance(x,y)",
+    #[cfg(feature = "list")]
+    "correlation(x,y)",
+    #[cfg(feature = "list")]
+    "linreg(x,y)",
+    #[cfg(feature = "list")]
+    "range(a,b(,step))",
+    #[cfg(feature = "list")]
+    "sort(l)",
+    #[cfg(feature = "list")]
+    "reverse(l)",
+    #[cfg(feature = "list")]
+    "concat(a,b)",
+    #[cfg(feature = "list")]
+    "zip(a,b)",
+    #[cfg(feature = "list")]
+    "slice(l,a(,b))",
+    #[cfg(feature = "list")]
+    "map(l(,x),f(x))",
+    #[cfg(feature = "list")]
+    "filter(l(,x),cond(x))",
+    "normal_pdf(x,mu,sigma)",
+    "normal_cdf(x,mu,sigma)",
+    "normal_inv(p,mu,sigma)",
+    "student_t_pdf(x,nu)",
+    "student_t_cdf(x,nu)",
+    "student_t_inv(p,nu)",
+    "chi_squared_pdf(x,k)",
+    "chi_squared_cdf(x,k)",
+    "chi_squared_inv(p,k)",
+    "binomial_pmf(k,n,p)",
+    "binomial_cdf(k,n,p)",
+    "binomial_inv(q,n,p)",
+    "poisson_pmf(k,lambda)",
+    "poisson_cdf(k,lambda)",
+    "poisson_inv(p,lambda)",
+    "exponential_pdf(x,lambda)",
+    "exponential_cdf(x,lambda)",
+    "exponential_inv(p,lambda)",
+    "uniform_pdf(x,a,b)",
+    "uniform_cdf(x,a,b)",
+    "uniform_inv(p,a,b)",
+    "gamma_pdf(x,k,theta)",
+    "gamma_cdf(x,k,theta)",
+    "gamma_inv(p,k,theta)",
     #[cfg(feature = "complex")]
     "real(x)",
     #[cfg(feature = "complex")]
@@ -72,14 +157,43 @@ pub const FUNCTION_LIST: &[&str] = &[
     "modify(x,new(x)(,f(x)))",
     "exprs(a,b,...)",
     "solve((x,)f(x))",
+    #[cfg(feature = "list")]
+    "solve_range(a,b(,x),f(x))",
+    "diff(f(x),x)",
     #[cfg(feature = "float_rand")]
     "rand_uniform(a,b)",
+    #[cfg(feature = "float_rand")]
+    "rand_normal(mu,sigma)",
+    #[cfg(feature = "float_rand")]
+    "rand_student_t(nu)",
+    #[cfg(feature = "float_rand")]
+    "rand_chi_squared(k)",
+    #[cfg(feature = "float_rand")]
+    "rand_binomial(n,p)",
+    #[cfg(feature = "float_rand")]
+    "rand_poisson(lambda)",
+    #[cfg(feature = "float_rand")]
+    "rand_exponential(lambda)",
+    #[cfg(feature = "float_rand")]
+    "rand_gamma(k,theta)",
     #[cfg(feature = "units")]
     "convert(a,b)",
     "numerical_solve(start(,x),f(x))",
+    "numerical_bracket(a,b(,x),f(x))",
+    #[cfg(feature = "list")]
+    "numerical_roots(a,b(,x),f(x))",
     "numerical_derivative(point(,x),f(x))",
     "numerical_integral(start,end(,x),f(x))",
+    "integral_error(start,end(,x),f(x))",
     "numerical_differential(x_0,t_0,t_1(,x,t),f(x,t))",
+    #[cfg(feature = "list")]
+    "numerical_trajectory(x_0,t_0,t_1(,x,t),f(x,t))",
+    #[cfg(feature = "vector")]
+    "vector(a,b,...)",
+    #[cfg(feature = "vector")]
+    "index(v,i(,j))",
+    #[cfg(all(feature = "list", not(feature = "vector")))]
+    "index(l,i(,j))",
 ];
 pub fn get_help(str: &str) -> &str {
     match str {
@@ -87,14 +201,14 @@ pub fn get_help(str: &str) -> &str {
         "sub" => "sub(a,b)",
         "mul" => "mul(a,b)",
         "div" => "div(a,b)",
-        "pow" => "pow(a,b)",
+        "pow" => "pow(a,b), integer powers are exact only with the rational feature",
         #[cfg(feature = "units")]
         "convert" => "convert(a,b)",
         "tetration" => "tetration(a,b)",
         "root" => "root(a,b)",
         "rem" => "rem(a,b)",
         "negate" => "negate(x)",
-        "factorial" => "factorial(n)",
+        "factorial" => "factorial(n), exact only with the rational feature",
         "subfactorial" => "subfactorial(n)",
         "equal" => "equal(a,b)",
         "notequal" => "notequal(a,b)",
@@ -119,19 +233,32 @@ pub fn get_help(str: &str) -> &str {
         "atanh" => "atanh(x)",
         "ln" => "ln(x)",
         "exp" => "exp(x)",
-        "min" => "min(a,b)",
-        "max" => "max(a,b)",
+        #[cfg(feature = "list")]
+        "min" => "min(a,b,...), min(l) is the smallest element of the list l",
+        #[cfg(not(feature = "list"))]
+        "min" => "min(a,b,...)",
+        #[cfg(feature = "list")]
+        "max" => "max(a,b,...), max(l) is the largest element of the list l",
+        #[cfg(not(feature = "list"))]
+        "max" => "max(a,b,...)",
+        #[cfg(feature = "list")]
+        "quadratic" => "quadratic(a,b,c), list of roots with real roots first",
+        #[cfg(not(feature = "list"))]
         "quadratic" => "quadratic(a,b,c)",
-        #[cfg(feature = "complex")]
+        #[cfg(all(feature = "complex", feature = "list"))]
+        "cubic" => "cubic(a,b,c,d), list of roots with real roots first",
+        #[cfg(all(feature = "complex", not(feature = "list")))]
         "cubic" => "cubic(a,b,c,d)",
-        #[cfg(feature = "complex")]
+        #[cfg(all(feature = "complex", feature = "list"))]
+        "quartic" => "quartic(a,b,c,d,e), list of roots with real roots first",
+        #[cfg(all(feature = "complex", not(feature = "list")))]
         "quartic" => "quartic(a,b,c,d,e)",
         "sqrt" => "sqrt(x)",
         "cbrt" => "cbrt(x)",
         "sq" => "sq(x)",
         "cb" => "cb(x)",
-        "sum" => "sum(start,end(,n),f(n))",
-        "prod" => "prod(start,end(,n),f(n))",
+        "sum" => "sum(start,end(,n),f(n)), exact only with the rational feature",
+        "prod" => "prod(start,end(,n),f(n)), exact only with the rational feature",
         "gamma" => "gamma(x)",
         "erf" => "erf(x)",
         "erfc" => "erfc(x)",
@@ -147,6 +274,99 @@ pub fn get_help(str: &str) -> &str {
         "round" => "round(x)",
         "trunc" => "trunc(x)",
         "fract" => "fract(x)",
+        "gcd" => "gcd(a,b)",
+        "lcm" => "lcm(a,b)",
+        "isprime" => "isprime(n), 1 if n is prime, 0 otherwise",
+        #[cfg(feature = "list")]
+        "factor" => {
+            "factor(n), list of the prime factors of n with multiplicity, -1 first if n is negative"
+        }
+        "nextprime" => "nextprime(n), the smallest prime greater than n",
+        "modpow" => "modpow(a,b,m), a^b mod m, nan if b is negative and a has no inverse mod m",
+        "modinv" => "modinv(a,m), the inverse of a mod m, nan if there is none",
+        "totient" => "totient(n), euler's totient of n",
+        #[cfg(feature = "list")]
+        "divisors" => "divisors(n), list of the positive divisors of n",
+        "sigma" => "sigma(n), the sum of the positive divisors of n",
+        "binomial" => {
+            "binomial(n,k), n!/(k!(n-k)!), through gamma for non integers, exact only with the rational feature"
+        }
+        "choose" => "choose(n,k), same as binomial(n,k)",
+        "perm" => {
+            "perm(n,k), n!/(n-k)!, through gamma for non integers, exact only with the rational feature"
+        }
+        "multinomial" => {
+            "multinomial(a,b,...), (a+b+...)!/(a!b!...), through gamma for non integers"
+        }
+        "stirling_first" => "stirling_first(n,k), signed stirling number of the first kind",
+        "stirling_second" => "stirling_second(n,k), stirling number of the second kind",
+        "bell" => "bell(n), number of partitions of a set of size n",
+        "catalan" => "catalan(n), (2n)!/(n!(n+1)!), through gamma for non integers",
+        "partitions" => "partitions(n), number of integer partitions of n",
+        #[cfg(feature = "list")]
+        "len" => "len(l), number of elements in l",
+        #[cfg(feature = "list")]
+        "mean" => "mean(l)",
+        #[cfg(feature = "list")]
+        "median" => "median(l)",
+        #[cfg(feature = "list")]
+        "mode" => "mode(l), most common element of l, the smallest on a tie",
+        #[cfg(feature = "list")]
+        "var" => "var(l), population variance",
+        #[cfg(feature = "list")]
+        "sample_var" => "sample_var(l), sample variance",
+        #[cfg(feature = "list")]
+        "stddev" => "stddev(l), population standard deviation",
+        #[cfg(feature = "list")]
+        "sample_stddev" => "sample_stddev(l), sample standard deviation",
+        #[cfg(feature = "list")]
+        "percentile" => "percentile(l,p), p from 0 to 100, interpolating between elements",
+        #[cfg(feature = "list")]
+        "covariance" => "covariance(x,y), population covariance",
+        #[cfg(feature = "list")]
+        "correlation" => "correlation(x,y), pearson correlation coefficient",
+        #[cfg(feature = "list")]
+        "linreg" => "linreg(x,y), [slope,intercept,r^2] of the least squares line",
+        #[cfg(feature = "list")]
+        "range" => "range(a,b(,step)), [a,a+step,...] up to and including b",
+        #[cfg(feature = "list")]
+        "sort" => "sort(l)",
+        #[cfg(feature = "list")]
+        "reverse" => "reverse(l)",
+        #[cfg(feature = "list")]
+        "concat" => "concat(a,b), elements of a followed by elements of b",
+        #[cfg(feature = "list")]
+        "zip" => "zip(a,b), [[a_0,b_0],[a_1,b_1],...] up to the shorter length",
+        #[cfg(feature = "list")]
+        "slice" => "slice(l,a(,b)), elements from index a up to but excluding b",
+        #[cfg(feature = "list")]
+        "map" => "map(l(,x),f(x))",
+        #[cfg(feature = "list")]
+        "filter" => "filter(l(,x),cond(x)), elements of l where cond(x) is not zero",
+        "normal_pdf" => "normal_pdf(x,mu,sigma), probability density at x",
+        "normal_cdf" => "normal_cdf(x,mu,sigma), probability of a value at most x",
+        "normal_inv" => "normal_inv(p,mu,sigma), x with normal_cdf(x,mu,sigma)=p",
+        "student_t_pdf" => "student_t_pdf(x,nu), probability density at x",
+        "student_t_cdf" => "student_t_cdf(x,nu), probability of a value at most x",
+        "student_t_inv" => "student_t_inv(p,nu), x with student_t_cdf(x,nu)=p",
+        "chi_squared_pdf" => "chi_squared_pdf(x,k), probability density at x",
+        "chi_squared_cdf" => "chi_squared_cdf(x,k), probability of a value at most x",
+        "chi_squared_inv" => "chi_squared_inv(p,k), x with chi_squared_cdf(x,k)=p",
+        "binomial_pmf" => "binomial_pmf(k,n,p), probability mass at k",
+        "binomial_cdf" => "binomial_cdf(k,n,p), probability of a value at most k",
+        "binomial_inv" => "binomial_inv(q,n,p), smallest k with binomial_cdf(k,n,p)>=q",
+        "poisson_pmf" => "poisson_pmf(k,lambda), probability mass at k",
+        "poisson_cdf" => "poisson_cdf(k,lambda), probability of a value at most k",
+        "poisson_inv" => "poisson_inv(p,lambda), smallest k with poisson_cdf(k,lambda)>=p",
+        "exponential_pdf" => "exponential_pdf(x,lambda), probability density at x",
+        "exponential_cdf" => "exponential_cdf(x,lambda), probability of a value at most x",
+        "exponential_inv" => "exponential_inv(p,lambda), x with exponential_cdf(x,lambda)=p",
+        "uniform_pdf" => "uniform_pdf(x,a,b), probability density at x",
+        "uniform_cdf" => "uniform_cdf(x,a,b), probability of a value at most x",
+        "uniform_inv" => "uniform_inv(p,a,b), x with uniform_cdf(x,a,b)=p",
+        "gamma_pdf" => "gamma_pdf(x,k,theta), probability density at x",
+        "gamma_cdf" => "gamma_cdf(x,k,theta), probability of a value at most x",
+        "gamma_inv" => "gamma_inv(p,k,theta), x with gamma_cdf(x,k,theta)=p",
         #[cfg(feature = "complex")]
         "real" => "real(x)",
         #[cfg(feature = "complex")]
@@ -157,13 +377,64 @@ pub fn get_help(str: &str) -> &str {
         "set" => "set(value(,x),f(x))",
         "modify" => "modify(new(x),x(,f(x)))",
         "exprs" => "exprs(a,b,...)",
+        #[cfg(all(feature = "vector", feature = "matrix"))]
+        "solve" => "solve((x,)f(x)), solve((x,y,...),f(x,y,...),g(x,y,...),...)",
+        #[cfg(not(all(feature = "vector", feature = "matrix")))]
         "solve" => "solve((x,)f(x))",
+        #[cfg(feature = "list")]
+        "solve_range" => {
+            "solve_range(a,b(,x),f(x)), list of every solution between a and b, a complex a and b bound a rectangle"
+        }
+        "diff" => "diff(f(x),x), the derivative of f(x) with respect to x as a new expression",
         #[cfg(feature = "float_rand")]
         "rand_uniform" => "rand_uniform(a,b)",
-        "numerical_solve" => "numerical_solve(start(,x),f(x))",
+        #[cfg(feature = "float_rand")]
+        "rand_normal" => "rand_normal(mu,sigma), random sample from the normal distribution",
+        #[cfg(feature = "float_rand")]
+        "rand_student_t" => "rand_student_t(nu), random sample from the student t distribution",
+        #[cfg(feature = "float_rand")]
+        "rand_chi_squared" => {
+            "rand_chi_squared(k), random sample from the chi squared distribution"
+        }
+        #[cfg(feature = "float_rand")]
+        "rand_binomial" => "rand_binomial(n,p), random sample from the binomial distribution",
+        #[cfg(feature = "float_rand")]
+        "rand_poisson" => "rand_poisson(lambda), random sample from the poisson distribution",
+        #[cfg(feature = "float_rand")]
+        "rand_exponential" => {
+            "rand_exponential(lambda), random sample from the exponential distribution"
+        }
+        #[cfg(feature = "float_rand")]
+        "rand_gamma" => "rand_gamma(k,theta), random sample from the gamma distribution",
+        "numerical_solve" => {
+            "numerical_solve(start(,x),f(x)), newton's method from start, nan if it does not converge"
+        }
+        "numerical_bracket" => {
+            "numerical_bracket(a,b(,x),f(x)), brent's method on [a,b], nan if f(a) and f(b) have the same sign"
+        }
+        #[cfg(feature = "list")]
+        "numerical_roots" => "numerical_roots(a,b(,x),f(x)), list of every root found in [a,b]",
         "numerical_derivative" => "numerical_derivative(point(,x),f(x))",
         "numerical_integral" => "numerical_integral(start,end(,x),f(x))",
+        "integral_error" => {
+            "integral_error(start,end(,x),f(x)), estimated error of numerical_integral"
+        }
+        #[cfg(feature = "vector")]
+        "numerical_differential" => {
+            "numerical_differential(x_0,t_0,t_1(,x,t),f(x,t)), x_0 may be a vector for a system, or [x,x',...] with a scalar f(x,t) for a higher order equation"
+        }
+        #[cfg(not(feature = "vector"))]
         "numerical_differential" => "numerical_differential(x_0,t_0,t_1(,x,t),f(x,t))",
+        #[cfg(feature = "list")]
+        "numerical_trajectory" => {
+            "numerical_trajectory(x_0,t_0,t_1(,x,t),f(x,t)), list of [t,x] at every step"
+        }
+        #[cfg(feature = "vector")]
+        "vector" => "vector(a,b,...), same as [a,b,...]",
+        #[cfg(feature = "vector")]
+        "index" => "index(v,i(,j)), same as v[i(,j)]",
+        #[cfg(all(feature = "list", not(feature = "vector")))]
+        "index" => "index(l,i(,j))",
         "" => "",
         _ => "unknown",
     }
@@ -1,8 +1,20 @@
+#[cfg(feature = "list")]
+use crate::compute::EvalError;
 use crate::functions::AtanInputs;
 use crate::{Function, Number};
+#[cfg(feature = "list")]
+use std::mem;
+#[cfg(all(feature = "complex", feature = "list"))]
+use ucalc_numbers::ComplexTrait;
 #[cfg(feature = "complex")]
 use ucalc_numbers::{ComplexFunctions, ComplexFunctionsMut};
+#[cfg(any(feature = "complex", feature = "list"))]
+use ucalc_numbers::{Constant, Float};
 use ucalc_numbers::{FloatFunctions, FloatFunctionsMut, Pow, PowAssign};
+#[cfg(any(not(feature = "complex"), feature = "list"))]
+use ucalc_numbers::{FloatTrait, RealTrait};
+#[cfg(feature = "list")]
+const MAX_PERIODS: usize = 1 << 12;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Inverse {
     Add,
@@ -95,6 +107,20 @@ impl Inverse {
             }
             Self::Pow => {
                 if N == 0 {
+                    #[cfg(feature = "list")]
+                    if b.real().clone().fract().is_zero() && *b.real() >= Float::from(2) && {
+                        #[cfg(feature = "complex")]
+                        {
+                            b.imag().is_zero()
+                        }
+                        #[cfg(not(feature = "complex"))]
+                        {
+                            true
+                        }
+                    } {
+                        let n = b.real().clone().into_usize();
+                        return flat_map(a, |a| Inverse::rooti(a, n));
+                    }
                     Inverse::pow_assign(a, b.recip())
                 } else {
                     a.ln_mut();
@@ -114,14 +140,230 @@ impl Inverse {
         }
     }
     pub fn rooti(a: Number, b: usize) -> Vec<Number> {
-        //TODO
-        vec![a.pow(Number::from(b).recip())]
+        #[cfg(feature = "complex")]
+        {
+            let root = a.pow(Number::from(b).recip());
+            (0..b)
+                .map(|k| {
+                    let t = Float::from(2 * k) * Float::from(Constant::Pi) / Float::from(b);
+                    root.clone() * Number::from((t.clone().cos(), t.sin()))
+                })
+                .collect()
+        }
+        #[cfg(not(feature = "complex"))]
+        {
+            let negative = a.real().is_sign_negative();
+            if negative && b.is_multiple_of(2) {
+                return Vec::new();
+            }
+            let root = if negative { -a } else { a }.pow(Number::from(b).recip());
+            if negative {
+                vec![-root]
+            } else if b.is_multiple_of(2) {
+                vec![-root.clone(), root]
+            } else {
+                vec![root]
+            }
+        }
+    }
+    #[cfg(feature = "list")]
+    pub(crate) fn branches(
+        self,
+        mut a: Number,
+        bounds: Option<&Bounds>,
+    ) -> Result<Vec<Number>, EvalError> {
+        let pi = Float::from(Constant::Pi);
+        let mut ret = Vec::new();
+        if let Self::Cb = self {
+            ret = Inverse::rooti(a, 3)
+        } else {
+            self.get_inverse().unwrap().compute_on_1(&mut a);
+            match (self, bounds) {
+                (Self::Sin, Some(bounds)) => {
+                    let period = Number::from(pi.clone() * Float::from(2));
+                    bounds.periods(&mut ret, -a.clone() + pi, &period)?;
+                    bounds.periods(&mut ret, a, &period)?;
+                }
+                (Self::Cos, Some(bounds)) => {
+                    let period = Number::from(pi * Float::from(2));
+                    bounds.periods(&mut ret, -a.clone(), &period)?;
+                    bounds.periods(&mut ret, a, &period)?;
+                }
+                (Self::Tan, Some(bounds)) => bounds.periods(&mut ret, a, &Number::from(pi))?,
+                #[cfg(feature = "complex")]
+                (Self::Exp, Some(bounds)) => bounds.periods(
+                    &mut ret,
+                    a,
+                    &Number::from((Float::from(0), pi * Float::from(2))),
+                )?,
+                #[cfg(feature = "complex")]
+                (Self::Sinh, Some(bounds)) => {
+                    let period = Number::from((Float::from(0), pi.clone() * Float::from(2)));
+                    bounds.periods(
+                        &mut ret,
+                        -a.clone() + Number::from((Float::from(0), pi)),
+                        &period,
+                    )?;
+                    bounds.periods(&mut ret, a, &period)?;
+                }
+                #[cfg(feature = "complex")]
+                (Self::Cosh, Some(bounds)) => {
+                    let period = Number::from((Float::from(0), pi * Float::from(2)));
+                    bounds.periods(&mut ret, -a.clone(), &period)?;
+                    bounds.periods(&mut ret, a, &period)?;
+                }
+                #[cfg(feature = "complex")]
+                (Self::Tanh, Some(bounds)) => {
+                    bounds.periods(&mut ret, a, &Number::from((Float::from(0), pi)))?
+                }
+                (Self::Sq | Self::Cosh, _) => {
+                    ret.push(-a.clone());
+                    ret.push(a)
+                }
+                _ => ret.push(a),
+            }
+        }
+        if let Some(bounds) = bounds {
+            ret.retain(|a| bounds.contains(a))
+        }
+        Ok(ret)
     }
     pub fn pow_assign(a: &mut Number, b: Number) {
         //TODO
         a.pow_assign(b)
     }
 }
+#[cfg(feature = "list")]
+pub(crate) fn into_list(a: Number) -> Vec<Number> {
+    match a {
+        Number::List(a) => a,
+        a => vec![a],
+    }
+}
+#[cfg(feature = "list")]
+pub(crate) fn flat_map(a: &mut Number, f: impl FnMut(Number) -> Vec<Number>) {
+    *a = Number::List(into_list(mem::take(a)).into_iter().flat_map(f).collect())
+}
+#[cfg(feature = "list")]
+pub(crate) fn try_flat_map(
+    a: &mut Number,
+    mut f: impl FnMut(Number) -> Result<Vec<Number>, EvalError>,
+) -> Result<(), EvalError> {
+    let mut ret = Vec::new();
+    for a in into_list(mem::take(a)) {
+        ret.extend(f(a)?)
+    }
+    *a = Number::List(ret);
+    Ok(())
+}
+#[cfg(feature = "list")]
+#[derive(Debug, Clone)]
+pub(crate) struct Bounds {
+    real: [Float; 2],
+    #[cfg(feature = "complex")]
+    imag: [Float; 2],
+}
+#[cfg(feature = "list")]
+impl Bounds {
+    pub(crate) fn new(points: impl IntoIterator<Item = Number>) -> Self {
+        fn extend([lo, hi]: &mut [Float; 2], a: &Float) {
+            if a < lo {
+                *lo = a.clone()
+            }
+            if a > hi {
+                *hi = a.clone()
+            }
+        }
+        let empty = [Float::from(f64::INFINITY), Float::from(f64::NEG_INFINITY)];
+        let mut bounds = Self {
+            real: empty.clone(),
+            #[cfg(feature = "complex")]
+            imag: empty,
+        };
+        for a in points {
+            extend(&mut bounds.real, a.real());
+            #[cfg(feature = "complex")]
+            extend(&mut bounds.imag, a.imag());
+        }
+        bounds
+    }
+    pub(crate) fn widen(mut self) -> Self {
+        fn widen([lo, hi]: &mut [Float; 2]) {
+            if lo <= hi {
+                let w = (hi.clone() - lo.clone()) / Float::from(64);
+                *lo -= w.clone();
+                *hi += w;
+            }
+        }
+        widen(&mut self.real);
+        #[cfg(feature = "complex")]
+        widen(&mut self.imag);
+        self
+    }
+    pub(crate) fn samples(&self) -> Vec<Number> {
+        fn lerp([lo, hi]: &[Float; 2], i: usize, n: usize) -> Float {
+            lo.clone() + (hi.clone() - lo.clone()) * Float::from(i) / Float::from(n)
+        }
+        #[cfg(feature = "complex")]
+        if self.imag[0] != self.imag[1] {
+            return (0..=16)
+                .flat_map(|i| {
+                    (0..=16).map(move |j| {
+                        Number::from((lerp(&self.real, i, 16), lerp(&self.imag, j, 16)))
+                    })
+                })
+                .collect();
+        }
+        (0..=256)
+            .map(|i| {
+                #[cfg(feature = "complex")]
+                return Number::from((lerp(&self.real, i, 256), self.imag[0].clone()));
+                #[cfg(not(feature = "complex"))]
+                Number::from(lerp(&self.real, i, 256))
+            })
+            .collect()
+    }
+    pub(crate) fn contains(&self, a: &Number) -> bool {
+        let tol = Float::from(2.0f64.powi(-16)) * (a.clone().abs() + Float::from(1));
+        let within = |[lo, hi]: &[Float; 2], a: &Float| {
+            lo.clone() - tol.clone() <= *a && *a <= hi.clone() + tol.clone()
+        };
+        #[cfg(feature = "complex")]
+        if !within(&self.imag, a.imag()) {
+            return false;
+        }
+        within(&self.real, a.real())
+    }
+    fn periods(
+        &self,
+        ret: &mut Vec<Number>,
+        base: Number,
+        period: &Number,
+    ) -> Result<(), EvalError> {
+        #[cfg(feature = "complex")]
+        let ([lo, hi], c) = if period.real().is_zero() {
+            (&self.imag, base.imag().clone())
+        } else {
+            (&self.real, base.real().clone())
+        };
+        #[cfg(not(feature = "complex"))]
+        let ([lo, hi], c) = (&self.real, base.real().clone());
+        if lo > hi {
+            return Ok(());
+        }
+        let size = period.clone().abs();
+        let start = ((lo.clone() - c.clone()) / size.clone()).floor();
+        let end = ((hi.clone() - c) / size).ceil();
+        let count = end.clone() - start.clone();
+        if count.is_nan() || count > Float::from(MAX_PERIODS) {
+            return Err(EvalError::TooManySolutions);
+        }
+        for k in start.into_isize()..=end.into_isize() {
+            ret.push(base.clone() + period.clone() * Number::from(Float::from(k)))
+        }
+        Ok(())
+    }
+}
 impl TryFrom<Function> for Inverse {
     type Error = ();
     fn try_from(value: Function) -> Result<Self, Self::Error> {
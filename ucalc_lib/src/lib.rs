@@ -2,11 +2,15 @@
 #![feature(vec_try_remove)]
 #![feature(adt_const_params)]
 #![feature(try_blocks)]
+#![cfg_attr(feature = "fastnum", allow(clippy::large_enum_variant))]
 mod compute;
 mod derivative;
+mod distributions;
 mod functions;
 mod functions_list;
 mod inverse;
+#[cfg(feature = "list")]
+mod lists;
 mod math;
 mod operators;
 mod parse;
@@ -16,19 +20,25 @@ mod polynomial_impls;
 mod rand;
 mod simplify;
 mod solver;
+#[cfg(feature = "list")]
+mod statistics;
 #[cfg(test)]
 mod tests;
 mod variable;
-pub use compute::Compute;
+pub use compute::{Compute, EvalError};
 pub use functions::Function;
 pub use functions_list::{FUNCTION_LIST, get_help};
 pub use operators::Operator;
-pub use parse::{Derivative, ParseReturn, Token, Tokens, TokensSlice, Volatility};
+pub use parse::{
+    Derivative, ParseError, ParseErrorSpan, ParseReturn, Token, Tokens, TokensSlice, Volatility,
+};
 #[cfg(feature = "float_rand")]
 pub use rand::{Rand, rng};
 #[cfg(feature = "units")]
 use ucalc_numbers::Quantity;
 pub use variable::{FunctionVar, Functions, Variable, Variables};
+#[cfg(all(feature = "rational", feature = "complex"))]
+compile_error!("the rational feature does not support complex numbers");
 #[cfg(not(feature = "complex"))]
 pub type NBase = ucalc_numbers::Float;
 #[cfg(feature = "complex")]
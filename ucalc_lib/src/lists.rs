@@ -0,0 +1,68 @@
+use crate::Number;
+use ucalc_numbers::{Constant, Float, FloatFunctions, FloatTrait, RealTrait};
+const MAX_LEN: usize = 1 << 20;
+pub(crate) fn elements(n: Number) -> Vec<Number> {
+    match n {
+        Number::List(l) => l,
+        #[cfg(feature = "vector")]
+        Number::Vector(v) => v.into_vec().into_iter().map(Number::Value).collect(),
+        n => vec![n],
+    }
+}
+fn to_index(n: Number, len: usize) -> usize {
+    usize::try_from(n.to_real().into_isize())
+        .unwrap_or(0)
+        .min(len)
+}
+pub(crate) fn range(start: Number, end: Number, step: Number) -> Number {
+    if step.is_zero() {
+        return Number::List(Vec::new());
+    }
+    let count = ((end - &start) / step.clone()).to_real();
+    let tol = Float::from(2.0f64.powi(-32)) * (count.clone().abs() + Float::from(1));
+    let count = (count + tol).floor();
+    if count.is_nan() || count >= Float::from(MAX_LEN) {
+        return Number::from(Constant::Nan);
+    }
+    if count < Float::from(0) {
+        return Number::List(Vec::new());
+    }
+    Number::List(
+        (0..=count.into_usize())
+            .map(|i| start.clone() + step.clone() * Number::from(i))
+            .collect(),
+    )
+}
+pub(crate) fn sort(n: Number) -> Number {
+    let mut l = elements(n);
+    l.sort_by(|a, b| a.total_cmp(b));
+    Number::List(l)
+}
+pub(crate) fn reverse(n: Number) -> Number {
+    let mut l = elements(n);
+    l.reverse();
+    Number::List(l)
+}
+pub(crate) fn concat(a: Number, b: Number) -> Number {
+    let mut l = elements(a);
+    l.extend(elements(b));
+    Number::List(l)
+}
+pub(crate) fn zip(a: Number, b: Number) -> Number {
+    Number::List(
+        elements(a)
+            .into_iter()
+            .zip(elements(b))
+            .map(|(a, b)| Number::List(vec![a, b]))
+            .collect(),
+    )
+}
+pub(crate) fn slice(n: Number, start: Number, end: Option<Number>) -> Number {
+    let mut l = elements(n);
+    let len = l.len();
+    let end = end.map_or(len, |n| to_index(n, len));
+    let start = to_index(start, len).min(end);
+    l.truncate(end);
+    l.drain(..start);
+    Number::List(l)
+}
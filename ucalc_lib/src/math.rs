@@ -1,9 +1,208 @@
 use crate::Number;
-use crate::compute::{Compute, StackToken};
+use crate::compute::{Compute, EvalError, StackToken};
+#[cfg(all(feature = "vector", feature = "matrix"))]
+use crate::parse::TokensSlice;
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
-use ucalc_numbers::{Float, FloatFunctions, FloatTrait, UInteger};
+use ucalc_numbers::Constant;
+use ucalc_numbers::{Float, FloatFunctions, FloatTrait, Pow, RealTrait, UInteger};
+#[cfg(feature = "list")]
+const MAX_SAMPLES: usize = 1 << 12;
+const MAX_EPSILON_BITS: usize = 1 << 12;
+fn epsilon() -> Float {
+    let one = Float::from(1);
+    let mut eps = one.clone();
+    for _ in 0..MAX_EPSILON_BITS {
+        let half = eps.clone() / Float::from(2);
+        if one.clone() + half.clone() == one {
+            break;
+        }
+        eps = half;
+    }
+    eps
+}
+// the kronrod nodes and the numerical derivative step are only as precise as f64
+fn tolerance() -> Float {
+    epsilon().max(&Float::from(f64::EPSILON)) * Float::from(1 << 12)
+}
 impl Compute<'_> {
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    pub fn solve_system(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        tokens: &[&TokensSlice],
+        var: usize,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        let n = tokens.len();
+        let origin = self.residuals(
+            inner_vars,
+            stack,
+            tokens,
+            #[cfg(feature = "float_rand")]
+            rand,
+        )?;
+        let mut jacobian = vec![Number::default(); n * n];
+        for j in 0..n {
+            inner_vars[var + j] = Number::from(1);
+            let col = self.residuals(
+                inner_vars,
+                stack,
+                tokens,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            inner_vars[var + j] = Number::default();
+            for (i, y) in col.into_iter().enumerate() {
+                jacobian[i * n + j] = y - &origin[i];
+            }
+        }
+        let epsilon = Float::from(2.0f64.powi(-32));
+        let mut linear = true;
+        for offset in [0.5, -1.75] {
+            for j in 0..n {
+                inner_vars[var + j] = Number::from(Float::from(j as f64 + offset));
+            }
+            let test = self.residuals(
+                inner_vars,
+                stack,
+                tokens,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            linear = test.into_iter().enumerate().all(|(i, y)| {
+                let mut guess = origin[i].clone();
+                for j in 0..n {
+                    guess += jacobian[i * n + j].clone() * &inner_vars[var + j];
+                }
+                let scale = y.clone().abs() + Float::from(1);
+                (y - guess).abs() <= epsilon.clone() * scale
+            });
+            if !linear {
+                break;
+            }
+        }
+        let ret = if linear {
+            linear_solve(
+                jacobian,
+                origin.into_iter().map(|y| Number::default() - y).collect(),
+            )
+        } else {
+            for j in 0..n {
+                inner_vars[var + j] = Number::from(Float::from(j as f64 + 0.5));
+            }
+            let ret = self.newton_system(
+                inner_vars,
+                stack,
+                tokens,
+                var,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            #[cfg(feature = "complex")]
+            let ret = if ret.is_none() {
+                for j in 0..n {
+                    inner_vars[var + j] =
+                        Number::from(Float::from(j as f64 + 0.5)) + Number::from((0, 1));
+                }
+                self.newton_system(
+                    inner_vars,
+                    stack,
+                    tokens,
+                    var,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?
+            } else {
+                ret
+            };
+            ret
+        };
+        Ok(Number::Vector(
+            ret.ok_or(EvalError::NoSolution)?
+                .into_iter()
+                .map(|n| n.get_value().cloned().unwrap_or(Constant::Nan.into()))
+                .collect::<Vec<_>>()
+                .into(),
+        ))
+    }
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    fn newton_system(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        tokens: &[&TokensSlice],
+        var: usize,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Option<Vec<Number>>, EvalError> {
+        let n = tokens.len();
+        for _ in 0..64 {
+            let y = self.residuals(
+                inner_vars,
+                stack,
+                tokens,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            if y.iter().all(|y| y.is_zero()) {
+                break;
+            }
+            let mut jacobian = Vec::with_capacity(n * n);
+            for tokens in tokens {
+                for j in 0..n {
+                    let point = inner_vars[var + j].clone();
+                    jacobian.push(self.tokens(tokens).numerical_derivative(
+                        inner_vars,
+                        stack,
+                        point.clone(),
+                        (var + j) as u16,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?);
+                    inner_vars[var + j] = point;
+                }
+            }
+            let Some(delta) = linear_solve(jacobian, y) else {
+                break;
+            };
+            for (j, d) in delta.into_iter().enumerate() {
+                inner_vars[var + j] -= d;
+            }
+        }
+        let tol = Float::from(2.0f64.powi(-16));
+        Ok(self
+            .residuals(
+                inner_vars,
+                stack,
+                tokens,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?
+            .into_iter()
+            .all(|y| y.abs() <= tol)
+            .then(|| inner_vars[var..var + n].to_vec()))
+    }
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    fn residuals(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        tokens: &[&TokensSlice],
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Vec<Number>, EvalError> {
+        tokens
+            .iter()
+            .map(|tokens| {
+                self.tokens(tokens).compute(
+                    inner_vars,
+                    stack,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )
+            })
+            .collect()
+    }
     pub fn numerical_solve(
         &self,
         inner_vars: &mut Vec<Number>,
@@ -11,29 +210,189 @@ impl Compute<'_> {
         point: Number,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
+        #[cfg(feature = "complex")]
+        let complex = point.clone() + Number::from((0, 1));
+        if let Some(x) = self.newton(
+            inner_vars,
+            stack,
+            point,
+            var,
+            #[cfg(feature = "float_rand")]
+            rand,
+        )? {
+            return Ok(x);
+        }
+        #[cfg(feature = "complex")]
+        if let Some(x) = self.newton(
+            inner_vars,
+            stack,
+            complex,
+            var,
+            #[cfg(feature = "float_rand")]
+            rand,
+        )? {
+            return Ok(x);
+        }
+        Ok(Number::from(Constant::Nan))
+    }
+    fn newton(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        point: Number,
+        var: u16,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Option<Number>, EvalError> {
+        let tol = tolerance();
         inner_vars[var as usize] = point;
-        for _ in 0..64 {
+        for _ in 0..128 {
             let y = self.compute(
                 inner_vars,
                 stack,
                 #[cfg(feature = "float_rand")]
                 rand,
-            );
+            )?;
             if y.is_zero() {
-                break;
+                return Ok(Some(inner_vars[var as usize].clone()));
             }
-            let val = self.numerical_derivative(
+            let x = inner_vars[var as usize].clone();
+            let step = y / self.numerical_derivative(
                 inner_vars,
                 stack,
-                inner_vars[var as usize].clone(),
+                x.clone(),
                 var,
                 #[cfg(feature = "float_rand")]
                 rand,
-            );
-            inner_vars[var as usize] -= val.recip() * y;
+            )?;
+            inner_vars[var as usize] = x.clone() - &step;
+            let step = step.abs();
+            if step.is_nan() {
+                return Ok(None);
+            }
+            if step <= tol.clone() * (x.abs() + Float::from(1)) {
+                let y = self.compute(
+                    inner_vars,
+                    stack,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?;
+                return Ok(if y.abs() <= Float::from(2.0f64.powi(-16)) {
+                    Some(inner_vars[var as usize].clone())
+                } else {
+                    None
+                });
+            }
         }
-        inner_vars[var as usize].clone()
+        Ok(None)
+    }
+    pub fn numerical_bracket(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        start: Number,
+        end: Number,
+        var: u16,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        Ok(brent(
+            &mut |x| {
+                inner_vars[var as usize] = Number::from(x);
+                Ok(self
+                    .compute(
+                        inner_vars,
+                        stack,
+                        #[cfg(feature = "float_rand")]
+                        rand,
+                    )?
+                    .real()
+                    .clone())
+            },
+            start.real().clone(),
+            end.real().clone(),
+        )?
+        .map_or(Number::from(Constant::Nan), Number::from))
+    }
+    #[cfg(feature = "list")]
+    pub fn numerical_roots(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        start: Number,
+        end: Number,
+        var: u16,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        let start = start.real().clone();
+        let mut f = |x: Float| {
+            inner_vars[var as usize] = Number::from(x);
+            Ok(self
+                .compute(
+                    inner_vars,
+                    stack,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )?
+                .real()
+                .clone())
+        };
+        let mut n = 256;
+        let (step, points, values) = loop {
+            let step = (end.real().clone() - start.clone()) / Float::from(n);
+            let points = (0..=n)
+                .map(|i| start.clone() + step.clone() * Float::from(i))
+                .collect::<Vec<_>>();
+            let values = points
+                .iter()
+                .map(|x| f(x.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let changes = values
+                .windows(2)
+                .filter(|v| v[0].is_sign_negative() != v[1].is_sign_negative())
+                .count();
+            if changes * 8 <= n {
+                break (step, points, values);
+            }
+            if n >= MAX_SAMPLES {
+                return Err(EvalError::TooManySolutions);
+            }
+            n *= 2;
+        };
+        let mut roots: Vec<Float> = Vec::new();
+        let mut minima = Vec::new();
+        for i in 0..n {
+            if let Some(x) = brent(&mut f, points[i].clone(), points[i + 1].clone())?
+                && f(x.clone())?.abs() <= Float::from(2.0f64.powi(-16))
+            {
+                roots.push(x);
+            } else if i != 0
+                && values[i].clone().abs() < values[i - 1].clone().abs()
+                && values[i].clone().abs() < values[i + 1].clone().abs()
+            {
+                minima.push(points[i].clone());
+            }
+        }
+        for point in minima {
+            if let Some(x) = self.newton(
+                inner_vars,
+                stack,
+                Number::from(point.clone()),
+                var,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )? {
+                let x = x.real().clone();
+                if (x.clone() - point).abs() <= step.clone().abs() {
+                    roots.push(x);
+                }
+            }
+        }
+        roots.sort_by(|a, b| a.total_cmp(b));
+        let tol = Float::from(2.0f64.powi(-20));
+        roots.dedup_by(|a, b| {
+            (a.clone() - b.clone()).abs() <= tol.clone() * (b.clone().abs() + Float::from(1))
+        });
+        Ok(Number::List(roots.into_iter().map(Number::from).collect()))
     }
     pub fn numerical_derivative(
         &self,
@@ -42,7 +401,7 @@ impl Compute<'_> {
         point: Number,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         let epsilon = Float::from(2.0f64.powi(-32));
         inner_vars[var as usize] = point.clone() - &epsilon;
         let start = self.compute(
@@ -50,15 +409,15 @@ impl Compute<'_> {
             stack,
             #[cfg(feature = "float_rand")]
             rand,
-        );
+        )?;
         inner_vars[var as usize] = point + &epsilon;
         let end = self.compute(
             inner_vars,
             stack,
             #[cfg(feature = "float_rand")]
             rand,
-        );
-        (end - start) / (Float::from(2) * epsilon)
+        )?;
+        Ok((end - start) / (Float::from(2) * epsilon))
     }
     pub fn numerical_nth_derivative(
         &self,
@@ -68,7 +427,7 @@ impl Compute<'_> {
         point: Number,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<Number, EvalError> {
         let e = -32;
         let e = e - e % n as i32;
         let epsilon = Float::from(2.0f64.powi(e / n as i32));
@@ -86,21 +445,21 @@ impl Compute<'_> {
                     stack,
                     #[cfg(feature = "float_rand")]
                     rand,
-                ) * r;
+                )? * r;
             } else {
                 sum -= self.compute(
                     inner_vars,
                     stack,
                     #[cfg(feature = "float_rand")]
                     rand,
-                ) * r;
+                )? * r;
             }
             if k != n {
                 inner_vars[var as usize] += &epsilon;
             }
         }
         let epsilon = Float::from(2.0f64.powi(e));
-        sum / epsilon
+        Ok(sum / epsilon)
     }
     pub fn numerical_integral(
         &self,
@@ -110,30 +469,20 @@ impl Compute<'_> {
         end: Number,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
-        let k = 1024;
-        let epsilon = (end - &start) / Float::from(k);
-        let mut total = Number::from(0);
-        inner_vars[var as usize] = start;
-        let mut last = self.compute(
-            inner_vars,
-            stack,
-            #[cfg(feature = "float_rand")]
-            rand,
-        );
-        let mid = epsilon.clone() / Float::from(2);
-        for _ in 1..=k {
-            inner_vars[var as usize] += &epsilon;
-            let cur = self.compute(
-                inner_vars,
-                stack,
-                #[cfg(feature = "float_rand")]
-                rand,
-            );
-            total += (last + &cur) * &mid;
-            last = cur;
-        }
-        total
+    ) -> Result<(Number, Float), EvalError> {
+        integrate(
+            |x| {
+                inner_vars[var as usize] = x;
+                self.compute(
+                    inner_vars,
+                    stack,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )
+            },
+            start,
+            end,
+        )
     }
     #[allow(clippy::too_many_arguments)]
     pub fn numerical_nth_integral(
@@ -145,7 +494,7 @@ impl Compute<'_> {
         end: Number,
         var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
+    ) -> Result<(Number, Float), EvalError> {
         if n == 0 {
             inner_vars[var as usize] = end;
             let end = self.compute(
@@ -153,55 +502,64 @@ impl Compute<'_> {
                 stack,
                 #[cfg(feature = "float_rand")]
                 rand,
-            );
+            )?;
             inner_vars[var as usize] = start;
             let start = self.compute(
                 inner_vars,
                 stack,
                 #[cfg(feature = "float_rand")]
                 rand,
-            );
-            return end - start;
-        }
-        let k = 1024;
-        let kf = Float::from(k);
-        let fact = Float::from(UInteger::from((n - 1) as usize).factorial().0);
-        let epsilon = (end - &start) / &kf;
-        let mut total = Number::from(0);
-        inner_vars[var as usize] = start;
-        let mut last = self.compute(
+            )?;
+            return Ok((end - start, Float::from(0)));
+        }
+        let fact = Float::from(UInteger::from((n - 1) as usize).factorial().unwrap().0);
+        integrate(
+            |x| {
+                let mut weight = Number::from(1);
+                for _ in 1..n {
+                    weight *= end.clone() - &x;
+                }
+                inner_vars[var as usize] = x;
+                Ok(self.compute(
+                    inner_vars,
+                    stack,
+                    #[cfg(feature = "float_rand")]
+                    rand,
+                )? * weight
+                    / &fact)
+            },
+            start.clone(),
+            end.clone(),
+        )
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn numerical_differential(
+        self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        x_0: Number,
+        t_0: Number,
+        t_1: Number,
+        x_var: u16,
+        t_var: u16,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        self.dormand_prince(
             inner_vars,
             stack,
+            x_0,
+            t_0,
+            t_1,
+            x_var,
+            t_var,
+            &mut |_, _| {},
             #[cfg(feature = "float_rand")]
             rand,
-        );
-        for _ in 1..n {
-            last *= &epsilon;
-            last *= &kf;
-        }
-        last /= &fact;
-        let mid = epsilon.clone() / Float::from(2);
-        for i in 1..=k {
-            inner_vars[var as usize] += &epsilon;
-            let mut cur = self.compute(
-                inner_vars,
-                stack,
-                #[cfg(feature = "float_rand")]
-                rand,
-            );
-            let kf = Float::from(k - i);
-            for _ in 1..n {
-                cur *= &epsilon;
-                cur *= &kf;
-            }
-            cur /= &fact;
-            total += (last + &cur) * &mid;
-            last = cur;
-        }
-        total
+        )
     }
+    #[cfg(feature = "list")]
     #[allow(clippy::too_many_arguments)]
-    pub fn numerical_differential(
+    pub fn numerical_trajectory(
         self,
         inner_vars: &mut Vec<Number>,
         stack: &mut Vec<StackToken>,
@@ -211,21 +569,406 @@ impl Compute<'_> {
         x_var: u16,
         t_var: u16,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Number {
-        let n = 1024;
-        let epsilon = (t_1 - &t_0) / Float::from(n);
-        inner_vars[x_var as usize] = x_0;
-        inner_vars[t_var as usize] = t_0;
-        for _ in 1..=n {
-            let delta = self.compute(
+    ) -> Result<Number, EvalError> {
+        let mut trajectory = vec![Number::List(vec![t_0.clone(), x_0.clone()])];
+        self.dormand_prince(
+            inner_vars,
+            stack,
+            x_0,
+            t_0,
+            t_1,
+            x_var,
+            t_var,
+            &mut |t, x| trajectory.push(Number::List(vec![t.clone(), x.clone()])),
+            #[cfg(feature = "float_rand")]
+            rand,
+        )?;
+        Ok(Number::List(trajectory))
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn dormand_prince(
+        self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        mut x: Number,
+        mut t: Number,
+        end: Number,
+        x_var: u16,
+        t_var: u16,
+        step: &mut impl FnMut(&Number, &Number),
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        let mut f = |x: &Number, t: &Number| {
+            inner_vars[x_var as usize] = x.clone();
+            inner_vars[t_var as usize] = t.clone();
+            let dx = self.compute(
                 inner_vars,
                 stack,
                 #[cfg(feature = "float_rand")]
                 rand,
-            ) * &epsilon;
-            inner_vars[x_var as usize] += delta;
-            inner_vars[t_var as usize] += &epsilon;
+            )?;
+            #[cfg(feature = "vector")]
+            if let (Number::Vector(x), Number::Value(dx)) = (x, &dx) {
+                let mut v = x[1..x.len()].to_vec();
+                v.push(dx.clone());
+                return Ok(Number::Vector(v.into()));
+            }
+            Ok(dx)
+        };
+        let tol = Float::from(2.0f64.powi(-32));
+        let mut h = (end.clone() - &t) / Float::from(64);
+        let mut k = vec![f(&x, &t)?];
+        for _ in 0..65536 {
+            let remaining = end.clone() - &t;
+            let last = h.clone().abs() >= remaining.clone().abs();
+            if last {
+                h = remaining;
+            }
+            k.truncate(1);
+            for (c, a) in DP_C.iter().zip(DP_A) {
+                let mut y = x.clone();
+                for (a, k) in a.iter().zip(&k) {
+                    y += k.clone() * &h * Float::from(*a);
+                }
+                k.push(f(&y, &(t.clone() + h.clone() * Float::from(*c)))?);
+            }
+            let mut next = x.clone();
+            for (b, k) in DP_A[5].iter().zip(&k) {
+                next += k.clone() * &h * Float::from(*b);
+            }
+            let mut err = Number::default();
+            for (e, k) in DP_E.iter().zip(&k) {
+                err += k.clone() * &h * Float::from(*e);
+            }
+            let err = norm(err);
+            if err.is_nan() {
+                return Ok(Number::from(Constant::Nan));
+            }
+            let scale = tol.clone() * (norm(next.clone()) + Float::from(1));
+            let accept = err <= scale;
+            if accept {
+                t += &h;
+                x = next;
+                k.swap(0, 6);
+                step(&t, &x);
+                if last {
+                    return Ok(x);
+                }
+            }
+            let factor = if err.is_zero() {
+                Float::from(5)
+            } else {
+                let factor = (scale / err).pow(Float::from(0.2)) * Float::from(0.9);
+                if factor > Float::from(5) {
+                    Float::from(5)
+                } else if factor < Float::from(0.2) {
+                    Float::from(0.2)
+                } else {
+                    factor
+                }
+            };
+            h *= factor;
+        }
+        Ok(Number::from(Constant::Nan))
+    }
+}
+const DP_C: [f64; 6] = [0.2, 0.3, 0.8, 8.0 / 9.0, 1.0, 1.0];
+const DP_A: [&[f64]; 6] = [
+    &[0.2],
+    &[3.0 / 40.0, 9.0 / 40.0],
+    &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+    &[
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+    ],
+    &[
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+    ],
+    &[
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+const DP_E: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+#[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+fn norm(mut n: Number) -> Float {
+    let mut sum = Float::from(0);
+    n.map_mut(&mut |a| sum += a.clone().abs());
+    sum
+}
+#[cfg(not(any(feature = "vector", feature = "matrix", feature = "list")))]
+fn norm(n: Number) -> Float {
+    n.abs()
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+fn linear_solve(mut a: Vec<Number>, mut b: Vec<Number>) -> Option<Vec<Number>> {
+    let n = b.len();
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| {
+                a[i * n + k]
+                    .clone()
+                    .abs()
+                    .total_cmp(&a[j * n + k].clone().abs())
+            })
+            .unwrap();
+        if a[pivot * n + k].is_zero() {
+            return None;
+        }
+        if pivot != k {
+            for j in 0..n {
+                a.swap(pivot * n + j, k * n + j);
+            }
+            b.swap(pivot, k);
+        }
+        for i in k + 1..n {
+            let factor = a[i * n + k].clone() / &a[k * n + k];
+            for j in k..n {
+                let d = factor.clone() * &a[k * n + j];
+                a[i * n + j] -= d;
+            }
+            let d = factor * &b[k];
+            b[i] -= d;
+        }
+    }
+    for k in (0..n).rev() {
+        for j in k + 1..n {
+            let d = a[k * n + j].clone() * &b[j];
+            b[k] -= d;
+        }
+        b[k] /= &a[k * n + k];
+    }
+    Some(b)
+}
+const KRONROD_NODES: [f64; 8] = [
+    0.9914553711208126,
+    0.9491079123427585,
+    0.8648644233597691,
+    0.7415311855993945,
+    0.5860872354676911,
+    0.4058451513773972,
+    0.20778495500789848,
+    0.0,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224,
+    0.06309209262997856,
+    0.10479001032225019,
+    0.14065325971552592,
+    0.1690047266392679,
+    0.19035057806478542,
+    0.20443294007529889,
+    0.20948214108472782,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.1294849661688697,
+    0.27970539148927664,
+    0.3818300505051189,
+    0.4179591836734694,
+];
+fn kronrod(
+    f: &mut impl FnMut(Number) -> Result<Number, EvalError>,
+    start: &Number,
+    end: &Number,
+) -> Result<(Number, Float), EvalError> {
+    let half = (end.clone() - start) / Float::from(2);
+    let center = start.clone() + &half;
+    let mid = f(center.clone())?;
+    let mut kronrod = mid.clone() * Float::from(KRONROD_WEIGHTS[7]);
+    let mut gauss = mid * Float::from(GAUSS_WEIGHTS[3]);
+    for j in 0..7 {
+        let dx = half.clone() * Float::from(KRONROD_NODES[j]);
+        let sum = f(center.clone() - &dx)? + f(center.clone() + dx)?;
+        kronrod += sum.clone() * Float::from(KRONROD_WEIGHTS[j]);
+        if j % 2 == 1 {
+            gauss += sum * Float::from(GAUSS_WEIGHTS[j / 2]);
+        }
+    }
+    let err = ((kronrod.clone() - gauss) * &half).abs();
+    Ok((kronrod * half, err))
+}
+fn adaptive(
+    f: &mut impl FnMut(Number) -> Result<Number, EvalError>,
+    start: Number,
+    end: Number,
+) -> Result<(Number, Float), EvalError> {
+    let tol = tolerance();
+    let (value, err) = kronrod(f, &start, &end)?;
+    let mut intervals = vec![(start, end, value, err)];
+    loop {
+        let total = intervals
+            .iter()
+            .map(|(_, _, v, _)| v.clone())
+            .fold(Number::default(), |a, b| a + b);
+        let err = intervals
+            .iter()
+            .map(|(_, _, _, e)| e.clone())
+            .fold(Float::from(0), |a, b| a + b);
+        let bound = tol.clone() * (total.clone().abs() + Float::from(1));
+        if err.clone() <= bound || err.is_nan() {
+            return Ok((total, err));
+        }
+        if intervals.len() >= 512 {
+            return if err.clone() <= bound * Float::from(2.0f64.powi(24)) {
+                Ok((total, err))
+            } else {
+                Ok((Number::from(Constant::Nan), err))
+            };
+        }
+        let i = (0..intervals.len())
+            .max_by(|&i, &j| intervals[i].3.total_cmp(&intervals[j].3))
+            .unwrap();
+        let (start, end, _, _) = intervals.swap_remove(i);
+        let mid = (start.clone() + &end) / Float::from(2);
+        let (a, e) = kronrod(f, &start, &mid)?;
+        let (b, g) = kronrod(f, &mid, &end)?;
+        intervals.push((start, mid.clone(), a, e));
+        intervals.push((mid, end, b, g));
+    }
+}
+fn integrate(
+    mut f: impl FnMut(Number) -> Result<Number, EvalError>,
+    start: Number,
+    end: Number,
+) -> Result<(Number, Float), EvalError> {
+    if start == end {
+        return Ok((Number::default(), Float::from(0)));
+    }
+    let inf = Float::from(Constant::Infinity);
+    let (lower, upper) = (*start.real() == -inf.clone(), *end.real() == inf);
+    if *start.real() == inf || *end.real() == -inf.clone() {
+        let (value, err) = integrate(f, end, start)?;
+        return Ok((-value, err));
+    }
+    let one = Number::from(1);
+    match (lower, upper) {
+        (true, true) => adaptive(
+            &mut |t| {
+                let s = one.clone() - t.clone() * &t;
+                Ok(f(t.clone() / &s)? * (one.clone() + t.clone() * &t) / (s.clone() * &s))
+            },
+            -one.clone(),
+            one.clone(),
+        ),
+        (false, true) => adaptive(
+            &mut |t| {
+                let s = one.clone() - &t;
+                Ok(f(start.clone() + t / &s)? / (s.clone() * &s))
+            },
+            Number::from(0),
+            one.clone(),
+        ),
+        (true, false) => adaptive(
+            &mut |t| Ok(f(end.clone() - (one.clone() - &t) / &t)? / (t.clone() * &t)),
+            Number::from(0),
+            one.clone(),
+        ),
+        (false, false) => adaptive(&mut f, start, end),
+    }
+}
+fn brent(
+    f: &mut impl FnMut(Float) -> Result<Float, EvalError>,
+    mut a: Float,
+    mut b: Float,
+) -> Result<Option<Float>, EvalError> {
+    let mut fa = f(a.clone())?;
+    let mut fb = f(b.clone())?;
+    if fa.is_zero() {
+        return Ok(Some(a));
+    }
+    if fb.is_zero() {
+        return Ok(Some(b));
+    }
+    if fa.is_sign_negative() == fb.is_sign_negative() {
+        return Ok(None);
+    }
+    let (mut c, mut fc) = (a.clone(), fa.clone());
+    let mut d = b.clone() - a.clone();
+    let mut e = d.clone();
+    for _ in 0..128 {
+        if fb.is_sign_negative() == fc.is_sign_negative() {
+            c = a.clone();
+            fc = fa.clone();
+            d = b.clone() - a.clone();
+            e = d.clone();
+        }
+        if fc.clone().abs() < fb.clone().abs() {
+            a = b;
+            b = c;
+            c = a.clone();
+            fa = fb;
+            fb = fc;
+            fc = fa.clone();
+        }
+        let tol = Float::from(2.0f64.powi(-52)) * (b.clone().abs() + Float::from(1));
+        let m = (c.clone() - b.clone()) / Float::from(2);
+        if m.clone().abs() <= tol || fb.is_zero() {
+            return Ok(Some(b));
+        }
+        if e.clone().abs() >= tol && fa.clone().abs() > fb.clone().abs() {
+            let s = fb.clone() / fa.clone();
+            let (mut p, mut q) = if a == c {
+                (Float::from(2) * m.clone() * s.clone(), Float::from(1) - s)
+            } else {
+                let q = fa.clone() / fc.clone();
+                let r = fb.clone() / fc.clone();
+                (
+                    s.clone()
+                        * (Float::from(2) * m.clone() * q.clone() * (q.clone() - r.clone())
+                            - (b.clone() - a.clone()) * (r.clone() - Float::from(1))),
+                    (q - Float::from(1)) * (r - Float::from(1)) * (s - Float::from(1)),
+                )
+            };
+            if p > Float::from(0) {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            let bound = Float::from(3) * m.clone() * q.clone() - (tol.clone() * q.clone()).abs();
+            let bound = if bound < (e.clone() * q.clone()).abs() {
+                bound
+            } else {
+                (e.clone() * q.clone()).abs()
+            };
+            if Float::from(2) * p.clone() < bound {
+                e = d;
+                d = p / q;
+            } else {
+                d = m.clone();
+                e = m.clone();
+            }
+        } else {
+            d = m.clone();
+            e = m.clone();
+        }
+        a = b.clone();
+        fa = fb;
+        if d.clone().abs() > tol {
+            b += d.clone();
+        } else if m.is_sign_negative() {
+            b -= tol;
+        } else {
+            b += tol;
         }
-        inner_vars[x_var as usize].clone()
+        fb = f(b.clone())?;
     }
+    Ok(None)
 }
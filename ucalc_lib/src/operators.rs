@@ -104,6 +104,8 @@ impl From<Bracket> for Operator {
 pub enum Bracket {
     Absolute,
     Parenthesis,
+    #[cfg(feature = "vector")]
+    Square,
 }
 impl TryFrom<&str> for Operator {
     type Error = ();
@@ -1,6 +1,9 @@
 #[cfg(feature = "units")]
 use crate::UNITS;
+use crate::compute::EvalError;
 use crate::functions::Function;
+#[cfg(feature = "vector")]
+use crate::functions::ModifyInputs;
 use crate::operators::{Bracket, Operator};
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
@@ -67,6 +70,7 @@ pub(crate) enum NewCustom<'a> {
 pub enum ParseReturn {
     Tokens(Tokens),
     Graph(Tokens, Vec<bool>),
+    Expr(Tokens),
     Var,
 }
 impl ParseReturn {
@@ -80,6 +84,7 @@ impl ParseReturn {
         match self {
             Self::Tokens(t) => t,
             Self::Graph(t, _) => t,
+            Self::Expr(t) => t,
             Self::Var => unreachable!(),
         }
     }
@@ -99,7 +104,7 @@ impl Tokens {
         base: u8,
         rpn: bool,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Result<ParseReturn, ParseError<'a>> {
+    ) -> Result<ParseReturn, ParseErrorSpan<'a>> {
         if rpn {
             Self::rpn(
                 value,
@@ -136,21 +141,25 @@ impl Tokens {
         simplify: bool,
         base: u8,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Result<ParseReturn, ParseError<'a>> {
+    ) -> Result<ParseReturn, ParseErrorSpan<'a>> {
         let mut tokens = Tokens(Vec::with_capacity(value.len()));
         let mut has_graph_vars = vec![false; graph_vars.len()];
         let mut inputs = None;
+        let mut span = 0..0;
         let inner = try {
             let mut inner_vars: Vec<&str> = Vec::with_capacity(value.len());
             let mut open_inputs: usize = 0;
+            let mut start = 0;
             for token in value.split(' ') {
+                span = start..start + token.len();
+                start = span.end + 1;
                 match token {
                     "" | "(" | ")" => {}
                     "let" => expect_let = true,
                     "=" if expect_let => {
                         expect_let = false;
                         let Some(name) = inner_vars.pop() else {
-                            return Err(ParseError::VarExpectedName);
+                            Err(ParseError::VarExpectedName)?
                         };
                         if !inner_vars.is_empty() {
                             let (a, b) = funs.add(
@@ -162,12 +171,12 @@ impl Tokens {
                         } else {
                             inner_vars.pop();
                             if !inner_vars.is_empty() {
-                                return Err(ParseError::InnerVarError);
+                                Err(ParseError::InnerVarError)?;
                             }
                             inputs = Some(NewCustom::Var(name));
                         }
                     }
-                    "=" => return Err(ParseError::RpnUnsupported),
+                    "=" => Err(ParseError::RpnUnsupported)?,
                     _ if expect_let && token.chars().all(|c| c.is_alphabetic()) => {
                         inner_vars.push(token)
                     }
@@ -193,7 +202,7 @@ impl Tokens {
                     }
                     _ if let Some(i) = graph_vars.iter().copied().position(|v| v == token) => {
                         if matches!(inputs, Some(NewCustom::Var(_))) {
-                            return Err(ParseError::GraphVarError);
+                            Err(ParseError::GraphVarError)?;
                         }
                         has_graph_vars[i] = true;
                         open_inputs += 1;
@@ -204,7 +213,7 @@ impl Tokens {
                         && let Some(j) = graph_vars.iter().copied().position(|v| v == "y") =>
                     {
                         if matches!(inputs, Some(NewCustom::Var(_))) {
-                            return Err(ParseError::GraphVarError);
+                            Err(ParseError::GraphVarError)?;
                         }
                         has_graph_vars[i] = true;
                         has_graph_vars[j] = true;
@@ -217,8 +226,12 @@ impl Tokens {
                         open_inputs = open_inputs
                             .checked_sub(fun.inputs().get() as usize - 1)
                             .ok_or(ParseError::MissingInput)?;
-                        tokens.compact_args(fun, &mut inner_vars, funs);
-                        tokens.push(fun.into());
+                        if fun == Function::Diff {
+                            tokens.push_diff(funs)?;
+                        } else {
+                            tokens.compact_args(fun, &mut inner_vars, funs);
+                            tokens.push(fun.into());
+                        }
                     }
                     _ if let Some(k) = token
                         .rfind(|c: char| c != '\'')
@@ -253,7 +266,7 @@ impl Tokens {
                         let inputs = token[i + 1..token.len() - (j + k)].parse().unwrap();
                         fun.set_inputs(inputs);
                         open_inputs = open_inputs
-                            .checked_sub(fun.inputs().get() as usize - 1)
+                            .checked_sub(inputs.get() as usize - 1)
                             .ok_or(ParseError::MissingInput)?;
                         let mut d = Derivative::from((j + k) as u8)?;
                         tokens.compact_args(fun, &mut inner_vars, funs);
@@ -294,11 +307,12 @@ impl Tokens {
                         open_inputs += 1;
                         tokens.push(n.into())
                     }
-                    _ => return Err(ParseError::UnknownToken(token)),
+                    _ => Err(ParseError::UnknownToken(token))?,
                 }
             }
+            span = value.len()..value.len();
             if open_inputs > 1 || (open_inputs == 0 && !tokens.is_empty()) {
-                return Err(ParseError::MissingInput);
+                Err(ParseError::MissingInput)?;
             }
         };
         if let Err(e) = inner {
@@ -310,17 +324,22 @@ impl Tokens {
                     funs[v as usize].name = n
                 }
             }
-            return Err(e);
+            return Err(ParseErrorSpan { error: e, span });
         }
-        Ok(tokens.end(
-            inputs,
-            simplify,
-            vars,
-            funs,
-            has_graph_vars,
-            #[cfg(feature = "float_rand")]
-            rand,
-        ))
+        tokens
+            .end(
+                inputs,
+                simplify,
+                vars,
+                funs,
+                has_graph_vars,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )
+            .map_err(|error| ParseErrorSpan {
+                error: ParseError::Eval(error),
+                span: 0..value.len(),
+            })
     }
     #[allow(clippy::too_many_arguments)]
     pub fn infix<'a>(
@@ -332,13 +351,17 @@ impl Tokens {
         simplify: bool,
         base: u8,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Result<ParseReturn, ParseError<'a>> {
+    ) -> Result<ParseReturn, ParseErrorSpan<'a>> {
         let mut inputs = None;
         let mut tokens = Tokens(Vec::with_capacity(value.len()));
         let mut has_graph_vars = vec![false; graph_vars.len()];
+        let mut span = 0..0;
+        let mut diff = false;
+        let mut expr = false;
         let inner = try {
             let mut operator_stack: Vec<Operator> = Vec::with_capacity(value.len());
             let mut inner_vars: Vec<&str> = Vec::with_capacity(value.len());
+            let mut fun_inputs = 0;
             let mut fn_inputs: Vec<NonZeroU8> = Vec::with_capacity(value.len());
             let mut inner_vars_count: Vec<u8> = Vec::with_capacity(value.len());
             let mut chars = value.char_indices();
@@ -350,11 +373,28 @@ impl Tokens {
             let mut expect_expr = !value.is_empty();
             let mut abs = 0;
             let mut needs_bracket = false;
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            let mut equations = 0usize;
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            let mut commas = 0usize;
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            let mut bad_comma = None;
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            let mut var_tuple: Option<(usize, usize)> = None;
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            let mut after_tuple = false;
             while let Some((i, c)) = chars.next() {
+                span = i..i + c.len_utf8();
                 if needs_bracket && c != '(' {
-                    return Err(ParseError::NeedsBracket);
+                    Err(ParseError::NeedsBracket)?;
                 }
                 needs_bracket = false;
+                #[cfg(all(feature = "vector", feature = "matrix"))]
+                if (var_tuple.is_some() && !(c.is_alphanumeric() || matches!(c, ',' | ')' | ' ')))
+                    || (after_tuple && !matches!(c, ',' | ' '))
+                {
+                    Err(ParseError::InnerVarError)?;
+                }
                 match c {
                     ' ' => {}
                     '@' if let Some(i) = vars.position("@") => {
@@ -408,7 +448,8 @@ impl Tokens {
                             }
                             _ => unreachable!(),
                         }) else {
-                            return Err(ParseError::UnknownToken(s));
+                            span.end = i + 2 + l;
+                            Err(ParseError::UnknownToken(s))?
                         };
                         tokens.last_mul(&mut operator_stack, no_input_left, &mut last_mul, true);
                         tokens.push(float.into());
@@ -440,8 +481,28 @@ impl Tokens {
                                 break;
                             }
                         }
+                        #[cfg(all(feature = "vector", feature = "matrix"))]
+                        if let Some((_, vars)) = var_tuple {
+                            let s = &value[i..i + l];
+                            span.end = i + l;
+                            if !s.chars().all(|c| c.is_alphabetic())
+                                || inner_vars[vars..].contains(&s)
+                            {
+                                Err(ParseError::InnerVarError)?;
+                            }
+                            tokens.push(Token::InnerVar(inner_vars.len() as u16));
+                            inner_vars.push(s);
+                            chars.advance_by(count - 1).unwrap();
+                            no_input_left = false;
+                            last_open = false;
+                            req_input = false;
+                            open_input = true;
+                            expect_expr = false;
+                            continue;
+                        }
                         loop {
                             let s = &value[i..i + l];
+                            span.end = i + l;
                             if i == 0 && s == "let" {
                                 expect_let = true;
                                 open_input = false;
@@ -483,7 +544,7 @@ impl Tokens {
                             } else if let Some(i) = graph_vars.iter().copied().position(|v| v == s)
                             {
                                 if matches!(inputs, Some(NewCustom::Var(_))) {
-                                    return Err(ParseError::GraphVarError);
+                                    Err(ParseError::GraphVarError)?;
                                 }
                                 has_graph_vars[i] = true;
                                 tokens.last_mul(
@@ -500,7 +561,7 @@ impl Tokens {
                                 && let Some(j) = graph_vars.iter().copied().position(|v| v == "y")
                             {
                                 if matches!(inputs, Some(NewCustom::Var(_))) {
-                                    return Err(ParseError::GraphVarError);
+                                    Err(ParseError::GraphVarError)?;
                                 }
                                 has_graph_vars[i] = true;
                                 has_graph_vars[j] = true;
@@ -516,6 +577,7 @@ impl Tokens {
                                 tokens.push(Function::Addi.into());
                                 open_input = true;
                             } else if let Ok(fun) = Function::try_from(s) {
+                                diff |= fun == Function::Diff;
                                 if fun.inputs().get() > 1 {
                                     needs_bracket = true;
                                 }
@@ -602,10 +664,10 @@ impl Tokens {
                         let d = match operator_stack.last_mut() {
                             Some(Operator::Custom(_, d)) => d,
                             Some(Operator::Function(_, d)) => d,
-                            _ => return Err(ParseError::DerivativeError),
+                            _ => Err(ParseError::DerivativeError)?,
                         };
                         if d.is_integral() {
-                            return Err(ParseError::MixedError);
+                            Err(ParseError::MixedError)?;
                         }
                         d.increment()?;
                         open_input = false;
@@ -618,13 +680,13 @@ impl Tokens {
                         let d = match operator_stack.last_mut() {
                             Some(Operator::Custom(_, d)) => d,
                             Some(Operator::Function(_, d)) => d,
-                            _ => return Err(ParseError::IntegralError),
+                            _ => Err(ParseError::IntegralError)?,
                         };
                         if d.is_derivative() {
                             if d.get() == 0 {
                                 d.set_integral()
                             } else {
-                                return Err(ParseError::MixedError);
+                                Err(ParseError::MixedError)?;
                             }
                         }
                         d.increment()?;
@@ -644,8 +706,9 @@ impl Tokens {
                             }
                         }
                         let s = &value[i..i + l];
+                        span.end = i + l;
                         let Some(float) = NumberBase::parse_radix(s, base) else {
-                            return Err(ParseError::UnknownToken(s));
+                            Err(ParseError::UnknownToken(s))?
                         };
                         tokens.last_mul(&mut operator_stack, no_input_left, &mut last_mul, true);
                         tokens.push(float.into());
@@ -768,9 +831,10 @@ impl Tokens {
                                 break;
                             }
                         }
+                        span.end = i + l;
                         #[allow(unused_mut)]
                         let Some(mut float) = NumberBase::parse_radix(&str, base) else {
-                            return Err(ParseError::UnknownToken(&value[i..i + l]));
+                            Err(ParseError::UnknownToken(&value[i..i + l]))?
                         };
                         if imag {
                             #[cfg(feature = "complex")]
@@ -794,17 +858,40 @@ impl Tokens {
                     }
                     ',' => {
                         if req_input || expect_expr {
-                            return Err(ParseError::MissingInput);
+                            Err(ParseError::MissingInput)?;
+                        }
+                        #[cfg(all(feature = "vector", feature = "matrix"))]
+                        if var_tuple.is_some() || after_tuple {
+                            after_tuple = false;
+                            no_input_left = true;
+                            last_open = false;
+                            last_mul = false;
+                            open_input = false;
+                            expect_expr = true;
+                            continue;
                         }
+                        #[cfg(all(feature = "vector", feature = "matrix"))]
+                        let system = fn_inputs.is_empty()
+                            && inputs.is_none()
+                            && !expect_let
+                            && !operator_stack
+                                .iter()
+                                .any(|top| matches!(top, Operator::Bracket(_)));
                         while let Some(top) =
                             operator_stack.pop_if(|top| !matches!(top, Operator::Bracket(_)))
                         {
+                            #[cfg(all(feature = "vector", feature = "matrix"))]
+                            if system && top == Operator::Solve {
+                                tokens.push(Function::Sub.into());
+                                equations += 1;
+                                continue;
+                            }
                             tokens.push_operator(top, &mut inner_vars, &operator_stack, funs)?;
                         }
                         if let Some(last) = fn_inputs.last_mut() {
-                            *last = last.checked_add(1).unwrap();
+                            *last = last.checked_add(1).ok_or(ParseError::ExtraInput)?;
                             if operator_stack.len() < 2 {
-                                return Err(ParseError::CommaError);
+                                Err(ParseError::CommaError)?;
                             }
                             match operator_stack[operator_stack.len() - 2] {
                                 Operator::Custom(_, _) => {}
@@ -814,10 +901,20 @@ impl Tokens {
                                             .extend(iter::repeat_n("", fun.inner_vars() as usize));
                                     }
                                 }
-                                _ => return Err(ParseError::CommaError),
+                                _ => Err(ParseError::CommaError)?,
                             }
                         } else if !expect_let {
-                            return Err(ParseError::CommaError);
+                            #[cfg(all(feature = "vector", feature = "matrix"))]
+                            if system {
+                                commas += 1;
+                                if equations != commas && bad_comma.is_none() {
+                                    bad_comma = Some(span.clone());
+                                }
+                            } else {
+                                Err(ParseError::CommaError)?;
+                            }
+                            #[cfg(not(all(feature = "vector", feature = "matrix")))]
+                            Err(ParseError::CommaError)?;
                         }
                         no_input_left = true;
                         last_open = false;
@@ -827,18 +924,51 @@ impl Tokens {
                     }
                     ')' => {
                         if req_input || expect_expr {
-                            return Err(ParseError::MissingInput);
+                            Err(ParseError::MissingInput)?;
+                        }
+                        #[cfg(all(feature = "vector", feature = "matrix"))]
+                        if let Some((start, _)) = var_tuple.take() {
+                            let count = u8::try_from(tokens.len() - start)
+                                .map_err(|_| ParseError::InnerVarError)?;
+                            if !tokens[start..]
+                                .iter()
+                                .all(|t| matches!(t, Token::InnerVar(_)))
+                            {
+                                Err(ParseError::InnerVarError)?;
+                            }
+                            *inner_vars_count.last_mut().unwrap() = 0;
+                            if count > 1 {
+                                tokens.truncate(start);
+                                operator_stack.pop();
+                                let len = operator_stack.len();
+                                let Operator::Function(_, d) = operator_stack[len - 2] else {
+                                    unreachable!()
+                                };
+                                operator_stack[len - 2] = Operator::Function(
+                                    Function::System(NonZeroU8::new(count).unwrap()),
+                                    d,
+                                );
+                                after_tuple = true;
+                                last_open = false;
+                                open_input = false;
+                                expect_expr = false;
+                                continue;
+                            }
                         }
                         while let Some(top) =
                             operator_stack.pop_if(|top| !matches!(top, Operator::Bracket(_)))
                         {
                             tokens.push_operator(top, &mut inner_vars, &operator_stack, funs)?;
                         }
-                        if matches!(
-                            operator_stack.last(),
-                            Some(Operator::Bracket(Bracket::Absolute))
-                        ) {
-                            return Err(ParseError::AbsoluteBracketFailed);
+                        match operator_stack.last() {
+                            Some(Operator::Bracket(Bracket::Absolute)) => {
+                                Err(ParseError::AbsoluteBracketFailed)?
+                            }
+                            #[cfg(feature = "vector")]
+                            Some(Operator::Bracket(Bracket::Square)) => {
+                                Err(ParseError::SquareBracketFailed)?
+                            }
+                            _ => {}
                         }
                         operator_stack
                             .pop_if(|top| matches!(top, Operator::Bracket(Bracket::Parenthesis)));
@@ -862,6 +992,17 @@ impl Tokens {
                         no_input_left = false;
                     }
                     '(' => {
+                        #[cfg(all(feature = "vector", feature = "matrix"))]
+                        if last_open
+                            && let [
+                                ..,
+                                Operator::Function(Function::Solve, _),
+                                Operator::Bracket(Bracket::Parenthesis),
+                            ] = operator_stack[..]
+                        {
+                            inner_vars.pop();
+                            var_tuple = Some((tokens.len(), inner_vars.len()));
+                        }
                         tokens.last_mul(&mut operator_stack, no_input_left, &mut last_mul, true);
                         operator_stack.push(Bracket::Parenthesis.into());
                         no_input_left = true;
@@ -871,6 +1012,66 @@ impl Tokens {
                         open_input = false;
                         expect_expr = true;
                     }
+                    #[cfg(feature = "vector")]
+                    '[' => {
+                        if open_input && !req_input {
+                            operator_stack.push(Operator::Function(
+                                Function::Index(ModifyInputs::Two),
+                                Derivative::default(),
+                            ));
+                            fn_inputs.push(NonZeroU8::new(2).unwrap());
+                        } else {
+                            tokens.last_mul(
+                                &mut operator_stack,
+                                no_input_left,
+                                &mut last_mul,
+                                false,
+                            );
+                            operator_stack.push(Operator::Function(
+                                Function::Vector(NonZeroU8::new(1).unwrap()),
+                                Derivative::default(),
+                            ));
+                            fn_inputs.push(NonZeroU8::new(1).unwrap());
+                        }
+                        operator_stack.push(Bracket::Square.into());
+                        no_input_left = true;
+                        last_open = true;
+                        req_input = false;
+                        last_mul = false;
+                        open_input = false;
+                        expect_expr = true;
+                    }
+                    #[cfg(feature = "vector")]
+                    ']' => {
+                        if req_input || expect_expr {
+                            Err(ParseError::MissingInput)?;
+                        }
+                        while let Some(top) =
+                            operator_stack.pop_if(|top| !matches!(top, Operator::Bracket(_)))
+                        {
+                            tokens.push_operator(top, &mut inner_vars, &operator_stack, funs)?;
+                        }
+                        if operator_stack
+                            .pop_if(|top| matches!(top, Operator::Bracket(Bracket::Square)))
+                            .is_none()
+                        {
+                            Err(ParseError::RightSquareBracketFailed)?;
+                        }
+                        tokens.close_off_bracket(
+                            &mut operator_stack,
+                            &mut inner_vars,
+                            &mut inner_vars_count,
+                            funs,
+                            &mut fn_inputs,
+                        )?;
+                        last_mul = true;
+                        open_input = true;
+                        expect_expr = false;
+                        last_open = false;
+                        no_input_left = false;
+                    }
+                    #[cfg(not(feature = "vector"))]
+                    '[' | ']' => Err(ParseError::VecMatNotEnabled)?,
                     '|' => {
                         if abs == 0 || last_open || req_input {
                             operator_stack.push(Bracket::Absolute.into());
@@ -896,7 +1097,7 @@ impl Tokens {
                                 operator_stack.pop(),
                                 Some(Operator::Bracket(Bracket::Absolute))
                             ) {
-                                return Err(ParseError::AbsoluteBracketFailed);
+                                Err(ParseError::AbsoluteBracketFailed)?;
                             }
                             tokens.push(Function::Abs.into());
                             if tokens.close_off_bracket(
@@ -929,14 +1130,16 @@ impl Tokens {
                             l += next.len_utf8();
                         }
                         let s = &value[i..i + l];
+                        span.end = i + l;
                         if let Ok(mut operator) = Operator::try_from(s) {
                             if expect_let && operator == Operator::Solve {
                                 open_input = false;
                                 expect_let = false;
                                 let Some(name) = inner_vars.try_remove(0) else {
-                                    return Err(ParseError::VarExpectedName);
+                                    Err(ParseError::VarExpectedName)?
                                 };
                                 if !inner_vars.is_empty() {
+                                    fun_inputs = inner_vars.len();
                                     let (a, b) = funs.add(
                                         vars,
                                         name,
@@ -946,7 +1149,7 @@ impl Tokens {
                                 } else {
                                     inner_vars.pop();
                                     if !inner_vars.is_empty() {
-                                        return Err(ParseError::InnerVarError);
+                                        Err(ParseError::InnerVarError)?;
                                     }
                                     inputs = Some(NewCustom::Var(name));
                                 }
@@ -963,7 +1166,7 @@ impl Tokens {
                                 if !operator.is_unary() {
                                     req_input = true;
                                     if !open_input {
-                                        return Err(ParseError::MissingInput);
+                                        Err(ParseError::MissingInput)?;
                                     }
                                 } else {
                                     req_input = operator.unary_left();
@@ -979,14 +1182,15 @@ impl Tokens {
                                 last_open = false;
                             }
                         } else {
-                            return Err(ParseError::UnknownToken(s));
+                            Err(ParseError::UnknownToken(s))?;
                         }
                         last_mul = false;
                     }
                 }
             }
+            span = value.len()..value.len();
             if req_input || expect_expr {
-                return Err(ParseError::MissingInput);
+                Err(ParseError::MissingInput)?;
             }
             while let Some(operator) = operator_stack.pop() {
                 if let Operator::Bracket(bracket) = operator {
@@ -995,6 +1199,8 @@ impl Tokens {
                             tokens.push(Function::Abs.into());
                         }
                         Bracket::Parenthesis => {}
+                        #[cfg(feature = "vector")]
+                        Bracket::Square => {}
                     };
                     tokens.close_off_bracket(
                         &mut operator_stack,
@@ -1004,11 +1210,40 @@ impl Tokens {
                         &mut fn_inputs,
                     )?;
                 } else {
+                    #[cfg(all(feature = "vector", feature = "matrix"))]
+                    if commas != 0 && operator == Operator::Solve {
+                        tokens.push(Function::Sub.into());
+                        equations += 1;
+                        continue;
+                    }
                     tokens.push_operator(operator, &mut inner_vars, &operator_stack, funs)?;
                 }
             }
-            if !inner_vars.is_empty() && matches!(inputs, None | Some(NewCustom::Var(_))) {
-                return Err(ParseError::InnerVarError);
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            if commas != 0 {
+                if let Some(comma) = bad_comma {
+                    span = comma;
+                    Err(ParseError::SystemError)?;
+                }
+                if equations != commas + 1 || inner_vars.len() != equations {
+                    Err(ParseError::SystemError)?;
+                }
+                let fun = Function::System(
+                    u8::try_from(equations)
+                        .ok()
+                        .and_then(NonZeroU8::new)
+                        .ok_or(ParseError::ExtraInput)?,
+                );
+                tokens.compact_args(fun, &mut inner_vars, funs);
+                tokens.push(fun.into());
+            }
+            if diff
+                && inputs.is_none()
+                && (!inner_vars.is_empty() || has_graph_vars.contains(&true))
+            {
+                expr = true;
+            } else if inner_vars.len() > fun_inputs {
+                Err(ParseError::InnerVarError)?;
             }
         };
         if let Err(e) = inner {
@@ -1020,17 +1255,25 @@ impl Tokens {
                     funs[v as usize].name = n
                 }
             }
-            return Err(e);
+            return Err(ParseErrorSpan { error: e, span });
         }
-        Ok(tokens.end(
-            inputs,
-            simplify,
-            vars,
-            funs,
-            has_graph_vars,
-            #[cfg(feature = "float_rand")]
-            rand,
-        ))
+        if expr {
+            return Ok(ParseReturn::Expr(tokens));
+        }
+        tokens
+            .end(
+                inputs,
+                simplify,
+                vars,
+                funs,
+                has_graph_vars,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )
+            .map_err(|error| ParseErrorSpan {
+                error: ParseError::Eval(error),
+                span: 0..value.len(),
+            })
     }
     pub fn last_mul(
         &mut self,
@@ -1053,29 +1296,26 @@ impl Tokens {
         custom_funs: &[FunctionVar],
     ) -> Result<(), ParseError<'static>> {
         if operator == Operator::Solve {
-            let count = operator_stack.iter().map(|a| a.inner_vars() as usize).sum();
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            if let Some(Operator::Function(Function::System(_), _)) = operator_stack
+                .iter()
+                .rfind(|a| matches!(a, Operator::Function(_, _) | Operator::Custom(_, _)))
+            {
+                self.push(Function::Sub.into());
+                return Ok(());
+            }
+            let count = operator_stack
+                .iter()
+                .map(|a| a.inner_vars() as usize)
+                .sum::<usize>();
             if inner_vars.len() == count {
                 return Err(ParseError::InnerVarError);
             }
-            if inner_vars.len() > count + 1 {
+            if inner_vars.len() > count + 1 || operator_stack.contains(&Operator::Solve) {
                 #[cfg(not(all(feature = "vector", feature = "matrix")))]
-                {
-                    return Err(ParseError::VecMatNotEnabled);
-                }
+                return Err(ParseError::VecMatNotEnabled);
                 #[cfg(all(feature = "vector", feature = "matrix"))]
-                {
-                    todo!()
-                }
-            }
-            if operator_stack.contains(&Operator::Solve) {
-                #[cfg(not(all(feature = "vector", feature = "matrix")))]
-                {
-                    return Err(ParseError::VecMatNotEnabled);
-                }
-                #[cfg(all(feature = "vector", feature = "matrix"))]
-                {
-                    todo!()
-                }
+                return Err(ParseError::SystemError);
             }
             self.push(Function::Sub.into());
             self.compact_args(Function::Solve, inner_vars, custom_funs);
@@ -1126,7 +1366,8 @@ impl Tokens {
                         match normal.cmp(&inputs) {
                             Ordering::Greater => return Err(ParseError::MissingInput),
                             Ordering::Less
-                                if d.is_derivative() || inputs.get() != normal.get() * 2 =>
+                                if d.is_derivative()
+                                    || inputs.get() as usize != normal.get() as usize * 2 =>
                             {
                                 return Err(ParseError::ExtraInput);
                             }
@@ -1142,7 +1383,10 @@ impl Tokens {
                         }
                         let mut inputs = fn_inputs.pop().unwrap();
                         fun.set_inputs(inputs);
-                        if fun.inputs().get() + 1 < inputs.get() + fun.inner_vars() {
+                        if fun.inputs().get() as usize + 1
+                            < inputs.get() as usize + fun.inner_vars() as usize
+                            && inputs >= fun.inputs()
+                        {
                             let last = self[..].get_last(custom_funs);
                             let mut t = last;
                             for _ in fun.inputs().get()..inputs.get() {
@@ -1160,15 +1404,20 @@ impl Tokens {
                         match normal.cmp(&inputs) {
                             Ordering::Greater => return Err(ParseError::MissingInput),
                             Ordering::Less
-                                if d.is_derivative() || inputs.get() != normal.get() * 2 =>
+                                if d.is_derivative()
+                                    || inputs.get() as usize != normal.get() as usize * 2 =>
                             {
                                 return Err(ParseError::ExtraInput);
                             }
                             Ordering::Less => d.set_integral_twice_input(),
                             _ => {}
                         }
-                        self.compact_args(fun, inner_vars, custom_funs);
-                        self.push(Token::Function(fun, d));
+                        if fun == Function::Diff {
+                            self.push_diff(custom_funs)?;
+                        } else {
+                            self.compact_args(fun, inner_vars, custom_funs);
+                            self.push(Token::Function(fun, d));
+                        }
                         false
                     }
                     _ => {
@@ -1180,6 +1429,19 @@ impl Tokens {
             },
         )
     }
+    pub fn push_diff(&mut self, custom_funs: &[FunctionVar]) -> Result<(), ParseError<'static>> {
+        let var = self.pop().unwrap();
+        if !matches!(var, Token::InnerVar(_) | Token::GraphVar(_)) {
+            return Err(ParseError::InnerVarError);
+        }
+        let last = self[..].get_last(custom_funs);
+        let diff = self[last..]
+            .diff(&var, custom_funs)
+            .ok_or(ParseError::DiffError)?;
+        self.truncate(last);
+        self.extend(diff[..].simplify_algebraic(custom_funs).0);
+        Ok(())
+    }
     pub fn compact_args(
         &mut self,
         fun: Function,
@@ -1220,7 +1482,7 @@ impl Tokens {
         funs: &mut Functions,
         has_graph_vars: Vec<bool>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> ParseReturn {
+    ) -> Result<ParseReturn, EvalError> {
         if self.is_empty() {
             self.push(Number::default().into());
         }
@@ -1241,7 +1503,7 @@ impl Tokens {
                     vars,
                     #[cfg(feature = "float_rand")]
                     rand,
-                );
+                )?;
                 if let Some(v) = vars.position(name) {
                     vars[v as usize].value = val;
                     vars[v as usize].volatile = Volatility::GraphConstant;
@@ -1271,7 +1533,7 @@ impl Tokens {
                 v.volatile = volatile;
                 v.tokens = self;
             }
-            ParseReturn::Var
+            Ok(ParseReturn::Var)
         } else if has_graph_vars.iter().copied().any(|a| a) {
             if simplify {
                 self.simplify::<{ Volatility::Volatile }>(
@@ -1282,7 +1544,7 @@ impl Tokens {
                     rand,
                 );
             }
-            ParseReturn::Graph(self, has_graph_vars)
+            Ok(ParseReturn::Graph(self, has_graph_vars))
         } else {
             if simplify {
                 self.simplify::<{ Volatility::Volatile }>(
@@ -1293,7 +1555,7 @@ impl Tokens {
                     rand,
                 );
             }
-            ParseReturn::Tokens(self)
+            Ok(ParseReturn::Tokens(self))
         }
     }
 }
@@ -1510,8 +1772,74 @@ pub enum ParseError<'a> {
     TooManyDerivatives,
     RpnUnsupported,
     GraphVarError,
+    DiffError,
+    #[cfg(feature = "vector")]
+    SquareBracketFailed,
+    #[cfg(feature = "vector")]
+    RightSquareBracketFailed,
     #[cfg(not(all(feature = "vector", feature = "matrix")))]
     VecMatNotEnabled,
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    SystemError,
+    Eval(EvalError),
+}
+impl Display for ParseError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownToken(s) => write!(f, "unknown token '{s}'"),
+            Self::LeftParenthesisNotFound => write!(f, "missing '('"),
+            Self::RightParenthesisNotFound => write!(f, "missing ')'"),
+            Self::AbsoluteBracketFailed => write!(f, "unmatched '|'"),
+            #[cfg(feature = "vector")]
+            Self::SquareBracketFailed => write!(f, "unmatched '['"),
+            #[cfg(feature = "vector")]
+            Self::RightSquareBracketFailed => write!(f, "unmatched ']'"),
+            Self::MissingInput => write!(f, "missing input"),
+            Self::ExtraInput => write!(f, "too many inputs"),
+            Self::NeedsBracket => write!(f, "function inputs need to be in brackets"),
+            Self::InnerVarError => write!(f, "unknown or misplaced variable"),
+            Self::VarExpectedName => write!(f, "expected a name to define"),
+            Self::CommaError => write!(f, "comma outside of function inputs"),
+            Self::DerivativeError => write!(f, "' needs to follow a function"),
+            Self::IntegralError => write!(f, "` needs to follow a function"),
+            Self::MixedError => write!(f, "can not mix ' and `"),
+            Self::TooManyDerivatives => write!(f, "too many ' or `"),
+            Self::RpnUnsupported => write!(f, "'=' is not supported in rpn"),
+            Self::GraphVarError => write!(f, "graph variables can not be used here"),
+            Self::DiffError => write!(f, "can not differentiate this expression"),
+            #[cfg(not(all(feature = "vector", feature = "matrix")))]
+            Self::VecMatNotEnabled => write!(f, "vector and matrix support is not enabled"),
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            Self::SystemError => write!(f, "a system needs one equation per unknown"),
+            Self::Eval(e) => write!(f, "{e}"),
+        }
+    }
+}
+#[derive(Debug, PartialEq)]
+pub struct ParseErrorSpan<'a> {
+    pub error: ParseError<'a>,
+    pub span: Range<usize>,
+}
+impl ParseErrorSpan<'_> {
+    pub fn render(&self, value: &str) -> String {
+        let start = value[..self.span.start].chars().count();
+        let len = value[self.span.clone()].chars().count().max(1);
+        format!(
+            "{value}\n{}{}\n{}",
+            " ".repeat(start),
+            "^".repeat(len),
+            self.error
+        )
+    }
+}
+impl Display for ParseErrorSpan<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.error, self.span.start, self.span.end
+        )
+    }
 }
 impl Display for Tokens {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -1534,6 +1862,10 @@ impl Display for TokensSlice {
 impl Token {
     fn greater_precedence(&self, o: Operator) -> bool {
         match self {
+            #[cfg(feature = "rational")]
+            Token::Number(n) if n.to_string().contains('/') => {
+                Operator::Div.precedence() > o.precedence()
+            }
             Token::Number(_) => true,
             Token::InnerVar(_) => true,
             Token::GraphVar(_) => true,
@@ -1568,6 +1900,7 @@ impl TokensSlice {
                 let mut first = true;
                 write!(fmt, "{}", custom_funs[i as usize].name.as_ref().unwrap())?;
                 write_commas(fmt, d)?;
+                write!(fmt, "(")?;
                 for arg in lasts {
                     let arg = arg.get_infix(custom_vars, custom_funs, graph_vars);
                     if first {
@@ -1614,9 +1947,37 @@ impl TokensSlice {
                     }
                 } else {
                     let lasts = self.get_lasts(custom_funs);
+                    #[cfg(feature = "vector")]
+                    if matches!(f, Function::Vector(_) | Function::Index(_)) {
+                        let mut lasts = lasts.into_iter();
+                        if matches!(f, Function::Index(_)) {
+                            let arg = lasts.next().unwrap();
+                            let bracket = matches!(
+                                arg.last(),
+                                Some(&Token::Function(f, _)) if Operator::try_from(f).is_ok()
+                            );
+                            let arg = arg.get_infix(custom_vars, custom_funs, graph_vars);
+                            if bracket {
+                                write!(fmt, "({arg})")?;
+                            } else {
+                                write!(fmt, "{arg}")?;
+                            }
+                        }
+                        write!(fmt, "[")?;
+                        for (i, arg) in lasts.enumerate() {
+                            let arg = arg.get_infix(custom_vars, custom_funs, graph_vars);
+                            if i == 0 {
+                                write!(fmt, "{arg}")?;
+                            } else {
+                                write!(fmt, ",{arg}")?;
+                            }
+                        }
+                        return write!(fmt, "]");
+                    }
                     let mut first = true;
-                    write!(fmt, "{f}(")?;
+                    write!(fmt, "{f}")?;
                     write_commas(fmt, d)?;
+                    write!(fmt, "(")?;
                     for arg in lasts {
                         let arg = arg.get_infix(custom_vars, custom_funs, graph_vars);
                         if first {
@@ -1687,6 +2048,17 @@ impl From<Number> for Token {
         Self::Number(value)
     }
 }
+#[cfg(any(
+    feature = "list",
+    feature = "vector",
+    feature = "matrix",
+    feature = "units"
+))]
+impl From<NumberBase> for Token {
+    fn from(value: NumberBase) -> Self {
+        Self::Number(value.into())
+    }
+}
 impl From<Operator> for Token {
     fn from(value: Operator) -> Self {
         Self::Function(value.into(), Derivative::default())
@@ -1,11 +1,15 @@
-use crate::compute::{Compute, StackToken};
+use crate::compute::{Compute, EvalError, StackToken};
+#[cfg(feature = "list")]
+use crate::inverse::Bounds;
 use crate::inverse::Inverse;
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
 use crate::{Function, Number, Token};
 use std::mem;
 #[cfg(feature = "complex")]
-use ucalc_numbers::ComplexFunctions;
+use ucalc_numbers::ComplexTrait;
+#[cfg(feature = "complex")]
+use ucalc_numbers::{ComplexFunctions, Constant, RealTrait};
 use ucalc_numbers::{Float, FloatFunctions, FloatTrait, NegAssign, Pow};
 #[derive(Debug, PartialEq, Clone, Default)]
 #[repr(transparent)]
@@ -137,10 +141,75 @@ impl PolyRef<'_> {
                 4 => Some(self.cubic().into()),
                 #[cfg(feature = "complex")]
                 5 => Some(self.quartic().into()),
+                #[cfg(feature = "complex")]
+                6.. => Some(self.aberth()),
                 _ => None,
             }
         }
     }
+    #[cfg(feature = "complex")]
+    fn eval(self, x: &Number) -> (Number, Number) {
+        let mut p = self[self.len() - 1].clone();
+        let mut d = Number::default();
+        for a in self[..self.len() - 1].iter().rev() {
+            d = d * x + &p;
+            p = p * x + a;
+        }
+        (p, d)
+    }
+    #[cfg(feature = "complex")]
+    pub fn aberth(self) -> Vec<Number> {
+        let n = self.len() - 1;
+        let lead = self[n].clone().abs();
+        let radius = (0..n)
+            .map(|i| (self[i].clone().abs() / lead.clone()).pow(Float::from(n - i).recip()))
+            .fold(Float::from(0), |a, b| if b > a { b } else { a });
+        let mut roots = (0..n)
+            .map(|k| {
+                let t = Float::from(2 * k) * Float::from(Constant::Pi) / Float::from(n)
+                    + Float::from(0.4);
+                Number::from((t.clone().cos() * &radius, t.sin() * &radius))
+            })
+            .collect::<Vec<_>>();
+        let tol = Float::from(2.0f64.powi(-48));
+        for _ in 0..256 {
+            let mut done = true;
+            for k in 0..n {
+                let (p, d) = self.eval(&roots[k]);
+                if p.is_zero() {
+                    continue;
+                }
+                let ratio = p / d;
+                let sum = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != k)
+                    .fold(Number::default(), |sum, (_, z)| {
+                        sum + (roots[k].clone() - z).recip()
+                    });
+                let offset = ratio.clone() / (Number::from(1) - ratio * sum);
+                if offset.clone().abs() > tol.clone() * (roots[k].clone().abs() + Float::from(1)) {
+                    done = false;
+                }
+                roots[k] -= offset;
+            }
+            if done {
+                break;
+            }
+        }
+        for root in roots.iter_mut() {
+            for _ in 0..4 {
+                let (p, d) = self.eval(root);
+                let next = root.clone() - p.clone() / d;
+                let q = self.eval(&next).0.abs();
+                if q.is_nan() || q >= p.abs() {
+                    break;
+                }
+                *root = next;
+            }
+        }
+        roots
+    }
     pub fn linear(self) -> Number {
         -self[0].clone() / &self[1]
     }
@@ -277,26 +346,67 @@ fn depressed_cubic(p: Number, q: Number) -> [Number; 3] {
         omega_conj * u + omega * v,
     ]
 }
+#[cfg(feature = "list")]
+pub(crate) fn root_list(roots: impl IntoIterator<Item = Number>) -> Number {
+    let mut ret: Vec<Number> = Vec::new();
+    for root in roots {
+        let tol = Float::from(2.0f64.powi(-16)) * (root.clone().abs() + Float::from(1));
+        if !ret.iter().any(|a| (a.clone() - &root).abs() <= tol) {
+            ret.push(root)
+        }
+    }
+    sort_roots(&mut ret);
+    Number::List(ret)
+}
+fn sort_roots(ret: &mut [Number]) {
+    #[cfg(feature = "complex")]
+    ret.iter_mut().for_each(|a| {
+        if a.imag().clone().abs()
+            <= Float::from(2.0f64.powi(-40)) * (a.clone().abs() + Float::from(1))
+        {
+            a.zero_imag()
+        }
+    });
+    #[cfg(feature = "complex")]
+    ret.sort_by(|a, b| {
+        b.imag()
+            .is_zero()
+            .cmp(&a.imag().is_zero())
+            .then(a.total_cmp(b))
+    });
+    #[cfg(not(feature = "complex"))]
+    ret.sort_by(|a, b| a.total_cmp(b));
+}
 impl Polynomial {
-    pub fn roots(self) -> Option<Number> {
+    fn base_roots(&self) -> Option<Vec<Number>> {
         let mut poly = Poly(Vec::with_capacity(8));
-        let mut ret = if self.quotient.len() >= self.divisor.len()
-            && self.divisor.len() > 1
-            && self.quotient.clone().div_buffer(&self.divisor, &mut poly)
-        {
-            poly.as_ref().roots()?
-        } else {
-            let mut roots = self.quotient.as_ref().roots()?;
-            if self.divisor.len() > 1 {
-                let anti_roots = self.divisor.as_ref().roots()?;
-                for r in anti_roots {
-                    if let Some(i) = roots.iter().position(|a| *a == r) {
-                        roots.remove(i);
+        Some(
+            if self.quotient.len() >= self.divisor.len()
+                && self.divisor.len() > 1
+                && self.quotient.clone().div_buffer(&self.divisor, &mut poly)
+            {
+                poly.as_ref().roots()?
+            } else {
+                let mut roots = self.quotient.as_ref().roots()?;
+                if self.divisor.len() > 1 {
+                    let anti_roots = self.divisor.as_ref().roots()?;
+                    for r in anti_roots {
+                        if let Some(i) = roots.iter().position(|a| *a == r) {
+                            roots.remove(i);
+                        }
                     }
                 }
-            }
-            roots
-        };
+                roots
+            },
+        )
+    }
+    #[cfg(feature = "list")]
+    pub fn roots(self) -> Option<Number> {
+        self.root_vec(None).ok()?.map(root_list)
+    }
+    #[cfg(not(feature = "list"))]
+    pub fn roots(self) -> Option<Number> {
+        let mut ret = self.base_roots()?;
         ret.iter_mut().for_each(|a| {
             self.functions.iter().rev().for_each(|f| match f {
                 &Func::Function(f) => Inverse::try_from(f)
@@ -307,8 +417,44 @@ impl Polynomial {
                 Func::Power(p) => Inverse::pow_assign(a, p.clone().recip()),
             })
         });
-        ret.sort_by(|a, b| a.total_cmp(b));
-        Some(ret[0].clone())
+        sort_roots(&mut ret);
+        ret.into_iter().next()
+    }
+    #[cfg(feature = "list")]
+    pub(crate) fn root_vec(
+        &self,
+        samples: Option<&[Number]>,
+    ) -> Result<Option<Vec<Number>>, EvalError> {
+        let Some(mut ret) = self.base_roots() else {
+            return Ok(None);
+        };
+        for (i, f) in self.functions.iter().enumerate().rev() {
+            let bounds = samples.map(|samples| {
+                Bounds::new(samples.iter().map(|x| {
+                    let mut x = x.clone();
+                    self.functions[..i].iter().for_each(|f| match f {
+                        &Func::Function(f) => f.compute_on_1(&mut x),
+                        Func::Power(p) => Inverse::pow_assign(&mut x, p.clone()),
+                    });
+                    x
+                }))
+                .widen()
+            });
+            let mut next = Vec::with_capacity(ret.len());
+            for mut a in ret {
+                match f {
+                    &Func::Function(f) => {
+                        next.extend(Inverse::try_from(f).unwrap().branches(a, bounds.as_ref())?)
+                    }
+                    Func::Power(p) => {
+                        Inverse::pow_assign(&mut a, p.clone().recip());
+                        next.push(a)
+                    }
+                }
+            }
+            ret = next;
+        }
+        Ok(Some(ret))
     }
     pub fn new() -> Self {
         let mut quotient = Vec::with_capacity(8);
@@ -335,7 +481,7 @@ impl Polynomial {
     pub fn is_constant(&self) -> bool {
         self.quotient.len() <= 1 && self.divisor.len() <= 1
     }
-    fn mul_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
+    pub(crate) fn mul_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
         if self.functions != rhs.functions && !self.is_constant() && !rhs.is_constant() {
             return None;
         }
@@ -350,7 +496,7 @@ impl Polynomial {
             },
         })
     }
-    fn div_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
+    pub(crate) fn div_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
         if self.functions != rhs.functions && !self.is_constant() && !rhs.is_constant() {
             return None;
         }
@@ -365,7 +511,7 @@ impl Polynomial {
             },
         })
     }
-    fn add_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
+    pub(crate) fn add_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
         if self.functions != rhs.functions && !self.is_constant() && !rhs.is_constant() {
             return None;
         }
@@ -381,7 +527,7 @@ impl Polynomial {
             },
         })
     }
-    fn sub_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
+    pub(crate) fn sub_buffer(self, rhs: &Self, buffer: &mut Poly) -> Option<Self> {
         if self.functions != rhs.functions && !self.is_constant() && !rhs.is_constant() {
             return None;
         }
@@ -405,16 +551,33 @@ impl Compute<'_> {
         stack: &mut Vec<StackToken>,
         to_poly: Option<u16>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Option<StackToken> {
+    ) -> Result<Option<StackToken>, EvalError> {
+        self.compute_polynomial_with(
+            inner_vars,
+            stack,
+            to_poly,
+            &[],
+            #[cfg(feature = "float_rand")]
+            rand,
+        )
+    }
+    fn compute_polynomial_with(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        to_poly: Option<u16>,
+        args: &[StackToken],
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Option<StackToken>, EvalError> {
         let stack_end = stack.len();
-        let res = try {
+        let res: Result<_, Option<EvalError>> = try {
             let mut poly = Vec::with_capacity(8).into();
             let mut tokens = self.tokens.iter().enumerate();
             while let Some((i, token)) = tokens.next() {
                 match token {
                     &Token::Function(fun, d) => {
                         if d.get() != 0 {
-                            todo!()
+                            Err(None)?
                         }
                         let inputs = fun.inputs().get() as usize;
                         let len = stack.len();
@@ -423,7 +586,8 @@ impl Compute<'_> {
                             &mut poly,
                             #[cfg(feature = "float_rand")]
                             rand,
-                        )?;
+                        )
+                        .ok_or(None)?;
                         stack.truncate(len + 1 - inputs);
                     }
                     &Token::CustomVar(index) => {
@@ -431,29 +595,41 @@ impl Compute<'_> {
                     }
                     &Token::CustomFun(index, d) => {
                         if d.get() != 0 {
-                            todo!()
+                            Err(None)?
                         }
                         let inputs = self.custom_funs[index as usize].inputs.get() as usize;
                         let end = inner_vars.len();
-                        let len = stack.len();
-                        inner_vars.push(stack[len - inputs].num_ref().clone()); //TODO not always num
-                        inner_vars.extend(stack.drain(len + 1 - inputs..).map(|n| n.num()));
-                        stack[len - inputs] = self
+                        let args = stack.split_off(stack.len() - inputs);
+                        inner_vars.extend(args.iter().map(|a| match a {
+                            StackToken::Number(n) => n.clone(),
+                            _ => Number::default(),
+                        }));
+                        let ret = self
                             .offset(end)
                             .tokens(&self.custom_funs[index as usize].tokens[..])
-                            .compute_polynomial(
+                            .compute_polynomial_with(
                                 inner_vars,
                                 stack,
                                 None,
+                                &args,
                                 #[cfg(feature = "float_rand")]
                                 rand,
-                            )?;
+                            )
+                            .map_err(Some)?
+                            .ok_or(None)?;
+                        stack.push(ret);
                         inner_vars.truncate(end);
                     }
                     Token::Number(n) => stack.push(n.clone().into()),
                     &Token::InnerVar(index) => {
                         if Some(index) == to_poly {
                             stack.push(Polynomial::new().into())
+                        } else if let Some(a) = args.get(index as usize) {
+                            stack.push(match a {
+                                StackToken::Number(n) => n.clone().into(),
+                                StackToken::Polynomial(p) => p.clone().into(),
+                                _ => Err(None)?,
+                            })
                         } else {
                             stack.push(inner_vars[self.offset + index as usize].clone().into())
                         }
@@ -469,12 +645,10 @@ impl Compute<'_> {
             }
             stack.pop().unwrap()
         };
-        if let Some(res) = res {
-            Some(res)
-        } else {
+        res.map(Some).or_else(|e| {
             stack.truncate(stack_end);
-            None
-        }
+            e.map_or(Ok(None), Err)
+        })
     }
 }
 impl Function {
@@ -503,10 +677,10 @@ impl Function {
         if let StackToken::Polynomial(a) = a {
             if b.len() == 1 {
                 if let StackToken::Number(n) = &b[0] {
-                    self.poly_num(a, n.clone());
+                    self.poly_num(a, n.clone())?;
                 } else {
                     let b = b[0].poly_ref();
-                    self.poly(a, b, buffer);
+                    self.poly(a, b, buffer)?;
                 }
             } else {
                 match self {
@@ -1,73 +1,116 @@
 #[cfg(feature = "float_rand")]
 use crate::Rand;
-use crate::{Functions, Token, Tokens, Variables, Volatility};
+use crate::derivative::{add, div, is_num, mul, num, pow, sub};
+use crate::parse::TokensSlice;
+use crate::polynomial::{Poly, Polynomial};
+use crate::{
+    Compute, Function, FunctionVar, Functions, Number, Token, Tokens, Variables, Volatility,
+};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use std::ops::IndexMut;
+#[cfg(feature = "complex")]
+use ucalc_numbers::ComplexTrait;
+use ucalc_numbers::{Constant, FloatFunctions, FloatTrait, NegAssign, Pow, RealTrait};
 impl Tokens {
     pub fn simplify<const V: Volatility>(
         &mut self,
         vars: &mut Variables,
         funs: &mut Functions,
-        _inputs: u8,
+        inputs: u8,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
     ) {
-        let mut skips = Vec::with_capacity(self.len());
+        let mut skips: Vec<(usize, u8)> = Vec::with_capacity(self.len());
         let mut i = 0;
         while i < self.len() {
+            while let Some(&(j, _)) = skips.last()
+                && j + self[j].skip() < i
+            {
+                skips.pop();
+            }
+            let mut removed = 0;
             match self[i] {
-                Token::Function(fun, d) => {
-                    let inputs = fun.inputs().get() as usize;
-                    if fun.volatility() < V
-                        && self[i - inputs..i]
-                            .iter()
-                            .all(|n| matches!(n, Token::Number(_)))
-                        && d.get() == 0
-                    {
-                        if fun.has_inner_fn() {
-                            //TODO
-                            let _ = 0;
-                            skips.pop();
-                        } else if fun.is_chainable() {
-                            //TODO
-                        } else {
-                            let n = fun
-                                .compute_drain(
-                                    self.drain(i - inputs..i).map(|t| t.num()),
+                Token::Function(fun, d) if fun.volatility() < V && d.get() == 0 => {
+                    let len = fun.inputs().get() as usize;
+                    if fun.has_inner_fn() {
+                        let mut args = self[..=i].get_lasts(funs);
+                        let mut start = i - args.iter().map(|a| a.len()).sum::<usize>();
+                        if let Token::Skip(to) = self[start]
+                            && start + to >= i
+                        {
+                            start += 1;
+                            args[0] = &args[0][1..];
+                        }
+                        let depth =
+                            inputs as usize + skips.iter().map(|(_, n)| *n as usize).sum::<usize>();
+                        if fun == Function::If
+                            && let [Token::Number(n)] = &args[0].0
+                        {
+                            let branch = args[if n.is_zero() { 2 } else { 1 }][1..].to_vec();
+                            removed = i + 1 - start - branch.len();
+                            self.splice(start..=i, branch);
+                            i -= removed;
+                        } else if self[start..i].is_constant::<V>(depth, funs)
+                            && let Ok(n) = Compute::new(&self[start..=i], &[], funs, vars, 0)
+                                .compute(
+                                    &mut vec![Number::default(); depth],
+                                    &mut Vec::new(),
                                     #[cfg(feature = "float_rand")]
                                     rand,
                                 )
-                                .into();
-                            i -= inputs;
-                            for i in skips.iter().copied() {
-                                *<Tokens as IndexMut<usize>>::index_mut(self, i).skip_mut() -=
-                                    inputs;
-                            }
-                            self[i] = n;
+                        {
+                            removed = i - start;
+                            self.drain(start..i);
+                            i = start;
+                            self[i] = n.into();
                         }
+                    } else if self[i - len..i]
+                        .iter()
+                        .all(|n| matches!(n, Token::Number(_)))
+                        && let Ok(mut n) = fun.compute_drain(
+                            self[i - len..i].iter().map(|t| t.num_ref().clone()),
+                            #[cfg(feature = "float_rand")]
+                            rand,
+                        )
+                    {
+                        let chain = fun.is_chainable()
+                            && matches!(self.get(i + 1), Some(Token::Function(o, _)) if o.is_chainable());
+                        let first = self[i - len].num_ref().clone();
+                        self.drain(i - len..i);
+                        if chain {
+                            n = if n.is_zero() {
+                                Number::from(Constant::Nan)
+                            } else {
+                                first
+                            };
+                        }
+                        removed = len;
+                        i -= len;
+                        self[i] = n.into();
                     }
                 }
                 Token::CustomFun(index, d) => {
                     let fun = &funs[index as usize];
-                    let inputs = fun.inputs.get();
+                    let len = fun.inputs.get() as usize;
                     if fun.volatile < V
                         && !fun.tokens.is_empty()
-                        && self[i - inputs as usize..i]
+                        && self[i - len..i]
                             .iter()
                             .all(|n| matches!(n, Token::Number(_)))
                         && d.get() == 0
-                    {
-                        let n = fun.tokens.compute_fun(
+                        && let Ok(n) = fun.tokens.compute_fun(
                             &[],
                             funs,
                             vars,
-                            self.drain(i - inputs as usize..i).map(|t| t.num()),
+                            self[i - len..i].iter().map(|t| t.num_ref().clone()),
                             #[cfg(feature = "float_rand")]
                             rand,
-                        );
-                        i -= inputs as usize;
-                        for i in skips.iter().copied() {
-                            *<Tokens as IndexMut<usize>>::index_mut(self, i).skip_mut() -=
-                                inputs as usize;
-                        }
+                        )
+                    {
+                        self.drain(i - len..i);
+                        removed = len;
+                        i -= len;
                         self[i] = n.into();
                     }
                 }
@@ -75,16 +118,524 @@ impl Tokens {
                     self[i] = vars[index as usize].value.clone().into()
                 }
                 Token::Skip(_) => {
-                    if let Some(n) = skips.last().copied()
-                        && n + self[n].skip() + 1 == i
-                    {
-                        skips.pop();
-                    }
-                    skips.push(i)
+                    let n = self[..]
+                        .skip_owner(i, funs)
+                        .map(|f| f.inner_vars())
+                        .unwrap_or(0);
+                    skips.push((i, n))
                 }
                 _ => {}
             }
+            for (j, _) in skips.iter().copied() {
+                *<Tokens as IndexMut<usize>>::index_mut(self, j).skip_mut() -= removed;
+            }
             i += 1;
         }
     }
 }
+impl TokensSlice {
+    fn is_constant<const V: Volatility>(&self, depth: usize, funs: &[FunctionVar]) -> bool {
+        self.iter().all(|t| match *t {
+            Token::Function(fun, _) => fun.volatility() < V && !matches!(fun, Function::While(_)),
+            Token::CustomFun(index, _) => funs[index as usize].volatile < V,
+            Token::CustomVar(_) | Token::GraphVar(_) => false,
+            Token::InnerVar(index) => index as usize >= depth,
+            _ => true,
+        })
+    }
+    fn skip_owner(&self, j: usize, funs: &[FunctionVar]) -> Option<Function> {
+        let mut above = 0;
+        let mut k = j + self[j].skip() + 1;
+        while k < self.len() {
+            let inputs = match self[k] {
+                Token::Function(fun, d) => {
+                    let n = fun.inputs().get() as usize;
+                    let n = if d.is_integral_twice_input() {
+                        2 * n
+                    } else {
+                        n
+                    };
+                    if n > above {
+                        return Some(fun);
+                    }
+                    n
+                }
+                Token::CustomFun(index, d) => {
+                    let n = funs[index as usize].inputs.get() as usize;
+                    let n = if d.is_integral_twice_input() {
+                        2 * n
+                    } else {
+                        n
+                    };
+                    if n > above {
+                        return None;
+                    }
+                    n
+                }
+                Token::Skip(to) => {
+                    k += to;
+                    0
+                }
+                _ => 0,
+            };
+            above = above + 1 - inputs;
+            k += 1;
+        }
+        None
+    }
+}
+type Monomial = Vec<(usize, u32)>;
+const MAX_DEGREE: usize = 64;
+const MAX_TERMS: usize = 1 << 10;
+#[derive(Clone, Default)]
+struct Sum(BTreeMap<Monomial, Number>);
+enum Term {
+    Sum(Sum),
+    Ratio(Polynomial, usize),
+}
+#[derive(Default)]
+struct Atoms(Vec<Vec<Token>>);
+impl Atoms {
+    fn get(&mut self, tokens: Vec<Token>) -> Term {
+        if let [Token::Number(n)] = &tokens[..] {
+            return Term::Sum(Sum::constant(n.clone()));
+        }
+        let i = self.0.iter().position(|t| *t == tokens).unwrap_or_else(|| {
+            self.0.push(tokens);
+            self.0.len() - 1
+        });
+        let mut sum = Sum::default();
+        sum.insert(vec![(i, 1)], Number::from(1));
+        Term::Sum(sum)
+    }
+}
+impl Sum {
+    fn constant(n: Number) -> Self {
+        let mut sum = Self::default();
+        sum.insert(Vec::new(), n);
+        sum
+    }
+    fn insert(&mut self, m: Monomial, c: Number) {
+        if c.is_zero() {
+            return;
+        }
+        match self.0.entry(m) {
+            Entry::Vacant(e) => {
+                e.insert(c);
+            }
+            Entry::Occupied(mut e) => {
+                *e.get_mut() += c;
+                if e.get().is_zero() {
+                    e.remove();
+                }
+            }
+        }
+    }
+    fn as_constant(&self) -> Option<Number> {
+        match self.0.len() {
+            0 => Some(Number::from(0)),
+            1 => self.0.get(&Vec::new()).cloned(),
+            _ => None,
+        }
+    }
+    fn degree(&self) -> usize {
+        self.0
+            .keys()
+            .map(|m| m.iter().map(|(_, e)| *e as usize).sum())
+            .max()
+            .unwrap_or(0)
+    }
+    fn add(mut self, rhs: Self) -> Self {
+        for (m, c) in rhs.0 {
+            self.insert(m, c);
+        }
+        self
+    }
+    fn neg(mut self) -> Self {
+        self.0.values_mut().for_each(|c| c.neg_assign());
+        self
+    }
+    fn mul(&self, rhs: &Self) -> Self {
+        let mut ret = Self::default();
+        for (a, c) in &self.0 {
+            for (b, d) in &rhs.0 {
+                let mut m = a.clone();
+                for &(i, e) in b {
+                    match m.binary_search_by_key(&i, |(j, _)| *j) {
+                        Ok(k) => m[k].1 += e,
+                        Err(k) => m.insert(k, (i, e)),
+                    }
+                }
+                ret.insert(m, c.clone() * d);
+            }
+        }
+        ret
+    }
+    fn pow(&self, n: usize) -> Option<Self> {
+        if self.degree() * n > MAX_DEGREE {
+            return None;
+        }
+        let mut ret = Self::constant(Number::from(1));
+        for _ in 0..n {
+            ret = ret.mul(self);
+            if ret.0.len() > MAX_TERMS {
+                return None;
+            }
+        }
+        Some(ret)
+    }
+    fn div_monomial(&self, rhs: &Self) -> Option<Self> {
+        let mut terms = rhs.0.iter();
+        let (b, d) = terms.next()?;
+        if terms.next().is_some() {
+            return None;
+        }
+        let mut ret = Self::default();
+        for (a, c) in &self.0 {
+            let mut m = a.clone();
+            for &(i, e) in b {
+                let k = m.iter().position(|(j, f)| *j == i && *f >= e)?;
+                m[k].1 -= e;
+                if m[k].1 == 0 {
+                    m.remove(k);
+                }
+            }
+            ret.insert(m, c.clone() / d);
+        }
+        Some(ret)
+    }
+    fn univariate(&self) -> Option<(Polynomial, Option<usize>)> {
+        let mut atom = None;
+        for m in self.0.keys() {
+            match m[..] {
+                [] => {}
+                [(i, _)] if atom.is_none_or(|a| a == i) => atom = Some(i),
+                _ => return None,
+            }
+        }
+        let degree = self.degree();
+        if degree > MAX_DEGREE {
+            return None;
+        }
+        let mut quotient = vec![Number::default(); degree + 1];
+        for (m, c) in &self.0 {
+            quotient[m.first().map_or(0, |(_, e)| *e as usize)] = c.clone();
+        }
+        Some((
+            Polynomial {
+                quotient: Poly(quotient),
+                divisor: Poly(vec![Number::from(1)]),
+                functions: Vec::new(),
+            },
+            atom,
+        ))
+    }
+    fn tokens(self, atoms: &Atoms) -> Vec<Token> {
+        let mut terms: Vec<_> = self.0.into_iter().collect();
+        terms.sort_by(|(a, _), (b, _)| order(a, b));
+        let mut ret = num(0);
+        for (m, c) in terms {
+            let negative = !is_num(&ret, 0) && is_negative(&c);
+            let mut term = vec![if negative { -c } else { c }.into()];
+            for (i, e) in m {
+                term = mul(term, pow(atoms.0[i].clone(), num(e as isize)));
+            }
+            ret = if negative {
+                sub(ret, term)
+            } else {
+                add(ret, term)
+            };
+        }
+        ret
+    }
+}
+fn order(a: &Monomial, b: &Monomial) -> Ordering {
+    let degree = |m: &Monomial| m.iter().map(|(_, e)| *e).sum::<u32>();
+    degree(b).cmp(&degree(a)).then_with(|| {
+        for (&(i, e), &(j, f)) in a.iter().zip(b) {
+            if i != j {
+                return i.cmp(&j);
+            }
+            if e != f {
+                return f.cmp(&e);
+            }
+        }
+        b.len().cmp(&a.len())
+    })
+}
+impl Term {
+    fn tokens(self, atoms: &Atoms) -> Vec<Token> {
+        match self {
+            Self::Sum(s) => s.tokens(atoms),
+            Self::Ratio(p, atom) => {
+                let var = &atoms.0[atom];
+                div(poly_tokens(&p.quotient, var), poly_tokens(&p.divisor, var))
+            }
+        }
+    }
+    fn sum(self, atoms: &mut Atoms) -> Sum {
+        match self {
+            Self::Sum(s) => s,
+            ratio => {
+                let tokens = ratio.tokens(atoms);
+                let Self::Sum(s) = atoms.get(tokens) else {
+                    unreachable!()
+                };
+                s
+            }
+        }
+    }
+    fn polynomial(&self) -> Option<(Polynomial, Option<usize>)> {
+        match self {
+            Self::Sum(s) => s.univariate(),
+            Self::Ratio(p, atom) => Some((p.clone(), Some(*atom))),
+        }
+    }
+    fn from_polynomial(p: Polynomial, atom: Option<usize>) -> Self {
+        let p = reduce(p);
+        match atom {
+            Some(atom) if p.divisor.len() > 1 => Self::Ratio(p, atom),
+            _ => {
+                let d = p.divisor[0].clone();
+                let mut sum = Sum::default();
+                for (e, c) in p.quotient.0.into_iter().enumerate() {
+                    let m = match atom {
+                        Some(atom) if e != 0 => vec![(atom, e as u32)],
+                        _ => Vec::new(),
+                    };
+                    sum.insert(m, c / &d);
+                }
+                Self::Sum(sum)
+            }
+        }
+    }
+}
+fn constant(p: &Polynomial) -> Option<Number> {
+    if p.quotient.len() <= 1 && p.divisor.len() == 1 {
+        Some(p.quotient.first().cloned().unwrap_or_default() / &p.divisor[0])
+    } else {
+        None
+    }
+}
+fn is_negative(n: &Number) -> bool {
+    n.real().is_sign_negative() && {
+        #[cfg(feature = "complex")]
+        {
+            n.imag().is_zero()
+        }
+        #[cfg(not(feature = "complex"))]
+        {
+            true
+        }
+    }
+}
+fn is_integer(n: &Number) -> bool {
+    n.real().clone().fract().is_zero() && {
+        #[cfg(feature = "complex")]
+        {
+            n.imag().is_zero()
+        }
+        #[cfg(not(feature = "complex"))]
+        {
+            true
+        }
+    }
+}
+fn reduce(mut p: Polynomial) -> Polynomial {
+    p.quotient.simplify();
+    p.divisor.simplify();
+    if p.quotient.is_zero() {
+        return Number::from(0).into();
+    }
+    let shift = p.quotient.as_ref().first().min(p.divisor.as_ref().first());
+    p.quotient.0.drain(..shift);
+    p.divisor.0.drain(..shift);
+    if p.divisor.len() > 1 {
+        let mut rem = p.quotient.clone();
+        let mut quotient = Poly::default();
+        if rem.div_buffer(&p.divisor, &mut quotient) {
+            p.quotient = quotient;
+            p.divisor = Poly(vec![Number::from(1)]);
+        }
+    }
+    if !p.divisor.is_zero() {
+        let lead = p.divisor.last().clone();
+        p.quotient /= lead.clone();
+        p.divisor /= lead;
+    }
+    p
+}
+fn poly_tokens(p: &Poly, var: &[Token]) -> Vec<Token> {
+    let mut ret = num(0);
+    for (i, c) in p.iter().enumerate().rev() {
+        if c.is_zero() {
+            continue;
+        }
+        let negative = !is_num(&ret, 0) && is_negative(c);
+        let c = vec![if negative { -c.clone() } else { c.clone() }.into()];
+        let term = if i == 0 {
+            c
+        } else {
+            mul(c, pow(var.to_vec(), num(i as isize)))
+        };
+        ret = if negative {
+            sub(ret, term)
+        } else {
+            add(ret, term)
+        };
+    }
+    ret
+}
+fn poly_op(fun: Function, a: &Polynomial, b: &Polynomial) -> Option<Polynomial> {
+    let mut buffer = Poly::default();
+    match fun {
+        Function::Add => a.clone().add_buffer(b, &mut buffer),
+        Function::Sub => a.clone().sub_buffer(b, &mut buffer),
+        Function::Mul if a.quotient.is_zero() || b.quotient.is_zero() => {
+            Some(Number::from(0).into())
+        }
+        Function::Mul => a.clone().mul_buffer(b, &mut buffer),
+        Function::Div if b.quotient.is_zero() => None,
+        Function::Div if a.quotient.is_zero() => Some(Number::from(0).into()),
+        Function::Div => a.clone().div_buffer(b, &mut buffer),
+        Function::Pow => {
+            let n = constant(b)?;
+            if let Some(a) = constant(a) {
+                return Some(a.pow(n).into());
+            }
+            if !is_integer(&n) {
+                return None;
+            }
+            let degree = a.quotient.len().max(a.divisor.len());
+            if degree * n.real().clone().into_isize().unsigned_abs() > MAX_DEGREE {
+                return None;
+            }
+            a.clone().pow(n)
+        }
+        _ => None,
+    }
+}
+fn sum_op(fun: Function, a: &Sum, b: &Sum) -> Option<Sum> {
+    match fun {
+        Function::Add => Some(a.clone().add(b.clone())),
+        Function::Sub => Some(a.clone().add(b.clone().neg())),
+        Function::Mul => Some(a.mul(b)),
+        Function::Div => match b.as_constant() {
+            Some(c) if c.is_zero() => None,
+            Some(c) => Some(a.mul(&Sum::constant(Number::from(1) / &c))),
+            None => a.div_monomial(b),
+        },
+        Function::Pow => {
+            let n = b.as_constant()?;
+            if let Some(a) = a.as_constant() {
+                return Some(Sum::constant(a.pow(n)));
+            }
+            if !is_integer(&n) || is_negative(&n) {
+                return None;
+            }
+            a.pow(n.real().clone().into_usize())
+        }
+        _ => None,
+    }
+}
+fn simplify_fun(fun: Function, mut args: Vec<Term>, atoms: &mut Atoms) -> Term {
+    match fun {
+        Function::Negate => {
+            return match args.pop().unwrap() {
+                Term::Sum(s) => Term::Sum(s.neg()),
+                Term::Ratio(mut p, atom) => {
+                    p.neg_mut();
+                    Term::Ratio(p, atom)
+                }
+            };
+        }
+        Function::Add | Function::Sub | Function::Mul | Function::Div | Function::Pow => {
+            let b = args.pop().unwrap();
+            let a = args.pop().unwrap();
+            if let (Term::Sum(x), Term::Sum(y)) = (&a, &b)
+                && let Some(s) = sum_op(fun, x, y)
+            {
+                return Term::Sum(s);
+            }
+            if let (Some((x, i)), Some((y, j))) = (a.polynomial(), b.polynomial())
+                && (i.is_none() || j.is_none() || i == j)
+                && let Some(p) = poly_op(fun, &x, &y)
+            {
+                return Term::from_polynomial(p, i.or(j));
+            }
+            if matches!(a, Term::Ratio(..)) || matches!(b, Term::Ratio(..)) {
+                let (x, y) = (a.sum(atoms), b.sum(atoms));
+                if let Some(s) = sum_op(fun, &x, &y) {
+                    return Term::Sum(s);
+                }
+                args = vec![Term::Sum(x), Term::Sum(y)];
+            } else {
+                args = vec![a, b];
+            }
+        }
+        _ => {}
+    }
+    let mut args = args.into_iter().map(|t| t.tokens(atoms));
+    let tokens = match fun {
+        Function::Add => add(args.next().unwrap(), args.next().unwrap()),
+        Function::Sub => sub(args.next().unwrap(), args.next().unwrap()),
+        Function::Mul => mul(args.next().unwrap(), args.next().unwrap()),
+        Function::Div => div(args.next().unwrap(), args.next().unwrap()),
+        Function::Pow => pow(args.next().unwrap(), args.next().unwrap()),
+        _ => {
+            let mut ret: Vec<Token> = args.flatten().collect();
+            ret.push(fun.into());
+            ret
+        }
+    };
+    atoms.get(tokens)
+}
+impl TokensSlice {
+    pub fn simplify_algebraic(&self, custom_funs: &[FunctionVar]) -> Tokens {
+        let mut atoms = Atoms::default();
+        let mut stack = Vec::with_capacity(self.len());
+        let mut tokens = self.iter().enumerate();
+        while let Some((i, token)) = tokens.next() {
+            match token {
+                Token::Number(n) => stack.push(Term::Sum(Sum::constant(n.clone()))),
+                &Token::Skip(to) => {
+                    stack.push(atoms.get(self[i..=i + to].to_vec()));
+                    tokens.advance_by(to).unwrap();
+                }
+                &Token::Function(fun, d) => {
+                    let n = fun.inputs().get() as usize;
+                    let n = if d.is_integral_twice_input() {
+                        2 * n
+                    } else {
+                        n
+                    };
+                    let args = stack.split_off(stack.len() - n);
+                    if d.get() == 0 {
+                        let term = simplify_fun(fun, args, &mut atoms);
+                        stack.push(term);
+                    } else {
+                        let mut ret: Vec<Token> =
+                            args.into_iter().flat_map(|t| t.tokens(&atoms)).collect();
+                        ret.push(token.clone());
+                        stack.push(atoms.get(ret));
+                    }
+                }
+                &Token::CustomFun(n, d) => {
+                    let inputs = custom_funs[n as usize].inputs.get() as usize;
+                    let inputs = if d.is_integral_twice_input() {
+                        2 * inputs
+                    } else {
+                        inputs
+                    };
+                    let args = stack.split_off(stack.len() - inputs);
+                    let mut ret: Vec<Token> =
+                        args.into_iter().flat_map(|t| t.tokens(&atoms)).collect();
+                    ret.push(token.clone());
+                    stack.push(atoms.get(ret));
+                }
+                t => stack.push(atoms.get(vec![t.clone()])),
+            }
+        }
+        Tokens(stack.pop().map(|t| t.tokens(&atoms)).unwrap_or_default())
+    }
+}
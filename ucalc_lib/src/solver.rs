@@ -1,32 +1,153 @@
-use crate::Number;
-use crate::compute::{Compute, StackToken};
+use crate::compute::{Compute, EvalError, StackToken};
+use crate::derivative::substitute;
 use crate::inverse::Inverse;
-use crate::parse::{Token, TokensSlice};
+#[cfg(feature = "list")]
+use crate::inverse::{Bounds, into_list, try_flat_map};
+use crate::parse::{Derivative, Token, Tokens, TokensSlice};
+#[cfg(feature = "list")]
+use crate::polynomial::root_list;
 #[cfg(feature = "float_rand")]
 use crate::rand::Rand;
+use crate::{FunctionVar, Number};
+#[cfg(feature = "list")]
+use std::mem;
+#[cfg(not(feature = "list"))]
 use std::ops::Deref;
+use ucalc_numbers::{FloatTrait, RealTrait};
 impl Compute<'_> {
     pub(crate) fn solve(
         &self,
         inner_vars: &mut Vec<Number>,
         stack: &mut Vec<StackToken>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Option<Number> {
+    ) -> Result<Option<Number>, EvalError> {
         let mut ret = Number::from(0);
-        Some(
-            if let Some(inner) = self.solve_inner(
+        let mut solved = self.solve_inner(
+            inner_vars,
+            &mut ret,
+            stack,
+            None,
+            #[cfg(feature = "list")]
+            None,
+            #[cfg(feature = "float_rand")]
+            rand,
+        )?;
+        if solved.is_none()
+            && let Some(tokens) = self.tokens.inline(self.custom_funs)
+            && tokens.0 != self.tokens.0
+        {
+            ret = Number::from(0);
+            solved = Compute::new(
+                &tokens[..],
+                self.graph_vars,
+                self.custom_funs,
+                self.custom_vars,
+                self.offset,
+            )
+            .solve_inner(
                 inner_vars,
                 &mut ret,
                 stack,
                 None,
+                #[cfg(feature = "list")]
+                None,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+        }
+        let Some(solved) = solved else {
+            inner_vars.push(Number::default());
+            let n = self.numerical_solve(
+                inner_vars,
+                stack,
+                Number::from(1),
+                (inner_vars.len() - 1) as u16,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            inner_vars.pop().unwrap();
+            #[cfg(feature = "list")]
+            return Ok((!n.real().is_nan()).then(|| Number::List(vec![n])));
+            #[cfg(not(feature = "list"))]
+            return Ok((!n.real().is_nan()).then_some(n));
+        };
+        #[cfg(feature = "list")]
+        return Ok(Some(root_list(into_list(solved.unwrap_or(ret)))));
+        #[cfg(not(feature = "list"))]
+        Ok(Some(solved.unwrap_or(ret)))
+    }
+    #[cfg(feature = "list")]
+    pub(crate) fn solve_range(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        start: Number,
+        end: Number,
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Number, EvalError> {
+        let bounds = Bounds::new([start.clone(), end.clone()]);
+        let samples = bounds.samples();
+        let inlined = self.tokens.inline(self.custom_funs);
+        let compute = if let Some(tokens) = &inlined {
+            Compute::new(
+                &tokens[..],
+                self.graph_vars,
+                self.custom_funs,
+                self.custom_vars,
+                self.offset,
+            )
+        } else {
+            self.clone()
+        };
+        let mut ret = Number::from(0);
+        let roots = if let Some(solved) = compute.solve_inner(
+            inner_vars,
+            &mut ret,
+            stack,
+            None,
+            Some(&samples),
+            #[cfg(feature = "float_rand")]
+            rand,
+        )? {
+            solved.unwrap_or(ret)
+        } else {
+            inner_vars.push(Number::default());
+            let roots = self.numerical_roots(
+                inner_vars,
+                stack,
+                start,
+                end,
+                (inner_vars.len() - 1) as u16,
+                #[cfg(feature = "float_rand")]
+                rand,
+            )?;
+            inner_vars.pop().unwrap();
+            roots
+        };
+        Ok(root_list(
+            into_list(roots).into_iter().filter(|a| bounds.contains(a)),
+        ))
+    }
+    #[cfg(feature = "list")]
+    fn bounds(
+        &self,
+        inner_vars: &mut Vec<Number>,
+        stack: &mut Vec<StackToken>,
+        samples: &[Number],
+        #[cfg(feature = "float_rand")] rand: &mut Rand,
+    ) -> Result<Bounds, EvalError> {
+        let mut points = Vec::with_capacity(samples.len());
+        for x in samples {
+            inner_vars.push(x.clone());
+            points.push(self.compute(
+                inner_vars,
+                stack,
                 #[cfg(feature = "float_rand")]
                 rand,
-            )? {
-                inner
-            } else {
-                ret
-            },
-        )
+            )?);
+            inner_vars.pop().unwrap();
+        }
+        Ok(Bounds::new(points).widen())
     }
     fn solve_inner(
         &self,
@@ -34,15 +155,16 @@ impl Compute<'_> {
         ret: &mut Number,
         stack: &mut Vec<StackToken>,
         args: Option<&mut Vec<&TokensSlice>>,
+        #[cfg(feature = "list")] samples: Option<&[Number]>,
         #[cfg(feature = "float_rand")] rand: &mut Rand,
-    ) -> Option<Option<Number>> {
+    ) -> Result<Option<Option<Number>>, EvalError> {
         let mut tokens = self.tokens.iter().enumerate();
         let mut start = 0;
         while let Some((i, token)) = tokens.next_back() {
             match *token {
                 Token::CustomFun(n, d) => {
                     if d.get() != 0 {
-                        todo!()
+                        return Ok(None);
                     }
                     let fun = &self.custom_funs[n as usize];
                     let tokens = &self.tokens[start..=i];
@@ -52,7 +174,7 @@ impl Compute<'_> {
                         .filter(|a| a.contains(&Token::InnerVar(inner_vars.len() as u16)))
                         .count();
                     if count != 1 {
-                        todo!() //polynomial
+                        return Ok(None);
                     }
                     let end = inner_vars.len();
                     for arg in args.iter().copied() {
@@ -64,18 +186,24 @@ impl Compute<'_> {
                                 stack,
                                 #[cfg(feature = "float_rand")]
                                 rand,
-                            );
+                            )?;
                             inner_vars.push(n)
                         }
                     }
-                    let roots = self.tokens(&fun.tokens[..]).offset(end).solve_inner(
+                    let Some(roots) = self.tokens(&fun.tokens[..]).offset(end).solve_inner(
                         inner_vars,
                         ret,
                         stack,
                         Some(&mut args),
+                        #[cfg(feature = "list")]
+                        None,
                         #[cfg(feature = "float_rand")]
                         rand,
-                    )?;
+                    )?
+                    else {
+                        inner_vars.truncate(end);
+                        return Ok(None);
+                    };
                     if let Some(n) = roots {
                         *ret = n;
                     }
@@ -85,19 +213,37 @@ impl Compute<'_> {
                         ret,
                         stack,
                         None,
+                        #[cfg(feature = "list")]
+                        samples,
                         #[cfg(feature = "float_rand")]
                         rand,
                     );
                 }
                 Token::Function(fun, d) => {
                     if d.get() != 0 {
-                        todo!()
+                        return Ok(None);
                     }
                     let Ok(inverse) = Inverse::try_from(fun) else {
-                        return None;
+                        return Ok(None);
                     };
-                    if let Some(inv) = inverse.get_inverse() {
-                        inv.compute_on_1(ret);
+                    if inverse.get_inverse().is_some() {
+                        #[cfg(feature = "list")]
+                        {
+                            let bounds = if let Some(samples) = samples {
+                                Some(self.tokens(&self.tokens[start..i]).bounds(
+                                    inner_vars,
+                                    stack,
+                                    samples,
+                                    #[cfg(feature = "float_rand")]
+                                    rand,
+                                )?)
+                            } else {
+                                None
+                            };
+                            try_flat_map(ret, |a| inverse.branches(a, bounds.as_ref()))?;
+                        }
+                        #[cfg(not(feature = "list"))]
+                        inverse.get_inverse().unwrap().compute_on_1(ret);
                     } else {
                         let right_tokens = &self.tokens[start..i];
                         let (right_tokens, last) = right_tokens.get_from_last(self.custom_funs);
@@ -127,7 +273,7 @@ impl Compute<'_> {
                                     left_tokens.contains(&Token::InnerVar(inner_vars.len() as u16))
                                 })
                             {
-                                let poly =
+                                let Some(poly) =
                                     self.tokens(&self.tokens[start..=i]).compute_polynomial(
                                         inner_vars,
                                         stack,
@@ -142,17 +288,31 @@ impl Compute<'_> {
                                         ),
                                         #[cfg(feature = "float_rand")]
                                         rand,
-                                    )?;
-                                let poly = poly.poly() - ret.deref();
-                                let roots = poly.roots();
-                                return Some(roots);
+                                    )?
+                                else {
+                                    return Ok(None);
+                                };
+                                let poly = poly.poly();
+                                #[cfg(feature = "list")]
+                                {
+                                    let mut roots = Vec::new();
+                                    for n in into_list(mem::take(ret)) {
+                                        let Some(r) = (poly.clone() - &n).root_vec(samples)? else {
+                                            return Ok(None);
+                                        };
+                                        roots.extend(r)
+                                    }
+                                    return Ok(Some(Some(Number::List(roots))));
+                                }
+                                #[cfg(not(feature = "list"))]
+                                return Ok((poly - ret.deref()).roots().map(Some));
                             } else {
                                 let num = self.tokens(left_tokens).compute(
                                     inner_vars,
                                     stack,
                                     #[cfg(feature = "float_rand")]
                                     rand,
-                                );
+                                )?;
                                 tokens.advance_by(last).unwrap();
                                 start += last;
                                 inverse.inverse_on_2::<1>(ret, num);
@@ -163,16 +323,70 @@ impl Compute<'_> {
                                 stack,
                                 #[cfg(feature = "float_rand")]
                                 rand,
-                            );
+                            )?;
                             tokens.advance_back_by(i - (start + last)).unwrap();
                             inverse.inverse_on_2::<0>(ret, num);
                         }
                     }
                 }
-                Token::InnerVar(_) => return Some(None),
-                _ => return None,
+                Token::InnerVar(_) => return Ok(Some(None)),
+                _ => return Ok(None),
             }
         }
-        Some(None)
+        Ok(Some(None))
+    }
+}
+impl TokensSlice {
+    pub(crate) fn inline(&self, custom_funs: &[FunctionVar]) -> Option<Tokens> {
+        let mut stack: Vec<Vec<Token>> = Vec::new();
+        for token in self.iter() {
+            match *token {
+                Token::Function(fun, d) => {
+                    let args = stack.split_off(stack.len() - fun.inputs().get() as usize);
+                    if d.get() == 0 {
+                        let mut g = args.concat();
+                        g.push(token.clone());
+                        stack.push(g);
+                    } else if args.len() == 1 {
+                        let body = [
+                            Token::InnerVar(0),
+                            Token::Function(fun, Derivative::default()),
+                        ];
+                        stack.push(substitute(&derivative(&body, d, custom_funs)?, &args));
+                    } else {
+                        return None;
+                    }
+                }
+                Token::CustomFun(n, d) => {
+                    let fun = &custom_funs[n as usize];
+                    let args = stack.split_off(stack.len() - fun.inputs.get() as usize);
+                    if d.get() != 0 && args.len() != 1 {
+                        return None;
+                    }
+                    let body = fun.tokens[..].inline(custom_funs)?;
+                    stack.push(substitute(&derivative(&body, d, custom_funs)?, &args));
+                }
+                Token::Skip(_) => return None,
+                _ => stack.push(vec![token.clone()]),
+            }
+        }
+        if stack.len() == 1 {
+            stack.pop().map(Tokens)
+        } else {
+            None
+        }
+    }
+}
+fn derivative(body: &[Token], d: Derivative, custom_funs: &[FunctionVar]) -> Option<Vec<Token>> {
+    if d.get() == 0 {
+        return Some(body.to_vec());
+    }
+    if d.is_integral() {
+        return None;
+    }
+    let mut body = Tokens(body.to_vec());
+    for _ in 0..d.get() {
+        body = body[..].diff(&Token::InnerVar(0), custom_funs)?;
     }
+    Some(body.0)
 }
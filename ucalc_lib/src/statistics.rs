@@ -0,0 +1,105 @@
+use crate::Number;
+use crate::lists::elements;
+use ucalc_numbers::{Constant, Float, FloatFunctions, FloatFunctionsMut, FloatTrait, RealTrait};
+fn sorted(n: Number) -> Vec<Number> {
+    let mut l = elements(n);
+    l.sort_by(|a, b| a.total_cmp(b));
+    l
+}
+fn nan() -> Number {
+    Number::from(Constant::Nan)
+}
+fn ratio(a: Number, b: Number) -> Number {
+    if b.is_zero() { nan() } else { a / b }
+}
+fn mean_of(l: &[Number]) -> Number {
+    l.iter().cloned().sum::<Number>() / Number::from(l.len())
+}
+fn covariance_of(x: &[Number], y: &[Number]) -> Number {
+    if x.len() != y.len() {
+        return nan();
+    }
+    let (mx, my) = (mean_of(x), mean_of(y));
+    x.iter()
+        .zip(y)
+        .map(|(a, b)| (a.clone() - &mx) * (b.clone() - &my))
+        .sum::<Number>()
+        / Number::from(x.len())
+}
+pub(crate) fn len(n: Number) -> Number {
+    Number::from(elements(n).len())
+}
+pub(crate) fn mean(n: Number) -> Number {
+    mean_of(&elements(n))
+}
+pub(crate) fn median(n: Number) -> Number {
+    let l = sorted(n);
+    match l.len() {
+        0 => nan(),
+        len if len % 2 == 1 => l[len / 2].clone(),
+        len => (l[len / 2 - 1].clone() + &l[len / 2]) / Float::from(2),
+    }
+}
+pub(crate) fn mode(n: Number) -> Number {
+    let l = sorted(n);
+    let mut best = (0, 0);
+    let mut start = 0;
+    for i in 1..=l.len() {
+        if i == l.len() || l[i] != l[start] {
+            if i - start > best.1 {
+                best = (start, i - start)
+            }
+            start = i;
+        }
+    }
+    l.get(best.0).cloned().unwrap_or_else(nan)
+}
+pub(crate) fn var(n: Number, sample: bool) -> Number {
+    let l = elements(n);
+    let m = mean_of(&l);
+    let len = l.len().saturating_sub(sample as usize);
+    l.into_iter()
+        .map(|a| {
+            let mut d = a - &m;
+            d.abs_mut();
+            d.clone() * d
+        })
+        .sum::<Number>()
+        / Number::from(len)
+}
+pub(crate) fn stddev(n: Number, sample: bool) -> Number {
+    var(n, sample).sqrt()
+}
+pub(crate) fn percentile(n: Number, p: Number) -> Number {
+    let l = sorted(n);
+    let p = p.to_real() / Float::from(100);
+    if l.is_empty() || p.is_nan() || p < Float::from(0) || p > Float::from(1) {
+        return nan();
+    }
+    let rank = p * Float::from(l.len() - 1);
+    let lo = rank.clone().floor().into_usize();
+    let t = rank.fract();
+    match l.get(lo + 1) {
+        Some(hi) => l[lo].clone() + (hi.clone() - &l[lo]) * t,
+        None => l[lo].clone(),
+    }
+}
+pub(crate) fn covariance(x: Number, y: Number) -> Number {
+    covariance_of(&elements(x), &elements(y))
+}
+pub(crate) fn correlation(x: Number, y: Number) -> Number {
+    let (x, y) = (elements(x), elements(y));
+    ratio(
+        covariance_of(&x, &y),
+        (covariance_of(&x, &x) * covariance_of(&y, &y)).sqrt(),
+    )
+}
+pub(crate) fn linreg(x: Number, y: Number) -> Number {
+    let (x, y) = (elements(x), elements(y));
+    let (vx, vy) = (covariance_of(&x, &x), covariance_of(&y, &y));
+    let cov = covariance_of(&x, &y);
+    let slope = ratio(cov.clone(), vx.clone());
+    let intercept = mean_of(&y) - slope.clone() * mean_of(&x);
+    let r2 = ratio(cov.clone() * cov, vx * vy);
+    Number::List(vec![slope, intercept, r2])
+}
@@ -1,15 +1,31 @@
+use crate::distributions::{Distribution, DistributionFunction};
 use crate::functions::{AtanInputs, Function, ModifyInputs};
-use crate::parse::{Derivative, ParseError};
-use crate::parse::{Token, Tokens};
+use crate::parse::{Derivative, ParseError, ParseErrorSpan};
+use crate::parse::{ParseReturn, Token, Tokens};
 use crate::polynomial::Poly;
+#[cfg(feature = "complex")]
+use crate::polynomial::PolyRef;
 #[cfg(feature = "float_rand")]
 use crate::rng;
 use crate::variable::{Functions, Variables};
-use crate::{FUNCTION_LIST, FunctionVar, Number, Variable, Volatility, get_help};
+use crate::{
+    Compute, EvalError, FUNCTION_LIST, FunctionVar, Number, Variable, Volatility, get_help,
+};
 use std::fmt::Debug;
 use std::num::NonZeroU8;
 use ucalc_numbers::*;
 fn assert_approx_eq(a: Number, b: Number) {
+    #[cfg(feature = "list")]
+    if let Number::List(a) = a {
+        let Number::List(b) = b else {
+            panic!("expected a list, found {b:?}")
+        };
+        assert!(!a.is_empty() && a.len() == b.len());
+        return a
+            .into_iter()
+            .zip(b)
+            .for_each(|(a, b)| assert_approx_eq(a, b));
+    }
     assert!((a - b).abs() < Float::from(2.0).pow(Float::from(-8)))
 }
 fn assert_approx_teq(a: Number, b: Number, c: Number) {
@@ -28,22 +44,26 @@ fn assert_approx_correct_with(
     c: Vec<Token>,
     d: Number,
 ) {
-    assert_teq(infix(a), rpn(b), Tokens(c));
+    assert_teq(infix(a, false), rpn(b, false), Tokens(c));
     assert_approx_teq(
-        infix(a).compute(
-            vf,
-            &f,
-            &v,
-            #[cfg(feature = "float_rand")]
-            &mut rng(),
-        ),
-        rpn(b).compute(
-            vf,
-            &f,
-            &v,
-            #[cfg(feature = "float_rand")]
-            &mut rng(),
-        ),
+        infix(a, true)
+            .compute(
+                vf,
+                &f,
+                &v,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap(),
+        rpn(b, true)
+            .compute(
+                vf,
+                &f,
+                &v,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap(),
         d,
     );
 }
@@ -51,6 +71,31 @@ fn assert_teq<N: PartialEq + Debug>(a: N, b: N, c: N) {
     assert_eq!(a, c, "a");
     assert_eq!(b, c, "b");
 }
+#[cfg(not(feature = "fastnum"))]
+fn assert_teq_num(a: Number, b: Number, c: Number) {
+    assert_teq(a, b, c)
+}
+#[cfg(feature = "fastnum")]
+fn assert_teq_num(a: Number, b: Number, c: Number) {
+    assert_eq!(a, b);
+    assert_close(a, c)
+}
+#[cfg(feature = "fastnum")]
+fn assert_close(a: Number, b: Number) {
+    if a == b {
+        return;
+    }
+    #[cfg(feature = "list")]
+    if let (Number::List(a), Number::List(b)) = (&a, &b) {
+        assert_eq!(a.len(), b.len());
+        return a
+            .iter()
+            .zip(b)
+            .for_each(|(a, b)| assert_close(a.clone(), b.clone()));
+    }
+    let tol = Float::from(10).pow(Float::from(-100)) * (b.clone().abs() + Float::from(1));
+    assert!((a.clone() - b.clone()).abs() < tol, "{a:?} {b:?}")
+}
 fn assert_correct(a: &str, b: &str, c: Vec<Token>, d: Number) {
     assert_correct_with(
         a,
@@ -113,21 +158,24 @@ fn assert_correct_with(
     )
     .unwrap()
     .tokens_any();
-    assert_teq(
-        infix.compute(
-            vf,
-            &f,
-            &v,
-            #[cfg(feature = "float_rand")]
-            &mut rng(),
-        ),
+    assert_teq_num(
+        infix
+            .compute(
+                vf,
+                &f,
+                &v,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap(),
         rpn.compute(
             vf,
             &f,
             &v,
             #[cfg(feature = "float_rand")]
             &mut rng(),
-        ),
+        )
+        .unwrap(),
         d,
     );
 }
@@ -166,14 +214,14 @@ fn assert_fun(infix: &str, rpn: &str, expected: &Functions) {
     );
     assert_teq(&f1, &f2, expected);
 }
-fn infix(s: &str) -> Tokens {
+fn infix(s: &str, simplify: bool) -> Tokens {
     Tokens::infix(
         s,
         &mut Variables::default(),
         &mut Functions::default(),
         &[],
         false,
-        true,
+        simplify,
         10,
         #[cfg(feature = "float_rand")]
         &mut rng(),
@@ -181,14 +229,14 @@ fn infix(s: &str) -> Tokens {
     .unwrap()
     .tokens()
 }
-fn rpn(s: &str) -> Tokens {
+fn rpn(s: &str, simplify: bool) -> Tokens {
     Tokens::rpn(
         s,
         &mut Variables::default(),
         &mut Functions::default(),
         &[],
         false,
-        true,
+        simplify,
         10,
         #[cfg(feature = "float_rand")]
         &mut rng(),
@@ -202,6 +250,14 @@ where
 {
     Number::from(f)
 }
+#[cfg(feature = "list")]
+fn roots(_: Number, all: Vec<Number>) -> Number {
+    Number::List(all)
+}
+#[cfg(not(feature = "list"))]
+fn roots(first: Number, _: Vec<Number>) -> Number {
+    first
+}
 fn num<T>(f: T) -> Token
 where
     Number: From<T>,
@@ -280,7 +336,8 @@ fn test_solve_poly() {
                 0,
                 #[cfg(feature = "float_rand")]
                 &mut rng(),
-            );
+            )
+            .unwrap();
             assert!(res.abs() < Float::from(2.0).pow(Float::from(-8)), "{s}");
         }
     }
@@ -469,6 +526,15 @@ fn test_inverses() {
         Function::Cbrt,
         Function::Cb,
     ] {
+        let n = match f {
+            #[cfg(feature = "complex")]
+            Function::Cosh => 2,
+            #[cfg(feature = "complex")]
+            Function::Cb => 3,
+            Function::Sq => 2,
+            _ => 1,
+        };
+        let half = roots(res(0.5), vec![res(0.5); n]);
         match f.inputs().get() {
             1 => {
                 assert_approx_correct(
@@ -483,7 +549,7 @@ fn test_inverses() {
                         Function::Solve.into(),
                         f.into(),
                     ],
-                    res(0.5),
+                    half.clone(),
                 );
             }
             2 => {
@@ -501,7 +567,7 @@ fn test_inverses() {
                         num(0.5),
                         f.into(),
                     ],
-                    res(0.5),
+                    half.clone(),
                 );
                 assert_approx_correct(
                     &format!("{f}(0.5,solve(x,{f}(0.5,x)-0.5))"),
@@ -517,7 +583,7 @@ fn test_inverses() {
                         Function::Solve.into(),
                         f.into(),
                     ],
-                    res(0.5),
+                    half.clone(),
                 );
             }
             _ => unreachable!(),
@@ -578,7 +644,22 @@ fn parse_min() {
     assert_correct(
         "min(1,2)",
         "1 2 min",
-        vec![num(1), num(2), Function::Min.into()],
+        vec![
+            num(1),
+            num(2),
+            Function::Min(NonZeroU8::new(2).unwrap()).into(),
+        ],
+        res(1),
+    );
+    assert_correct(
+        "min(3,1,2)",
+        "3 1 2 min3",
+        vec![
+            num(3),
+            num(1),
+            num(2),
+            Function::Min(NonZeroU8::new(3).unwrap()).into(),
+        ],
         res(1),
     );
 }
@@ -600,9 +681,24 @@ fn parse_max() {
     assert_correct(
         "max(1,2)",
         "1 2 max",
-        vec![num(1), num(2), Function::Max.into()],
+        vec![
+            num(1),
+            num(2),
+            Function::Max(NonZeroU8::new(2).unwrap()).into(),
+        ],
         res(2),
     );
+    assert_correct(
+        "max(3,1,2)",
+        "3 1 2 max3",
+        vec![
+            num(3),
+            num(1),
+            num(2),
+            Function::Max(NonZeroU8::new(3).unwrap()).into(),
+        ],
+        res(3),
+    );
 }
 #[test]
 fn parse_vars() {
@@ -663,7 +759,7 @@ fn test_polynomial() {
             Function::Div.into(),
             Function::Solve.into(),
         ],
-        res(2),
+        roots(res(2), vec![res(2)]),
     );
     assert_correct(
         "solve(x,(x-2)(x-3)/(x-3)/(x-3))",
@@ -687,7 +783,7 @@ fn test_polynomial() {
             Function::Div.into(),
             Function::Solve.into(),
         ],
-        res(2),
+        roots(res(2), vec![res(2)]),
     );
     assert_correct(
         "solve(x,x+x^2)",
@@ -701,7 +797,7 @@ fn test_polynomial() {
             Function::Add.into(),
             Function::Solve.into(),
         ],
-        res(-1),
+        roots(res(-1), vec![res(-1), res(0)]),
     );
     #[cfg(feature = "complex")]
     assert_correct(
@@ -722,7 +818,7 @@ fn test_polynomial() {
             Function::Mul.into(),
             Function::Solve.into(),
         ],
-        res(4),
+        roots(res(4), vec![res(4)]),
     );
     #[cfg(feature = "complex")]
     assert_correct(
@@ -747,7 +843,7 @@ fn test_polynomial() {
             Function::Mul.into(),
             Function::Solve.into(),
         ],
-        res(1),
+        roots(res(-2), vec![res(-2), res(-1), res(1), res(2)]),
     );
     #[cfg(feature = "complex")]
     assert_correct(
@@ -772,7 +868,7 @@ fn test_polynomial() {
             Function::Mul.into(),
             Function::Solve.into(),
         ],
-        res(-3),
+        roots(res(-3), vec![res(-3), res(-1), res(1), res(2)]),
     );
     #[cfg(feature = "complex")]
     assert_approx_correct(
@@ -793,7 +889,7 @@ fn test_polynomial() {
             Function::Mul.into(),
             Function::Solve.into(),
         ],
-        res(-3),
+        roots(res(-3), vec![res(-3), res(1), res(2)]),
     );
 }
 #[test]
@@ -920,19 +1016,21 @@ fn parse_gamma() {
 #[cfg(feature = "float_rand")]
 fn parse_rand_uniform() {
     assert_teq(
-        infix("rand_uniform(2,3)"),
-        rpn("2 3 rand_uniform"),
+        infix("rand_uniform(2,3)", true),
+        rpn("2 3 rand_uniform", true),
         Tokens(vec![num(2), num(3), Function::RandUniform.into()]),
     );
-    let n = infix("rand_uniform(2,3)").compute(
-        &[],
-        &[],
-        &[],
-        #[cfg(feature = "float_rand")]
-        &mut rng(),
-    );
+    let n = infix("rand_uniform(2,3)", true)
+        .compute(
+            &[],
+            &[],
+            &[],
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap();
     #[cfg(feature = "complex")]
-    assert!(n.imag.is_zero());
+    assert!(n.imag().is_zero());
     let n = n.real();
     assert!(n <= &Float::from(3));
     assert!(n >= &Float::from(2));
@@ -948,12 +1046,20 @@ fn parse_erf() {
 }
 #[test]
 fn parse_erfc() {
+    #[cfg(not(any(feature = "rug", feature = "fastnum")))]
     assert_correct(
         "erfc(100)",
         "100 erfc",
         vec![num(100), Function::Erfc.into()],
         res(0),
     );
+    #[cfg(any(feature = "rug", feature = "fastnum"))]
+    assert_correct(
+        "erfc(0)",
+        "0 erfc",
+        vec![num(0), Function::Erfc.into()],
+        res(1),
+    );
 }
 #[test]
 fn parse_abs() {
@@ -1080,7 +1186,8 @@ fn test_fuzz() {
                     &mut rng,
                 ) {
                     println!("{tokens:?}");
-                    std::hint::black_box(tokens.tokens_any().compute(&[], &funs, &vars, &mut rng));
+                    std::hint::black_box(tokens.tokens_any().compute(&[], &funs, &vars, &mut rng))
+                        .unwrap();
                 }
             }
         }
@@ -1163,6 +1270,7 @@ fn parse_arctan() {
 #[test]
 #[cfg(feature = "complex")]
 fn parse_cubic() {
+    #[cfg(not(feature = "list"))]
     assert_correct(
         "cubic(1,-2,0,1)",
         "1 2 ~ 0 1 cubic",
@@ -1176,6 +1284,24 @@ fn parse_cubic() {
         ],
         (res(1) + res(5).sqrt()) / res(2),
     );
+    #[cfg(feature = "list")]
+    assert_approx_correct(
+        "cubic(1,-2,0,1)",
+        "1 2 ~ 0 1 cubic",
+        vec![
+            num(1),
+            num(2),
+            Function::Negate.into(),
+            num(0),
+            num(1),
+            Function::Cubic.into(),
+        ],
+        Number::List(vec![
+            (res(1) - res(5).sqrt()) / res(2),
+            res(1),
+            (res(1) + res(5).sqrt()) / res(2),
+        ]),
+    );
 }
 #[test]
 #[cfg(feature = "complex")]
@@ -1192,7 +1318,7 @@ fn parse_quartic() {
             num(36),
             Function::Quartic.into(),
         ],
-        res(3),
+        roots(res(3), vec![res(-3), res(-2), res(2), res(3)]),
     );
 }
 #[test]
@@ -1208,7 +1334,13 @@ fn parse_quadratic() {
             Function::Negate.into(),
             Function::Quadratic.into(),
         ],
-        res(2).sqrt() + Float::from(1),
+        roots(
+            res(2).sqrt() + Float::from(1),
+            vec![
+                Float::from(1) - res(2).sqrt(),
+                res(2).sqrt() + Float::from(1),
+            ],
+        ),
     );
     assert_correct(
         "quadratic((4-2)/2,3-2-3,-ln(e))",
@@ -1229,7 +1361,46 @@ fn parse_quadratic() {
             Function::Negate.into(),
             Function::Quadratic.into(),
         ],
-        res(2).sqrt() + Float::from(1),
+        roots(
+            res(2).sqrt() + Float::from(1),
+            vec![
+                Float::from(1) - res(2).sqrt(),
+                res(2).sqrt() + Float::from(1),
+            ],
+        ),
+    );
+}
+#[cfg(all(feature = "complex", feature = "list"))]
+#[test]
+fn test_root_list() {
+    let half = res(3).sqrt() / res(2);
+    assert_eq!(
+        infix("quadratic(1,-2,1)", true)
+            .compute(
+                &[],
+                &Functions::default(),
+                &Variables::default(),
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap(),
+        Number::List(vec![res(1)])
+    );
+    assert_approx_eq(
+        infix("cubic(1,0,0,-1)", true)
+            .compute(
+                &[],
+                &Functions::default(),
+                &Variables::default(),
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap(),
+        Number::List(vec![
+            res(1),
+            res(-0.5) - half.clone().mul_i(false),
+            res(-0.5) + half.mul_i(false),
+        ]),
     );
 }
 #[test]
@@ -1266,7 +1437,7 @@ fn parse_order_of_operations() {
         vec![
             num(2),
             num(3),
-            Function::Max.into(),
+            Function::Max(NonZeroU8::new(2).unwrap()).into(),
             num(3),
             Function::Div.into(),
             var("pi"),
@@ -1327,7 +1498,7 @@ fn test_set() {
             Token::InnerVar(0),
             Function::Set.into(),
         ],
-        res(-1),
+        roots(res(-1), vec![res(-1)]),
     );
 }
 #[test]
@@ -1349,6 +1520,70 @@ fn test_numerical_differential() {
     );
 }
 #[test]
+fn test_runge_kutta() {
+    for (s, r) in [
+        ("numerical_differential(1,0,1,x,t,x)", "e"),
+        ("numerical_differential(1,0,10,x,t,-x)", "exp(-10)"),
+        ("numerical_differential(0,0,2,x,t,cos(t))", "sin(2)"),
+        ("numerical_differential(1,1,0,x,t,x)", "1/e"),
+        (
+            "numerical_differential(0,0,1,x,t,100(sin(t)-x))",
+            "100/10001*(100sin(1)-cos(1)+exp(-100))",
+        ),
+    ] {
+        let d = (eval(s) - eval(r)).abs();
+        assert!(d < Float::from(2.0).pow(Float::from(-24)), "{s} {d:?}");
+    }
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+#[test]
+fn test_ode_system() {
+    for s in [
+        "numerical_differential([1,0],0,pi,x,t,[x[1],-x[0]])",
+        "numerical_differential([1,0],0,pi,x,t,-x[0])",
+    ] {
+        let Number::Vector(d) = eval(s) - vector(&[-1, 0]) else {
+            unreachable!()
+        };
+        assert!(
+            d.iter()
+                .all(|a| a.clone().abs() < Float::from(2.0).pow(Float::from(-24))),
+            "{s}"
+        );
+    }
+}
+#[cfg(feature = "list")]
+#[test]
+fn test_trajectory() {
+    let n = Tokens::infix(
+        "numerical_trajectory(1,0,1,x,t,x)",
+        &mut Variables::default(),
+        &mut Functions::default(),
+        &[],
+        false,
+        true,
+        10,
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+    .unwrap()
+    .tokens()
+    .compute(
+        &[],
+        &[],
+        &Variables::default(),
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+    .unwrap();
+    let list = n.list_ref();
+    assert!(list.len() > 2);
+    assert_eq!(list[0], Number::List(vec![res(0), res(1)]));
+    let last = list.last().unwrap().list_ref();
+    assert_eq!(last[0], res(1));
+    assert_approx_eq(last[1].clone(), res(Constant::E));
+}
+#[test]
 fn test_numerical_integral() {
     assert_approx_correct(
         "numerical_integral(2,3,x,x^2-2)",
@@ -1368,6 +1603,53 @@ fn test_numerical_integral() {
     );
 }
 #[test]
+fn test_adaptive_integral() {
+    for (s, r) in [
+        (
+            "numerical_integral(-10,10,x,1/(1+1000x^2))",
+            "2atan(10sqrt(1000))/sqrt(1000)",
+        ),
+        ("numerical_integral(-inf,inf,x,exp(-x^2))", "sqrt(pi)"),
+        ("numerical_integral(0,inf,x,exp(-x))", "1"),
+        ("numerical_integral(-inf,0,x,exp(x))", "1"),
+        ("numerical_integral(inf,0,x,exp(-x))", "-1"),
+        ("numerical_integral(0,1,x,1/sqrt(x))", "2"),
+        ("exp``(0,1)", "e-2"),
+    ] {
+        let d = (eval(s) - eval(r)).abs();
+        assert!(d < Float::from(2.0).pow(Float::from(-24)), "{s} {d:?}");
+    }
+    for s in [
+        "numerical_integral(inf,inf,x,1)",
+        "numerical_integral(-inf,-inf,x,1)",
+        "numerical_integral(2,2,x,1/0)",
+    ] {
+        assert_eq!(eval(s), res(0), "{s}");
+    }
+    #[cfg(not(feature = "rational"))]
+    assert_eq!(
+        eval("numerical_integral(0,1,x,1/x)").to_string(),
+        res(Constant::Nan).to_string()
+    );
+    let tokens = Tokens(vec![Token::InnerVar(0), num(2), Function::Pow.into()]);
+    let (value, err) = Compute::new(&tokens[..], &[], &[], &[], 0)
+        .numerical_integral(
+            &mut vec![Number::default()],
+            &mut Vec::new(),
+            res(0),
+            res(1),
+            0,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap();
+    assert_approx_eq(value, res(1) / res(3));
+    assert!(err < Float::from(2.0).pow(Float::from(-32)));
+    assert_eq!(eval("integral_error(0,1,x,x^2)"), Number::from(err));
+    #[cfg(not(feature = "rational"))]
+    assert!(*eval("integral_error(0,1,x,1/x)").real() > Float::from(2.0).pow(Float::from(-8)));
+}
+#[test]
 fn test_numerical_derivative() {
     assert_approx_correct(
         "numerical_derivative(2,x,x^2-2)",
@@ -1404,6 +1686,103 @@ fn test_numerical_solve() {
     );
 }
 #[test]
+fn test_numerical_bracket() {
+    for (s, r) in [
+        ("numerical_bracket(0,2,x,x^2-2)", "sqrt(2)"),
+        ("numerical_bracket(2,0,x,x^2-2)", "sqrt(2)"),
+        ("numerical_bracket(3,4,x,sin(x))", "pi"),
+        ("numerical_bracket(-2,0,x,x^3+2)", "-(2^(1/3))"),
+        ("numerical_solve(1,x,x^2-2)", "sqrt(2)"),
+        #[cfg(feature = "complex")]
+        ("numerical_solve(1,x,x^2+1)", "i"),
+    ] {
+        let d = (eval(s) - eval(r)).abs();
+        assert!(d < Float::from(2.0).pow(Float::from(-32)), "{s} {d:?}");
+    }
+    for s in [
+        "numerical_bracket(-1,1,x,x^2+1)",
+        "numerical_solve(1,x,exp(x))",
+        "numerical_solve(1,x,abs(x)+1)",
+    ] {
+        assert!(eval(s).real().is_nan(), "{s}");
+    }
+}
+#[cfg(feature = "list")]
+#[test]
+fn test_numerical_roots() {
+    for (s, r) in [
+        (
+            "numerical_roots(-1,7,x,sin(x))",
+            vec![res(0), res(Constant::Pi), res(Constant::Pi) * res(2)],
+        ),
+        (
+            "numerical_roots(-1,2,x,(x-1)^2(x+0.5))",
+            vec![res(-0.5), res(1)],
+        ),
+        ("numerical_roots(-1,1,x,x^2+1)", vec![]),
+    ] {
+        let n = eval(s);
+        let list = n.list_ref();
+        assert_eq!(list.len(), r.len(), "{s}");
+        for (a, b) in list.iter().zip(r) {
+            assert_approx_eq(a.clone(), b);
+        }
+    }
+}
+#[cfg(feature = "complex")]
+#[test]
+fn test_aberth() {
+    let check = |x: Number| {
+        assert!(
+            (x.clone().pow(Float::from(5)) - x - res(1)).abs()
+                < Float::from(2.0).pow(Float::from(-40))
+        )
+    };
+    #[cfg(feature = "list")]
+    {
+        let Number::List(l) = eval("solve(x^5-x-1)") else {
+            unreachable!()
+        };
+        assert_eq!(l.len(), 5);
+        l.into_iter().for_each(check)
+    }
+    #[cfg(not(feature = "list"))]
+    check(eval("solve(x^5-x-1)"));
+    assert_approx_eq(
+        eval("solve(x,(x-1)(x-2)(x-3)(x-4)(x-5)(x+2))"),
+        roots(
+            res(-2),
+            vec![-2, 1, 2, 3, 4, 5].into_iter().map(res).collect(),
+        ),
+    );
+    let sol = eval("solve(x,x^7-128)");
+    #[cfg(feature = "list")]
+    {
+        assert_eq!(sol.list_ref().len(), 7);
+        for a in sol.list_ref() {
+            assert_approx_eq(a.clone().pow(res(7)), res(128));
+        }
+        assert_approx_eq(sol.list_ref()[0].clone(), res(2));
+    }
+    #[cfg(not(feature = "list"))]
+    assert_approx_eq(sol, res(2));
+    let poly = [-1, -1, 0, 0, 0, 1].map(res);
+    let roots = PolyRef(&poly).aberth();
+    assert_eq!(roots.len(), 5);
+    for (i, a) in roots.iter().enumerate() {
+        let x = a.clone();
+        assert!(
+            (x.clone().pow(Float::from(5)) - x - res(1)).abs()
+                < Float::from(2.0).pow(Float::from(-40))
+        );
+        assert!(
+            roots[i + 1..]
+                .iter()
+                .all(|b| (a.clone() - b).abs() > Float::from(0.1))
+        );
+    }
+}
+#[test]
 fn test_solve() {
     assert_correct(
         "1^2+y^2=2",
@@ -1421,7 +1800,7 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(1),
+        roots(res(1), vec![res(-1), res(1)]),
     );
     assert_correct(
         "0.5^2+y^2=1",
@@ -1439,7 +1818,10 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(3).sqrt() / res(2),
+        roots(
+            res(3).sqrt() / res(2),
+            vec![-res(3).sqrt() / res(2), res(3).sqrt() / res(2)],
+        ),
     );
     assert_correct(
         "solve(x,2-3x)",
@@ -1453,7 +1835,7 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(2) / res(3),
+        roots(res(2) / res(3), vec![res(2) / res(3)]),
     );
     assert_correct(
         "solve(x,x^4-2x^2+1)",
@@ -1473,7 +1855,7 @@ fn test_solve() {
             Function::Add.into(),
             Function::Solve.into(),
         ],
-        res(1),
+        roots(res(-1), vec![res(-1), res(1)]),
     );
     assert_correct(
         "solve(x,4-x-x)",
@@ -1487,7 +1869,7 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(2),
+        roots(res(2), vec![res(2)]),
     );
     assert_correct(
         "solve(x,x^2-2)",
@@ -1501,7 +1883,7 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(2).sqrt(),
+        roots(res(2).sqrt(), vec![-res(2).sqrt(), res(2).sqrt()]),
     );
     assert_correct(
         "solve(x,2*x-1)",
@@ -1515,7 +1897,7 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(0.5),
+        roots(res(0.5), vec![res(0.5)]),
     );
     assert_correct(
         "solve(x,x*x-2*x-1)",
@@ -1533,7 +1915,13 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        Float::from(1) - res(2).sqrt(),
+        roots(
+            Float::from(1) - res(2).sqrt(),
+            vec![
+                Float::from(1) - res(2).sqrt(),
+                Float::from(1) + res(2).sqrt(),
+            ],
+        ),
     );
     assert_correct(
         "solve(x,exp(x)^2-2exp(x)+1)",
@@ -1553,7 +1941,7 @@ fn test_solve() {
             Function::Add.into(),
             Function::Solve.into(),
         ],
-        res(0),
+        roots(res(0), vec![res(0)]),
     );
     assert_correct(
         "solve(x,ln(x))",
@@ -1564,7 +1952,7 @@ fn test_solve() {
             Function::Ln.into(),
             Function::Solve.into(),
         ],
-        res(1),
+        roots(res(1), vec![res(1)]),
     );
     let mut funs = Functions(vec![]);
     assert!(
@@ -1616,7 +2004,10 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(3).sqrt() - res(3),
+        roots(
+            res(3).sqrt() - res(3),
+            vec![-res(3).sqrt() - res(3), res(3).sqrt() - res(3)],
+        ),
     );
     let mut funs = Functions(vec![]);
     assert!(
@@ -1668,7 +2059,132 @@ fn test_solve() {
             Function::Sub.into(),
             Function::Solve.into(),
         ],
-        res(3).sqrt() - res(3),
+        roots(
+            -res(3).sqrt() - res(3),
+            vec![-res(3).sqrt() - res(3), res(3).sqrt() - res(3)],
+        ),
+    );
+}
+#[test]
+fn test_solve_inline() {
+    let mut funs = Functions::default();
+    let mut vars = Variables::default();
+    for s in ["let f(a)=a^2+a", "let g(a,b)=a*b", "let h(a)=g(a,a+1)"] {
+        Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap();
+    }
+    for (s, r) in [
+        ("f(x)=3", res(-0.5) - res(13).sqrt() / res(2)),
+        ("f(x)+x=6", res(-1) - res(7).sqrt()),
+        ("f(x)+f(x)=6", res(-0.5) - res(13).sqrt() / res(2)),
+        ("x+f(x)=6", res(-1) - res(7).sqrt()),
+        ("f(x+1)+x=5", res(-2) - res(7).sqrt()),
+        ("g(x,x)+h(x)=6", res(-2)),
+        ("f'(x)+x=4", res(1)),
+        ("solve(x,g(x,x)-4)", res(-2)),
+        ("solve(x,h(x)-6)", res(-3)),
+        ("solve(x,f'(x)-3)", res(1)),
+        ("sin'(x)=0.5", res(Constant::Pi) / res(3)),
+        ("solve(x,floor(x)+x-2.5)", res(1.5)),
+        ("solve(x,x+sin(x)-1)", res(0.510973429388569)),
+    ] {
+        let n = Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap()
+        .tokens()
+        .compute(
+            &[],
+            &funs,
+            &vars,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap();
+        #[cfg(feature = "list")]
+        let n = n.list_ref()[0].clone();
+        assert_approx_eq(n, r);
+    }
+}
+#[cfg(all(feature = "complex", feature = "list"))]
+#[test]
+fn test_solve_range() {
+    let pi = |a: f64| res(Constant::Pi) * res(a);
+    for (s, r) in [
+        (
+            "solve_range(0,7,x,sin(x)-0.5)",
+            vec![pi(1.0 / 6.0), pi(5.0 / 6.0), pi(13.0 / 6.0)],
+        ),
+        (
+            "solve_range(4,-4,x,cos(2x)-1)",
+            vec![pi(-1.0), res(0), pi(1.0)],
+        ),
+        (
+            "solve_range(-4,4,x,tan(x)-1)",
+            vec![pi(-0.75), pi(0.25), pi(1.25)],
+        ),
+        (
+            "solve_range(0,10,x,sin(x)^2-0.25)",
+            [1, 5, 7, 11, 13, 17, 19]
+                .map(|k| pi(k as f64 / 6.0))
+                .to_vec(),
+        ),
+        ("solve_range(0,3,x,x^4-5x^2+4)", vec![res(1), res(2)]),
+        (
+            "solve_range(-7i,7i,x,exp(x)-1)",
+            vec![res(0), pi(-2.0).mul_i(false), pi(2.0).mul_i(false)],
+        ),
+        (
+            "solve_range(0,2,x,x+sin(x)-1)",
+            vec![res(0.510973429388569)],
+        ),
+        ("solve_range(0,1,x,sin(x)-2)", vec![]),
+    ] {
+        let n = eval(s);
+        let list = n.list_ref();
+        assert_eq!(list.len(), r.len(), "{s}");
+        for (a, b) in list.iter().zip(r) {
+            assert_approx_eq(a.clone(), b);
+        }
+    }
+    for (s, n) in [
+        ("solve_range(0,2000,x,sin(x))", 637),
+        ("numerical_roots(0,200,x,sin(x))", 64),
+    ] {
+        assert_eq!(eval(s).list_ref().len(), n, "{s}");
+    }
+    for s in [
+        "solve_range(0,10^6,x,sin(x))",
+        "solve_range(0,inf,x,sin(x))",
+        "numerical_roots(0,10^4,x,sin(x))",
+    ] {
+        assert_eq!(try_eval(s), Err(EvalError::TooManySolutions), "{s}");
+    }
+    assert_approx_eq(
+        eval("solve(x,x^3-8)"),
+        Number::List(vec![
+            res(2),
+            Number::from((Float::from(-1), -Float::from(3).sqrt())),
+            Number::from((Float::from(-1), Float::from(3).sqrt())),
+        ]),
     );
 }
 #[test]
@@ -2677,13 +3193,346 @@ fn test_exprs() {
         res(26),
     );
 }
-#[test]
-fn test_prod() {
-    assert_correct(
-        "prod(1,4,x,x^2)",
-        "1 4 x x 2 ^ prod",
-        vec![
-            num(1),
+fn try_eval(s: &str) -> Result<Number, EvalError> {
+    infix(s, true).compute(
+        &[],
+        &Functions::default(),
+        &Variables::default(),
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+}
+fn eval(s: &str) -> Number {
+    try_eval(s).unwrap()
+}
+fn compute(tokens: Vec<Token>) -> Result<Number, EvalError> {
+    Tokens(tokens).compute(
+        &[],
+        &Functions::default(),
+        &Variables::default(),
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+fn vector(v: &[isize]) -> Number {
+    Number::Vector(
+        v.iter()
+            .map(|&n| crate::NumberBase::from(n))
+            .collect::<Vec<_>>()
+            .into(),
+    )
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+fn matrix(v: &[isize], width: u32) -> Number {
+    Number::Matrix(Matrix::new(
+        v.iter().map(|&n| crate::NumberBase::from(n)).collect(),
+        width,
+    ))
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+#[test]
+fn test_vector() {
+    assert_correct(
+        "[1,2,3]",
+        "1 2 3 vector3",
+        vec![
+            num(1),
+            num(2),
+            num(3),
+            Function::Vector(NonZeroU8::new(3).unwrap()).into(),
+        ],
+        vector(&[1, 2, 3]),
+    );
+    assert_eq!(eval("2*[1,2]+[3,4]"), vector(&[5, 8]));
+    assert_eq!(eval("sq([1,-2,3])"), vector(&[1, 4, 9]));
+    assert_eq!(
+        eval("[1,2]+[1,2,3]").to_string(),
+        res(Constant::Nan).to_string()
+    );
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+#[test]
+fn test_matrix() {
+    assert_correct(
+        "[[1,2],[3,4]]",
+        "1 2 vector2 3 4 vector2 vector2",
+        vec![
+            num(1),
+            num(2),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+            num(3),
+            num(4),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+        ],
+        matrix(&[1, 2, 3, 4], 2),
+    );
+    assert_eq!(
+        eval("[[1,2],[3,4]]*[[5,6],[7,8]]"),
+        matrix(&[19, 22, 43, 50], 2)
+    );
+    assert_eq!(eval("[[1,2,3],[4,5,6]]*[1,0,1]"), vector(&[4, 10]));
+    assert_eq!(eval("[[1,2],[3,4]]-1"), matrix(&[0, 1, 2, 3], 2));
+    assert_eq!(
+        Tokens(vec![
+            num(1),
+            num(2),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+            num(3),
+            num(4),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+            Function::Vector(NonZeroU8::new(2).unwrap()).into(),
+            num(1),
+            num(1),
+            Function::Index(ModifyInputs::Three).into(),
+            num(1),
+            Function::Add.into(),
+        ])
+        .get_infix(&[], &[], &[])
+        .to_string(),
+        "[[1,2],[3,4]][1,1]+1"
+    );
+    assert_eq!(
+        matrix(&[1, 2, 3, 4], 2).to_string(),
+        "[[1,2],[3,4]]".to_string()
+    );
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+#[test]
+fn test_system() {
+    assert_eq!(
+        Tokens::infix(
+            "x+y=3,x-y=1",
+            &mut Variables::default(),
+            &mut Functions::default(),
+            &[],
+            false,
+            false,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng()
+        )
+        .unwrap()
+        .tokens_any(),
+        Tokens(vec![
+            Token::Skip(5),
+            Token::InnerVar(0),
+            Token::InnerVar(1),
+            Function::Add.into(),
+            num(3),
+            Function::Sub.into(),
+            Token::Skip(5),
+            Token::InnerVar(0),
+            Token::InnerVar(1),
+            Function::Sub.into(),
+            num(1),
+            Function::Sub.into(),
+            Function::System(NonZeroU8::new(2).unwrap()).into(),
+        ])
+    );
+    assert_eq!(eval("x+y=3,x-y=1"), vector(&[2, 1]));
+    assert_eq!(eval("solve((x,y),2x+y-5,x-y+2)"), vector(&[1, 3]));
+    assert_eq!(
+        eval("solve((a,b,c),a+b+c=6,a-b=-1,2c=6)"),
+        vector(&[1, 2, 3])
+    );
+    assert_eq!(eval("1+solve((x,y),x+y=3,x-y=1)[0]"), res(3));
+    #[cfg(not(feature = "fastnum"))]
+    assert_eq!(eval("x^2+y^2=5,x*y=2"), vector(&[1, 2]));
+    #[cfg(feature = "fastnum")]
+    {
+        let Number::Vector(d) = eval("x^2+y^2=5,x*y=2") - vector(&[1, 2]) else {
+            unreachable!()
+        };
+        assert!(
+            d.iter()
+                .all(|a| a.clone().abs() < Float::from(2.0).pow(Float::from(-24)))
+        );
+    }
+    for s in ["x+y=1,x+y=2", "x+y=1,2x+2y=2"] {
+        assert_eq!(try_eval(s), Err(EvalError::NoSolution), "{s}");
+    }
+    #[cfg(not(feature = "complex"))]
+    assert_eq!(try_eval("x^2=-1,y=1"), Err(EvalError::NoSolution));
+    #[cfg(feature = "complex")]
+    {
+        let Number::Vector(d) = eval("x^2=-1,y=1") else {
+            unreachable!()
+        };
+        assert!(
+            (d[0].clone() * d[0].clone() + crate::NumberBase::from(1)).abs()
+                < Float::from(2.0).pow(Float::from(-16))
+        );
+        assert_eq!(d[1], 1.into());
+    }
+    assert_eq!(
+        eval("x^2+y=2,x-y=0").to_string(),
+        eval("solve((x,y),y+x^2=2,y-x=0)").to_string()
+    );
+    assert_eq!(eval("solve((a,b),(a+b)*(a-b)=0,a+b=2)"), vector(&[1, 1]));
+    for s in [
+        "solve((a,a),a=1,a=2)",
+        "solve((a,1),a=1,a=2)",
+        "solve((a,b)+1,a=1,b=2)",
+        "solve((a,b),a=1,b=2,a=3)",
+    ] {
+        assert!(
+            Tokens::infix(
+                s,
+                &mut Variables::default(),
+                &mut Functions::default(),
+                &[],
+                false,
+                true,
+                10,
+                #[cfg(feature = "float_rand")]
+                &mut rng()
+            )
+            .is_err(),
+            "{s}"
+        );
+    }
+    let vars = (0..256).map(|i| format!("v{i}")).collect::<Vec<_>>();
+    let s = format!(
+        "solve(({}),{})",
+        vars.join(","),
+        vars.iter()
+            .map(|v| format!("{v}=0"))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    assert!(
+        Tokens::infix(
+            &s,
+            &mut Variables::default(),
+            &mut Functions::default(),
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng()
+        )
+        .is_err()
+    );
+    assert_eq!(eval("solve((a),a-2)"), eval("solve(a,a-2)"));
+    for (s, span, error) in [
+        ("x+y=3", 5..5, ParseError::SystemError),
+        ("x+y=3,x-y", 9..9, ParseError::SystemError),
+        ("x=y=1,y=2", 5..6, ParseError::SystemError),
+        ("1,2", 1..2, ParseError::SystemError),
+        ("1,2]", 3..4, ParseError::RightSquareBracketFailed),
+        ("x=1,2]", 5..6, ParseError::RightSquareBracketFailed),
+    ] {
+        assert_eq!(
+            Tokens::infix(
+                s,
+                &mut Variables::default(),
+                &mut Functions::default(),
+                &[],
+                false,
+                true,
+                10,
+                #[cfg(feature = "float_rand")]
+                &mut rng()
+            ),
+            Err(ParseErrorSpan { error, span }),
+            "{s}"
+        );
+    }
+}
+#[cfg(all(feature = "vector", feature = "matrix"))]
+#[test]
+fn test_index() {
+    assert_correct(
+        "[4,5,6][1]+1",
+        "4 5 6 vector3 1 index 1 +",
+        vec![
+            num(4),
+            num(5),
+            num(6),
+            Function::Vector(NonZeroU8::new(3).unwrap()).into(),
+            num(1),
+            Function::Index(ModifyInputs::Two).into(),
+            num(1),
+            Function::Add.into(),
+        ],
+        res(6),
+    );
+    assert_eq!(eval("[[1,2],[3,4]][1,0]"), res(3));
+    assert_eq!(eval("[[1,2],[3,4]][1][1]"), res(4));
+    assert_eq!(eval("[[1,2],[3,4]][0]"), vector(&[1, 2]));
+    assert_eq!(eval("[1,2][2]").to_string(), res(Constant::Nan).to_string());
+    assert_eq!(
+        Tokens::infix(
+            "[1,2)",
+            &mut Variables::default(),
+            &mut Functions::default(),
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng()
+        ),
+        Err(ParseErrorSpan {
+            error: ParseError::SquareBracketFailed,
+            span: 4..5
+        })
+    );
+    assert_eq!(
+        Tokens::infix(
+            "(1]",
+            &mut Variables::default(),
+            &mut Functions::default(),
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng()
+        ),
+        Err(ParseErrorSpan {
+            error: ParseError::RightSquareBracketFailed,
+            span: 2..3
+        })
+    );
+    let list = |n: isize| {
+        format!(
+            "[{}]",
+            (1..=n).map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+        )
+    };
+    assert_eq!(eval(&list(255)), vector(&(1..=255).collect::<Vec<_>>()));
+    let s = list(300);
+    let comma = s.match_indices(',').nth(254).unwrap().0;
+    assert_eq!(
+        Tokens::infix(
+            &s,
+            &mut Variables::default(),
+            &mut Functions::default(),
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng()
+        )
+        .map(|_| ()),
+        Err(ParseErrorSpan {
+            error: ParseError::ExtraInput,
+            span: comma..comma + 1
+        })
+    );
+}
+#[test]
+fn test_prod() {
+    assert_correct(
+        "prod(1,4,x,x^2)",
+        "1 4 x x 2 ^ prod",
+        vec![
+            num(1),
             num(4),
             Token::Skip(3),
             Token::InnerVar(0),
@@ -2897,38 +3746,607 @@ fn test_cmp() {
         res(1),
     );
     assert_correct(
-        "3>2<4",
-        "3 2 4 < >",
+        "3>2<4",
+        "3 2 4 < >",
+        vec![
+            num(3),
+            num(2),
+            num(4),
+            Function::Less.into(),
+            Function::Greater.into(),
+        ],
+        res(1),
+    );
+    assert_correct(
+        "3>2>4",
+        "3 2 4 > >",
+        vec![
+            num(3),
+            num(2),
+            num(4),
+            Function::Greater.into(),
+            Function::Greater.into(),
+        ],
+        res(0),
+    );
+}
+#[test]
+fn test_tetration() {
+    assert_correct(
+        "2^^3",
+        "2 3 ^^",
+        vec![num(2), num(3), Function::Tetration.into()],
+        res(16),
+    );
+}
+#[test]
+fn test_factorial() {
+    assert_correct(
+        "20!",
+        "20 !",
+        vec![num(20), Function::Factorial.into()],
+        res(2432902008176640000u64),
+    );
+    assert_correct(
+        "factorial(22)",
+        "22 factorial",
+        vec![num(22), Function::Factorial.into()],
+        res(1124000727777607680000u128),
+    );
+}
+#[test]
+fn test_number_theory() {
+    assert_correct(
+        "gcd(12,18)",
+        "12 18 gcd",
+        vec![num(12), num(18), Function::Gcd.into()],
+        res(6),
+    );
+    assert_correct(
+        "lcm(4,6)",
+        "4 6 lcm",
+        vec![num(4), num(6), Function::Lcm.into()],
+        res(12),
+    );
+    assert_correct(
+        "isprime(97)",
+        "97 isprime",
+        vec![num(97), Function::IsPrime.into()],
+        res(1),
+    );
+    assert_correct(
+        "nextprime(13)",
+        "13 nextprime",
+        vec![num(13), Function::NextPrime.into()],
+        res(17),
+    );
+    assert_correct(
+        "modpow(4,13,497)",
+        "4 13 497 modpow",
+        vec![num(4), num(13), num(497), Function::ModPow.into()],
+        res(445),
+    );
+    assert_correct(
+        "modinv(3,11)",
+        "3 11 modinv",
+        vec![num(3), num(11), Function::ModInv.into()],
+        res(4),
+    );
+    assert_correct(
+        "totient(36)",
+        "36 totient",
+        vec![num(36), Function::Totient.into()],
+        res(12),
+    );
+    assert_correct(
+        "sigma(28)",
+        "28 sigma",
+        vec![num(28), Function::Sigma.into()],
+        res(56),
+    );
+    #[cfg(feature = "list")]
+    assert_correct(
+        "factor(360)",
+        "360 factor",
+        vec![num(360), Function::Factor.into()],
+        Number::List(vec![res(2), res(2), res(2), res(3), res(3), res(5)]),
+    );
+    #[cfg(feature = "list")]
+    assert_correct(
+        "divisors(12)",
+        "12 divisors",
+        vec![num(12), Function::Divisors.into()],
+        Number::List(vec![res(1), res(2), res(3), res(4), res(6), res(12)]),
+    );
+    assert_eq!(
+        compute(vec![num(2), num(4), Function::ModInv.into()])
+            .unwrap()
+            .to_string(),
+        res(Constant::Nan).to_string()
+    );
+    assert_eq!(
+        compute(vec![num(1.5), num(2), Function::Gcd.into()]),
+        Err(EvalError::NonInteger)
+    );
+    assert_eq!(
+        compute(vec![num(Constant::Infinity), Function::IsPrime.into()]),
+        Err(EvalError::NonInteger)
+    );
+    assert_eq!(eval("isprime(2^53-111)"), res(1));
+    #[cfg(feature = "f64")]
+    for s in [
+        "isprime(2^53+1)",
+        "isprime(2^61-1)",
+        "isprime(2^89-1)",
+        "gcd(2^60,3)",
+    ] {
+        assert_eq!(try_eval(s), Err(EvalError::TooLarge), "{s}");
+    }
+    #[cfg(not(feature = "f64"))]
+    {
+        assert_eq!(eval("isprime(2^53+1)"), res(0));
+        assert_eq!(eval("isprime(2^61-1)"), res(1));
+        assert_eq!(eval("isprime(2^89-1)"), res(1));
+        #[cfg(feature = "list")]
+        assert_eq!(eval("factor(2^61-1)"), Number::List(vec![eval("2^61-1")]));
+        #[cfg(feature = "list")]
+        assert_eq!(
+            eval("factor(1000000016000000063)"),
+            Number::List(vec![res(1000000007), res(1000000009)])
+        );
+    }
+}
+#[test]
+fn test_combinatorics() {
+    assert_correct(
+        "binomial(10,3)",
+        "10 3 binomial",
+        vec![num(10), num(3), Function::Binomial.into()],
+        res(120),
+    );
+    assert_correct(
+        "choose(10,3)",
+        "10 3 choose",
+        vec![num(10), num(3), Function::Binomial.into()],
+        res(120),
+    );
+    assert_correct(
+        "perm(10,3)",
+        "10 3 perm",
+        vec![num(10), num(3), Function::Perm.into()],
+        res(720),
+    );
+    assert_correct(
+        "multinomial(2,3,4)",
+        "2 3 4 multinomial3",
+        vec![
+            num(2),
+            num(3),
+            num(4),
+            Function::Multinomial(NonZeroU8::new(3).unwrap()).into(),
+        ],
+        res(1260),
+    );
+    assert_correct(
+        "stirling_first(5,2)",
+        "5 2 stirling_first",
+        vec![num(5), num(2), Function::StirlingFirst.into()],
+        res(-50),
+    );
+    assert_correct(
+        "stirling_second(5,2)",
+        "5 2 stirling_second",
+        vec![num(5), num(2), Function::StirlingSecond.into()],
+        res(15),
+    );
+    assert_correct(
+        "bell(10)",
+        "10 bell",
+        vec![num(10), Function::Bell.into()],
+        res(115975),
+    );
+    assert_correct(
+        "catalan(10)",
+        "10 catalan",
+        vec![num(10), Function::Catalan.into()],
+        res(16796),
+    );
+    assert_correct(
+        "partitions(100)",
+        "100 partitions",
+        vec![num(100), Function::Partitions.into()],
+        res(190569292),
+    );
+    assert_approx_correct(
+        "binomial(0.5,2)",
+        "0.5 2 binomial",
+        vec![num(0.5), num(2), Function::Binomial.into()],
+        res(-0.125),
+    );
+    assert_approx_correct(
+        "perm(2.5,2)",
+        "2.5 2 perm",
+        vec![num(2.5), num(2), Function::Perm.into()],
+        res(3.75),
+    );
+    assert_correct(
+        "binomial(0.5,2.5)",
+        "0.5 2.5 binomial",
+        vec![num(0.5), num(2.5), Function::Binomial.into()],
+        res(0),
+    );
+    assert_correct(
+        "binomial(-1,2)",
+        "1 ~ 2 binomial",
+        vec![
+            num(1),
+            Function::Negate.into(),
+            num(2),
+            Function::Binomial.into(),
+        ],
+        res(1),
+    );
+    assert_correct(
+        "perm(-1,2)",
+        "1 ~ 2 perm",
+        vec![
+            num(1),
+            Function::Negate.into(),
+            num(2),
+            Function::Perm.into(),
+        ],
+        res(2),
+    );
+    assert_correct(
+        "perm(0.5,2.5)",
+        "0.5 2.5 perm",
+        vec![num(0.5), num(2.5), Function::Perm.into()],
+        res(0),
+    );
+    assert_correct(
+        "multinomial(-1,1.5)",
+        "1 ~ 1.5 multinomial2",
+        vec![
+            num(1),
+            Function::Negate.into(),
+            num(1.5),
+            Function::Multinomial(NonZeroU8::new(2).unwrap()).into(),
+        ],
+        res(0),
+    );
+    assert_approx_correct(
+        "catalan(0.5)",
+        "0.5 catalan",
+        vec![num(0.5), Function::Catalan.into()],
+        res(8.0 / (3.0 * std::f64::consts::PI)),
+    );
+    #[cfg(feature = "complex")]
+    assert_approx_correct(
+        "binomial(i,1)",
+        "i 1 binomial",
+        vec![var("i"), num(1), Function::Binomial.into()],
+        res((0, 1)),
+    );
+    #[cfg(feature = "complex")]
+    assert_eq!(eval("binomial(2i,1)"), res((0, 2)));
+    #[cfg(feature = "complex")]
+    assert_eq!(eval("perm(2i,2)"), res((-4, -2)));
+    assert_eq!(
+        compute(vec![num(1.5), num(1), Function::StirlingFirst.into()]),
+        Err(EvalError::NonInteger)
+    );
+    for s in [
+        "bell(1000000)",
+        "partitions(100000000)",
+        "catalan(100000)",
+        "stirling_first(5000,5000)",
+        "stirling_second(5000,2)",
+    ] {
+        assert_eq!(try_eval(s), Err(EvalError::TooLarge), "{s}");
+    }
+    assert_eq!(
+        compute(vec![num(0.5), Function::Bell.into()]),
+        Err(EvalError::NonInteger)
+    );
+}
+#[test]
+#[cfg(feature = "list")]
+fn test_statistics() {
+    assert_correct(
+        "mean(divisors(12))",
+        "12 divisors mean",
+        vec![num(12), Function::Divisors.into(), Function::Mean.into()],
+        res(28) / res(6),
+    );
+    assert_correct(
+        "max(divisors(12))",
+        "12 divisors max1",
+        vec![
+            num(12),
+            Function::Divisors.into(),
+            Function::Max(NonZeroU8::new(1).unwrap()).into(),
+        ],
+        res(12),
+    );
+    let list = |v: &[isize]| Token::Number(Number::List(v.iter().map(|&n| res(n)).collect()));
+    let x = || list(&[2, 4, 4, 4, 5, 5, 7, 9]);
+    let y = || list(&[5, 9, 9, 9, 11, 11, 15, 19]);
+    for (f, r) in [
+        (Function::Len, res(8)),
+        (Function::Mean, res(5)),
+        (Function::Median, res(4.5)),
+        (Function::Mode, res(4)),
+        (Function::Var, res(4)),
+        (Function::SampleVar, res(32) / res(7)),
+        (Function::Stddev, res(2)),
+        (Function::Min(NonZeroU8::new(1).unwrap()), res(2)),
+        (Function::Max(NonZeroU8::new(1).unwrap()), res(9)),
+    ] {
+        assert_approx_eq(compute(vec![x(), f.into()]).unwrap(), r);
+    }
+    assert_approx_eq(
+        compute(vec![x(), Function::SampleStddev.into()]).unwrap(),
+        (res(32) / res(7)).sqrt(),
+    );
+    for (p, r) in [(0, res(2)), (25, res(4)), (50, res(4.5)), (100, res(9))] {
+        assert_approx_eq(
+            compute(vec![x(), num(p), Function::Percentile.into()]).unwrap(),
+            r,
+        );
+    }
+    assert_eq!(
+        compute(vec![x(), num(101), Function::Percentile.into()])
+            .unwrap()
+            .to_string(),
+        res(Constant::Nan).to_string()
+    );
+    assert_approx_eq(
+        compute(vec![x(), y(), Function::Covariance.into()]).unwrap(),
+        res(8),
+    );
+    assert_approx_eq(
+        compute(vec![x(), y(), Function::Correlation.into()]).unwrap(),
+        res(1),
+    );
+    assert_approx_eq(
+        compute(vec![x(), y(), Function::Linreg.into()]).unwrap(),
+        Number::List(vec![res(2), res(1), res(1)]),
+    );
+    assert_eq!(
+        compute(vec![x(), list(&[1, 2]), Function::Covariance.into()])
+            .unwrap()
+            .to_string(),
+        res(Constant::Nan).to_string()
+    );
+    assert_eq!(
+        compute(vec![x(), list(&[3; 8]), Function::Correlation.into()])
+            .unwrap()
+            .to_string(),
+        res(Constant::Nan).to_string()
+    );
+    #[cfg(all(feature = "vector", feature = "matrix"))]
+    assert_eq!(eval("median([3,1,2])"), res(2));
+}
+#[test]
+#[cfg(feature = "list")]
+fn test_list_operations() {
+    let list = |v: &[isize]| Number::List(v.iter().map(|&n| res(n)).collect());
+    assert_correct(
+        "range(1,3)",
+        "1 3 range",
+        vec![num(1), num(3), Function::Range(ModifyInputs::Two).into()],
+        list(&[1, 2, 3]),
+    );
+    assert_correct(
+        "range(0,1,0.25)",
+        "0 1 0.25 range3",
+        vec![
+            num(0),
+            num(1),
+            num(0.25),
+            Function::Range(ModifyInputs::Three).into(),
+        ],
+        Number::List(vec![res(0), res(0.25), res(0.5), res(0.75), res(1)]),
+    );
+    assert_correct(
+        "map(range(1,3),x,x^2)",
+        "1 3 range x x 2 ^ map",
+        vec![
+            num(1),
+            num(3),
+            Function::Range(ModifyInputs::Two).into(),
+            Token::Skip(3),
+            Token::InnerVar(0),
+            num(2),
+            Function::Pow.into(),
+            Function::Map.into(),
+        ],
+        list(&[1, 4, 9]),
+    );
+    assert_correct(
+        "filter(range(1,6),n,n%2)",
+        "1 6 range n n 2 % filter",
+        vec![
+            num(1),
+            num(6),
+            Function::Range(ModifyInputs::Two).into(),
+            Token::Skip(3),
+            Token::InnerVar(0),
+            num(2),
+            Function::Mod.into(),
+            Function::Filter.into(),
+        ],
+        list(&[1, 3, 5]),
+    );
+    for (s, r) in [
+        ("range(5,1,-2)", list(&[5, 3, 1])),
+        (
+            "sort(concat(range(4,6),range(1,3)))",
+            list(&[1, 2, 3, 4, 5, 6]),
+        ),
+        ("reverse(range(1,3))", list(&[3, 2, 1])),
+        ("concat(range(1,2),5)", list(&[1, 2, 5])),
+        ("slice(range(0,9),2,5)", list(&[2, 3, 4])),
+        ("slice(range(0,9),7)", list(&[7, 8, 9])),
+        ("slice(range(0,9),8,20)", list(&[8, 9])),
+        ("index(range(1,5),2)", res(3)),
+        ("index(zip(range(1,3),range(4,5)),1)", list(&[2, 5])),
+        ("len(zip(range(1,3),range(4,5)))", res(2)),
+        ("set(3,b,map(range(1,b),a,a+b))", list(&[4, 5, 6])),
+        ("map(range(1,3),a,filter(range(1,a),b,a%b==0))", {
+            Number::List(vec![list(&[1]), list(&[1, 2]), list(&[1, 3])])
+        }),
+        ("len(filter(range(1,100),p,isprime(p)))", res(25)),
+        (
+            "numerical_derivative(2,a,index(sort(range(a,a+2)),1))",
+            res(1),
+        ),
+    ] {
+        assert_approx_eq(eval(s), r);
+    }
+    for (s, len, last) in [
+        ("range(0,1,0.1)", 11, res(1)),
+        ("range(1,0,-0.1)", 11, res(0)),
+        ("range(0,0.3,0.1)", 4, res(0.3)),
+        ("range(0,0.35,0.1)", 4, res(0.3)),
+    ] {
+        let Number::List(l) = eval(s) else {
+            unreachable!()
+        };
+        assert_eq!(l.len(), len, "{s}");
+        assert_approx_eq(l[len - 1].clone(), last);
+    }
+    for s in ["range(1,0)", "range(1,2,0)", "slice(range(0,9),5,2)"] {
+        assert_eq!(eval(s), list(&[]));
+    }
+    for s in [
+        "derivative(2,a,index(map(range(1,2),b,a*b),1))",
+        "range(1,inf)",
+        "range(-inf,0)",
+        "range(0,10^12)",
+        "range(0,1,10^-12)",
+    ] {
+        assert_eq!(eval(s).to_string(), res(Constant::Nan).to_string(), "{s}");
+    }
+}
+#[test]
+fn test_distributions() {
+    assert_correct(
+        "normal_cdf(0,0,1)",
+        "0 0 1 normal_cdf",
         vec![
-            num(3),
-            num(2),
-            num(4),
-            Function::Less.into(),
-            Function::Greater.into(),
+            num(0),
+            num(0),
+            num(1),
+            Function::Distribution(Distribution::Normal, DistributionFunction::Cdf).into(),
         ],
-        res(1),
+        res(0.5),
     );
-    assert_correct(
-        "3>2>4",
-        "3 2 4 > >",
+    assert_approx_correct(
+        "binomial_pmf(3,10,0.5)",
+        "3 10 0.5 binomial_pmf",
         vec![
             num(3),
-            num(2),
-            num(4),
-            Function::Greater.into(),
-            Function::Greater.into(),
+            num(10),
+            num(0.5),
+            Function::Distribution(Distribution::Binomial, DistributionFunction::Density).into(),
         ],
-        res(0),
+        res(0.1171875),
     );
+    let close = |a: Number, b: f64| {
+        assert!(
+            (a.clone() - Number::from(b)).abs() < Float::from(1e-9),
+            "{a} {b}"
+        )
+    };
+    for (s, r) in [
+        ("normal_pdf(0,0,1)", 0.3989422804014327),
+        ("normal_cdf(1.96,0,1)", 0.9750021048517795),
+        ("normal_inv(0.975,0,1)", 1.959963984540054),
+        ("normal_inv(0.5,3,2)", 3.0),
+        ("normal_inv(0.00001,0,1)", -4.264890793922825),
+        ("student_t_pdf(0,3)", 0.3675525969478614),
+        ("student_t_cdf(2,5)", 0.9490302605850709),
+        ("student_t_cdf(-2,5)", 0.0509697394149291),
+        ("student_t_inv(0.975,10)", 2.2281388519649385),
+        ("student_t_inv(0.025,10)", -2.2281388519649385),
+        ("chi_squared_pdf(2,3)", 0.2075537487102974),
+        ("chi_squared_cdf(3.84,1)", 0.9499564787512949),
+        ("chi_squared_inv(0.95,1)", 3.841458820694124),
+        ("chi_squared_inv(0.95,10)", 18.307038053275146),
+        ("binomial_cdf(3,10,0.5)", 0.171875),
+        ("binomial_cdf(500,1000,0.5)", 0.5126125090888418),
+        ("binomial_pmf(0,10,0)", 1.0),
+        ("binomial_pmf(1.5,10,0.5)", 0.0),
+        ("binomial_inv(0.5,10,0.5)", 5.0),
+        ("binomial_inv(1,10,0.3)", 10.0),
+        ("poisson_pmf(2,3)", 0.22404180765538775),
+        ("poisson_cdf(2,3)", 0.42319008112684353),
+        ("poisson_cdf(1000,1000)", 0.5084093671689827),
+        ("poisson_inv(0.5,3)", 3.0),
+        ("exponential_pdf(1,2)", 0.2706705664732254),
+        ("exponential_cdf(1,2)", 0.8646647167633873),
+        ("exponential_inv(0.5,2)", 0.34657359027997264),
+        ("uniform_pdf(1,0,4)", 0.25),
+        ("uniform_pdf(5,0,4)", 0.0),
+        ("uniform_cdf(1,0,4)", 0.25),
+        ("uniform_inv(0.25,0,4)", 1.0),
+        ("gamma_pdf(2,2,3)", 0.11409269311835388),
+        ("gamma_cdf(2,2,3)", 0.14430480161234662),
+        ("gamma_cdf(gamma_inv(0.3,200,1),200,1)", 0.3),
+        ("chi_squared_inv(0,3)", 0.0),
+    ] {
+        close(eval(s), r)
+    }
+    for s in [
+        "normal_cdf(1,0,-1)",
+        "binomial_pmf(1,2.5,0.5)",
+        "poisson_cdf(1,0)",
+        "uniform_pdf(1,4,0)",
+        "normal_inv(2,0,1)",
+    ] {
+        assert_eq!(eval(s).to_string(), res(Constant::Nan).to_string(), "{s}")
+    }
+    for s in [
+        "normal_inv(1,0,1)",
+        "student_t_inv(1,3)",
+        "poisson_inv(1,3)",
+    ] {
+        assert_eq!(
+            eval(s).to_string(),
+            res(Constant::Infinity).to_string(),
+            "{s}"
+        )
+    }
 }
 #[test]
-fn test_tetration() {
-    assert_correct(
-        "2^^3",
-        "2 3 ^^",
-        vec![num(2), num(3), Function::Tetration.into()],
-        res(16),
+#[cfg(feature = "float_rand")]
+fn parse_rand_distribution() {
+    assert_teq(
+        infix("rand_normal(2,3)", true),
+        rpn("2 3 rand_normal", true),
+        Tokens(vec![
+            num(2),
+            num(3),
+            Function::RandDistribution(Distribution::Normal).into(),
+        ]),
     );
+    assert_eq!(
+        Function::try_from("rand_uniform"),
+        Ok(Function::RandUniform)
+    );
+    let sample = |s: &str| eval(s).to_real();
+    for _ in 0..16 {
+        let n = sample("rand_binomial(10,0.5)");
+        assert!(n >= Float::from(0) && n <= Float::from(10) && n.clone().fract().is_zero());
+        let n = sample("rand_poisson(4)");
+        assert!(n >= Float::from(0) && n.clone().fract().is_zero());
+        assert!(sample("rand_exponential(2)") >= Float::from(0));
+        assert!(sample("rand_gamma(2,3)") >= Float::from(0));
+        assert!(!sample("rand_normal(0,1)").is_nan());
+        assert!(!sample("rand_student_t(3)").is_nan());
+        assert!(!sample("rand_chi_squared(3)").is_nan());
+    }
 }
 #[test]
 fn test_subfactorial() {
@@ -3027,7 +4445,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng(),
         ),
-        Err(ParseError::UnknownToken("2.3.4")),
+        Err(ParseErrorSpan {
+            error: ParseError::UnknownToken("2.3.4"),
+            span: 0..5,
+        }),
     );
     assert_eq!(
         Tokens::infix(
@@ -3041,7 +4462,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::MissingInput)
+        Err(ParseErrorSpan {
+            error: ParseError::MissingInput,
+            span: 3..4
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3055,7 +4479,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::MissingInput)
+        Err(ParseErrorSpan {
+            error: ParseError::MissingInput,
+            span: 3..4
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3069,7 +4496,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::MissingInput)
+        Err(ParseErrorSpan {
+            error: ParseError::MissingInput,
+            span: 2..3
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3083,7 +4513,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::AbsoluteBracketFailed)
+        Err(ParseErrorSpan {
+            error: ParseError::AbsoluteBracketFailed,
+            span: 3..4
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3097,7 +4530,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::VarExpectedName)
+        Err(ParseErrorSpan {
+            error: ParseError::VarExpectedName,
+            span: 0..1
+        })
     );
     assert_eq!(
         Tokens::rpn(
@@ -3111,7 +4547,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::VarExpectedName)
+        Err(ParseErrorSpan {
+            error: ParseError::VarExpectedName,
+            span: 0..1
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3125,7 +4564,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::MixedError)
+        Err(ParseErrorSpan {
+            error: ParseError::MixedError,
+            span: 4..5
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3139,7 +4581,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::MixedError)
+        Err(ParseErrorSpan {
+            error: ParseError::MixedError,
+            span: 4..5
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3153,7 +4598,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::ExtraInput)
+        Err(ParseErrorSpan {
+            error: ParseError::ExtraInput,
+            span: 8..9
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3167,7 +4615,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::ExtraInput)
+        Err(ParseErrorSpan {
+            error: ParseError::ExtraInput,
+            span: 7..8
+        })
     );
     assert_eq!(
         Tokens::rpn(
@@ -3181,7 +4632,10 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::UnknownToken("=-="))
+        Err(ParseErrorSpan {
+            error: ParseError::UnknownToken("=-="),
+            span: 0..3
+        })
     );
     assert_eq!(
         Tokens::infix(
@@ -3195,8 +4649,493 @@ fn test_err() {
             #[cfg(feature = "float_rand")]
             &mut rng()
         ),
-        Err(ParseError::UnknownToken("\\"))
+        Err(ParseErrorSpan {
+            error: ParseError::UnknownToken("\\"),
+            span: 0..1
+        })
+    );
+}
+#[test]
+fn test_err_render() {
+    let value = "max(1,2)+(4,5)";
+    let err = Tokens::infix(
+        value,
+        &mut Variables::default(),
+        &mut Functions::default(),
+        &[],
+        false,
+        true,
+        10,
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+    .unwrap_err();
+    assert_eq!(err.span, 11..12);
+    assert_eq!(
+        err.render(value),
+        "max(1,2)+(4,5)\n           ^\ncomma outside of function inputs"
+    );
+    let value = "1 2 + +";
+    let err = Tokens::rpn(
+        value,
+        &mut Variables::default(),
+        &mut Functions::default(),
+        &[],
+        false,
+        true,
+        10,
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+    .unwrap_err();
+    assert_eq!(err.span, 7..7);
+    assert_eq!(err.render(value), "1 2 + +\n       ^\nmissing input");
+    let mut funs = Functions::default();
+    let value = "let f(x)=x+(1,2)";
+    let err = Tokens::infix(
+        value,
+        &mut Variables::default(),
+        &mut funs,
+        &[],
+        false,
+        true,
+        10,
+        #[cfg(feature = "float_rand")]
+        &mut rng(),
+    )
+    .unwrap_err();
+    assert_eq!(err.span, 13..14);
+    assert!(funs.is_empty());
+}
+#[test]
+fn test_derivative_notation() {
+    let d = |n, integral, twice| {
+        let mut d = Derivative::from(n).unwrap();
+        if twice {
+            d.set_integral_twice_input()
+        } else if integral {
+            d.set_integral()
+        }
+        d
+    };
+    assert_correct(
+        "sin'(0)",
+        "0 sin'",
+        vec![num(0), Token::Function(Function::Sin, d(1, false, false))],
+        res(1),
+    );
+    assert_approx_correct(
+        "exp''(1)",
+        "1 exp''",
+        vec![num(1), Token::Function(Function::Exp, d(2, false, false))],
+        res(Constant::E),
+    );
+    for (s, r) in [
+        ("sin''(0)", res(0)),
+        ("exp''(1)", res(Constant::E)),
+        ("sin'''(0)", res(-1)),
+        ("ln''(2)", res(-0.25)),
+    ] {
+        assert!(
+            (eval(s) - r).abs() < Float::from(2.0).pow(Float::from(-40)),
+            "{s}"
+        );
+    }
+    assert_approx_correct(
+        "sin`(0,pi)",
+        "0 pi sin2`",
+        vec![
+            num(0),
+            Token::CustomVar(0),
+            Token::Function(Function::Sin, d(1, true, true)),
+        ],
+        res(2),
+    );
+    assert_approx_correct(
+        "cos`(1)",
+        "1 cos`",
+        vec![num(1), Token::Function(Function::Cos, d(1, true, false))],
+        res(1).sin(),
+    );
+    let mut funs = Functions::default();
+    let mut vars = Variables::default();
+    for s in ["let f(x)=x^3", "let g(x)=root(2,x)", "let h(x)=x*~x"] {
+        assert!(
+            Tokens::infix(
+                s,
+                &mut vars,
+                &mut funs,
+                &[],
+                false,
+                true,
+                10,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .unwrap()
+            .is_var()
+        );
+    }
+    for (s, n) in [
+        ("f'(2)", 12.0),
+        ("f''(2)", 12.0),
+        ("f`(2)", 4.0),
+        ("g'(2)", -0.2450645358671368),
+        ("h'(3)", -6.0),
+        ("h''(3)", -2.0),
+    ] {
+        let n = Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap()
+        .tokens()
+        .compute(
+            &[],
+            &funs,
+            &vars,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap()
+            - Float::from(n);
+        assert!(n.abs() < Float::from(2.0).pow(Float::from(-8)), "{s}");
+    }
+}
+#[test]
+fn test_diff() {
+    fn parse(
+        s: &'static str,
+        vars: &mut Variables,
+        funs: &mut Functions,
+    ) -> Result<Number, ParseError<'static>> {
+        match Tokens::infix(
+            s,
+            vars,
+            funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        ) {
+            Ok(ParseReturn::Tokens(tokens)) => Ok(tokens
+                .compute(
+                    &[],
+                    funs,
+                    vars,
+                    #[cfg(feature = "float_rand")]
+                    &mut rng(),
+                )
+                .unwrap()),
+            Ok(_) => Ok(Number::default()),
+            Err(e) => Err(e.error),
+        }
+    }
+    let mut funs = Functions::default();
+    let mut vars = Variables::default();
+    for s in [
+        "let f(x)=x^3",
+        "let g(x)=diff(x^3,x)",
+        "let h(x)=diff(sin(x)*x,x)",
+        "let k(x,y)=diff(x*y+y^2,y)",
+        "let p(x)=diff(f(2x),x)",
+    ] {
+        parse(s, &mut vars, &mut funs).unwrap();
+    }
+    for (i, s) in [
+        (1, "3*n^2"),
+        (2, "n*cos(n)+sin(n)"),
+        (3, "n+2*o"),
+        (4, "24*n^2"),
+    ] {
+        assert_eq!(funs[i].tokens.get_infix(&vars, &funs, &[]).to_string(), s);
+    }
+    assert_eq!(parse("g(2)+p(1)", &mut vars, &mut funs), Ok(res(36)));
+    assert_eq!(
+        parse("diff(x^2,x)=4", &mut vars, &mut funs),
+        Ok(roots(res(2), vec![res(2)]))
+    );
+    for (s, g, r) in [
+        ("diff(n^3,n)", &[][..], "3*n^2"),
+        ("diff(x^2,x)", &["x"][..], "2*x"),
+        ("diff(x*y,y)+1", &["x"][..], "x+1"),
+    ] {
+        let Ok(ParseReturn::Expr(tokens)) = Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            g,
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        ) else {
+            panic!("{s}")
+        };
+        assert_eq!(tokens.get_infix(&vars, &funs, g).to_string(), r);
+    }
+    assert_eq!(
+        parse("diff(x,x+1)", &mut vars, &mut funs),
+        Err(ParseError::InnerVarError)
+    );
+    assert_eq!(
+        parse("let q(x)=diff(gamma(x),x)", &mut vars, &mut funs),
+        Err(ParseError::DiffError)
+    );
+}
+#[test]
+fn test_simplify_fold() {
+    let mut funs = Functions::default();
+    let mut vars = Variables::default();
+    for s in [
+        "let f(x)=sum(1,100,n,n^2)+x",
+        "let g(x)=if(1,x,2)",
+        "let h(x)=if(x,2,3)+if(0,x,2*3)",
+        "let k(x)=sum(1,x,n,n)",
+        "let p(x)=sum(1,3,n,sum(1,n,m,m*x))",
+        "let q(x)=sum(1,3,n,x*sum(1,2,m,m))",
+        "let r(x)=x<2<3",
+    ] {
+        Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        funs[0].tokens,
+        Tokens(vec![num(338350), Token::InnerVar(0), Function::Add.into()])
+    );
+    assert_eq!(funs[1].tokens, Tokens(vec![Token::InnerVar(0)]));
+    assert!(funs[2].tokens.ends_with(&[num(6), Function::Add.into()]));
+    assert!(matches!(funs[3].tokens[2], Token::Skip(1)));
+    assert!(matches!(funs[4].tokens[2], Token::Skip(_)));
+    assert_eq!(
+        funs[5].tokens,
+        Tokens(vec![
+            num(1),
+            num(3),
+            Token::Skip(3),
+            Token::InnerVar(0),
+            num(3),
+            Function::Mul.into(),
+            Function::Sum.into(),
+        ])
+    );
+    assert_eq!(
+        funs[6].tokens,
+        Tokens(vec![Token::InnerVar(0), num(2), Function::Less.into()])
+    );
+    for (s, r) in [
+        ("p(2)", 20),
+        ("q(2)", 18),
+        ("h(0)+h(1)", 17),
+        ("r(1)+r(2)", 1),
+        ("1<2<3", 1),
+        ("3>2>4", 0),
+    ] {
+        let tokens = Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap()
+        .tokens();
+        assert_eq!(tokens, Tokens(vec![num(r)]), "{s}");
+    }
+    for s in ["let u(x)=fold(1,5,s,n,s*n)+x", "let v(x)=set(b,3)+x"] {
+        assert_eq!(
+            Tokens::infix(
+                s,
+                &mut vars,
+                &mut funs,
+                &[],
+                false,
+                true,
+                10,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            )
+            .map_err(|e| e.error),
+            Err(ParseError::InnerVarError),
+            "{s}"
+        );
+    }
+    assert_eq!(funs.len(), 7);
+}
+#[test]
+fn test_simplify_algebraic() {
+    let mut funs = Functions::default();
+    let mut vars = Variables::default();
+    let mut simplify = |s: &str| {
+        Tokens::infix(
+            s,
+            &mut vars,
+            &mut funs,
+            &["x", "y"],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+        .unwrap()
+        .tokens_any()[..]
+            .simplify_algebraic(&funs)
+            .get_infix(&vars, &funs, &["x", "y"])
+            .to_string()
+    };
+    for (s, r) in [
+        ("x+x", "2*x"),
+        ("x*1", "x"),
+        ("x^0", "1"),
+        ("x-x", "0"),
+        ("x*x^2/x", "x^2"),
+        ("(x+1)^2", "x^2+2*x+1"),
+        ("(x-1)*(x+1)-x^2", "-1"),
+        ("(x^2-1)/(x-1)", "x+1"),
+        #[cfg(not(feature = "rational"))]
+        ("x/(2x+2)", "(0.5*x)/(x+1)"),
+        #[cfg(feature = "rational")]
+        ("x/(2x+2)", "((1/2)*x)/(x+1)"),
+        ("2*x-3*x", "-x"),
+        ("x^2-3x+x", "x^2-2*x"),
+        ("sin(x)+sin(x)", "2*sin(x)"),
+        ("sin(x)-sin(x)+y*1", "y"),
+        ("y*(x+x)", "2*y*x"),
+        ("2x+3x-y+y", "5*x"),
+        ("x+y+x", "2*x+y"),
+        ("x*y-y*x", "0"),
+        ("(x+y)^2-x^2", "2*x*y+y^2"),
+        ("(x+y)*(x-y)+y^2", "x^2"),
+        ("x*y*x/(x*y)", "x"),
+        ("(x*y+y)/y", "x+1"),
+        ("sin(x)*y+y*sin(x)", "2*sin(x)*y"),
+        ("x/(x+1)+y-y", "x/(x+1)"),
+        #[cfg(not(feature = "rational"))]
+        ("x^0.5*1", "x^0.5"),
+        #[cfg(feature = "rational")]
+        ("x^0.5*1", "x^(1/2)"),
+        ("sum(1,3,n,n*1)+x*0", "6"),
+        ("sum(1,3,n,n*y)*1", "sum(1,3,n*y)"),
+        ("sin`(0,x)", "sin`(0,x)"),
+        ("sin`(x*1,x+x)", "sin`(x,2*x)"),
+        ("sin`(x)*1", "sin`(x)"),
+    ] {
+        assert_eq!(simplify(s), r, "{s}");
+    }
+}
+#[test]
+fn test_eval_err() {
+    fn parse<'a>(
+        s: &'a str,
+        vars: &mut Variables,
+        funs: &mut Functions,
+    ) -> Result<ParseReturn, ParseErrorSpan<'a>> {
+        Tokens::infix(
+            s,
+            vars,
+            funs,
+            &[],
+            false,
+            true,
+            10,
+            #[cfg(feature = "float_rand")]
+            &mut rng(),
+        )
+    }
+    let mut vars = Variables::default();
+    let mut funs = Functions::default();
+    assert!(
+        parse("let f(x,y)=x*y", &mut vars, &mut funs)
+            .unwrap()
+            .is_var()
     );
+    let value = "let a=exprs'(1)";
+    assert_eq!(
+        parse(value, &mut vars, &mut funs),
+        Err(ParseErrorSpan {
+            error: ParseError::Eval(EvalError::DerivativeUnsupported),
+            span: 0..value.len()
+        })
+    );
+    for (tokens, e) in [
+        (
+            Tokens(vec![
+                Token::Skip(1),
+                num(1),
+                Token::Function(
+                    Function::Exprs(NonZeroU8::new(1).unwrap()),
+                    Derivative::from(1).unwrap(),
+                ),
+            ]),
+            EvalError::DerivativeUnsupported,
+        ),
+        (
+            Tokens(vec![
+                num(1),
+                num(2),
+                Token::Function(
+                    Function::Max(NonZeroU8::new(2).unwrap()),
+                    Derivative::from(1).unwrap(),
+                ),
+            ]),
+            EvalError::MultiInputDerivative,
+        ),
+        (
+            Tokens(vec![
+                num(1),
+                num(2),
+                Token::CustomFun(0, Derivative::from(1).unwrap()),
+            ]),
+            EvalError::MultiInputDerivative,
+        ),
+        #[cfg(feature = "list")]
+        (
+            Tokens(vec![
+                num(1),
+                Token::Skip(1),
+                Token::InnerVar(0),
+                Token::Function(Function::Map, Derivative::from(1).unwrap()),
+            ]),
+            EvalError::DerivativeUnsupported,
+        ),
+    ] {
+        assert_eq!(
+            tokens.compute(
+                &[],
+                &funs,
+                &vars,
+                #[cfg(feature = "float_rand")]
+                &mut rng(),
+            ),
+            Err(e),
+            "{tokens}"
+        );
+    }
 }
 #[test]
 fn function_exists() {
@@ -3212,6 +5151,78 @@ fn function_exists() {
         Function::Negate,
         Function::Factorial,
         Function::SubFactorial,
+        Function::Gcd,
+        Function::Lcm,
+        Function::IsPrime,
+        #[cfg(feature = "list")]
+        Function::Factor,
+        Function::NextPrime,
+        Function::ModPow,
+        Function::ModInv,
+        Function::Totient,
+        #[cfg(feature = "list")]
+        Function::Divisors,
+        Function::Sigma,
+        Function::Binomial,
+        Function::Perm,
+        Function::Multinomial(NonZeroU8::new(1).unwrap()),
+        Function::StirlingFirst,
+        Function::StirlingSecond,
+        Function::Bell,
+        Function::Catalan,
+        Function::Partitions,
+        #[cfg(feature = "list")]
+        Function::Len,
+        #[cfg(feature = "list")]
+        Function::Mean,
+        #[cfg(feature = "list")]
+        Function::Median,
+        #[cfg(feature = "list")]
+        Function::Mode,
+        #[cfg(feature = "list")]
+        Function::Var,
+        #[cfg(feature = "list")]
+        Function::SampleVar,
+        #[cfg(feature = "list")]
+        Function::Stddev,
+        #[cfg(feature = "list")]
+        Function::SampleStddev,
+        #[cfg(feature = "list")]
+        Function::Percentile,
+        #[cfg(feature = "list")]
+        Function::Covariance,
+        #[cfg(feature = "list")]
+        Function::Correlation,
+        #[cfg(feature = "list")]
+        Function::Linreg,
+        #[cfg(feature = "list")]
+        Function::Range(ModifyInputs::Two),
+        #[cfg(feature = "list")]
+        Function::Sort,
+        #[cfg(feature = "list")]
+        Function::Reverse,
+        #[cfg(feature = "list")]
+        Function::Concat,
+        #[cfg(feature = "list")]
+        Function::Zip,
+        #[cfg(feature = "list")]
+        Function::Slice(ModifyInputs::Two),
+        #[cfg(feature = "list")]
+        Function::Map,
+        #[cfg(feature = "list")]
+        Function::Filter,
+        Function::Distribution(Distribution::Normal, DistributionFunction::Density),
+        Function::Distribution(Distribution::StudentT, DistributionFunction::Cdf),
+        Function::Distribution(Distribution::ChiSquared, DistributionFunction::Inverse),
+        Function::Distribution(Distribution::Binomial, DistributionFunction::Density),
+        Function::Distribution(Distribution::Poisson, DistributionFunction::Cdf),
+        Function::Distribution(Distribution::Exponential, DistributionFunction::Inverse),
+        Function::Distribution(Distribution::Uniform, DistributionFunction::Density),
+        Function::Distribution(Distribution::Gamma, DistributionFunction::Cdf),
+        #[cfg(feature = "float_rand")]
+        Function::RandDistribution(Distribution::Normal),
+        #[cfg(feature = "float_rand")]
+        Function::RandDistribution(Distribution::Binomial),
         Function::Equal,
         Function::NotEqual,
         Function::Greater,
@@ -3236,8 +5247,8 @@ fn function_exists() {
         Function::Exp,
         Function::Atan(AtanInputs::One),
         Function::Atan(AtanInputs::Two),
-        Function::Max,
-        Function::Min,
+        Function::Max(NonZeroU8::new(2).unwrap()),
+        Function::Min(NonZeroU8::new(2).unwrap()),
         Function::Quadratic,
         #[cfg(feature = "complex")]
         Function::Cubic,
@@ -3279,10 +5290,23 @@ fn function_exists() {
         Function::While(ModifyInputs::Three),
         Function::Exprs(NonZeroU8::new(1).unwrap()),
         Function::Solve,
+        Function::Diff,
         Function::NumericalDerivative,
         Function::NumericalDifferential,
+        #[cfg(feature = "list")]
+        Function::NumericalTrajectory,
         Function::NumericalIntegral,
+        Function::IntegralError,
         Function::NumericalSolve,
+        Function::NumericalBracket,
+        #[cfg(feature = "list")]
+        Function::NumericalRoots,
+        #[cfg(feature = "list")]
+        Function::SolveRange,
+        #[cfg(feature = "vector")]
+        Function::Vector(NonZeroU8::new(1).unwrap()),
+        #[cfg(any(feature = "vector", feature = "list"))]
+        Function::Index(ModifyInputs::Two),
     ] {
         assert_eq!(
             std::mem::discriminant(&Function::try_from(f.to_string().as_str()).unwrap()),
@@ -0,0 +1,555 @@
+use crate::float::F;
+use crate::integer::I;
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+pub mod consts {
+    use super::F;
+    pub const PI: F = F::PI;
+    pub const TAU: F = F::TAU;
+    pub const E: F = F::E;
+    pub const FRAC_PI_2: F = F::FRAC_PI_2;
+    pub const FRAC_2_SQRT_PI: F = F::FRAC_2_SQRT_PI;
+}
+const LN_EPSILON: usize = 720;
+const HALVINGS: usize = 16;
+static TOLERANCE: LazyLock<F> = LazyLock::new(|| F::TEN.powi(-154));
+pub trait DecimalFunctions: Sized {
+    fn fract(self) -> Self;
+    fn powf(self, rhs: Self) -> Self;
+    fn gamma(self) -> Self;
+    fn erf(self) -> Self;
+    fn erfc(self) -> Self;
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+pub trait ToDecimal {
+    fn to_decimal(self) -> F;
+}
+macro_rules! to_decimal_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ToDecimal for $ty {
+                fn to_decimal(self) -> F {
+                    F::try_from(self).unwrap_or(F::NAN)
+                }
+            }
+        )*
+    };
+}
+to_decimal_int!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+macro_rules! to_decimal_float {
+    ($($ty:ty),*) => {
+        $(
+            impl ToDecimal for $ty {
+                fn to_decimal(self) -> F {
+                    if self.is_finite() {
+                        float_base::parse_radix(&(self as f64).to_string(), 10).unwrap_or(F::NAN)
+                    } else {
+                        F::from(self as f64)
+                    }
+                }
+            }
+        )*
+    };
+}
+to_decimal_float!(f16, f32, f64, f128);
+fn converged(next: F, prev: F) -> bool {
+    (next - prev).abs() <= next.abs() * *TOLERANCE
+}
+pub fn mul(a: F, b: F) -> F {
+    if !a.is_finite() || !b.is_finite() {
+        return a * b;
+    }
+    let prod = (a.resize::<32>() * b.resize::<32>()).resize::<16>();
+    if !prod.is_nan() {
+        prod
+    } else if a.is_sign_negative() == b.is_sign_negative() {
+        F::INFINITY
+    } else {
+        F::NEG_INFINITY
+    }
+}
+pub fn powi(a: F, n: i32) -> F {
+    let (mut base, mut exp, mut acc) = (a, n.unsigned_abs(), F::ONE);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul(acc, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    if n < 0 { div(F::ONE, acc) } else { acc }
+}
+pub fn div(a: F, b: F) -> F {
+    if !b.is_zero() {
+        a / b
+    } else if a.is_zero() || a.is_nan() {
+        F::NAN
+    } else if a.is_sign_negative() == b.is_sign_negative() {
+        F::INFINITY
+    } else {
+        F::NEG_INFINITY
+    }
+}
+pub fn rem(a: F, b: F) -> F {
+    if b.is_zero() || a.is_infinite() || a.is_nan() || b.is_nan() {
+        F::NAN
+    } else if b.is_infinite() {
+        a
+    } else {
+        a % b
+    }
+}
+pub fn recip(a: F) -> F {
+    div(F::ONE, a)
+}
+pub fn round(a: F) -> F {
+    a.round(0)
+}
+fn exp_m1_small(a: F) -> F {
+    let x = a / F::from(1usize << HALVINGS);
+    let mut term = x;
+    let mut sum = x;
+    for k in 2usize.. {
+        term = mul(term, x) / F::from(k);
+        if term.abs() <= sum.abs() * F::EPSILON {
+            break;
+        }
+        sum += term;
+    }
+    for _ in 0..HALVINGS {
+        sum = mul(sum, sum + F::TWO);
+    }
+    sum
+}
+pub fn exp_m1(a: F) -> F {
+    if a.abs() <= F::ONE {
+        exp_m1_small(a)
+    } else {
+        exp(a) - F::ONE
+    }
+}
+pub fn exp(a: F) -> F {
+    if a.is_nan() {
+        return a;
+    }
+    let n = a.round(0);
+    if n.abs() > F::from(LN_EPSILON * 128) {
+        return if a.is_sign_negative() {
+            F::ZERO
+        } else {
+            F::INFINITY
+        };
+    }
+    mul(powi(F::E, to_isize(n) as i32), exp_m1_small(a - n) + F::ONE)
+}
+fn halley_ln(u: F, guess: f64) -> F {
+    let mut y = guess.to_decimal();
+    for _ in 0..16 {
+        let e = exp_m1(y);
+        let next = y + F::TWO * (u - e) / (F::TWO + u + e);
+        if converged(next, y) {
+            return next;
+        }
+        y = next;
+    }
+    y
+}
+pub fn ln(a: F) -> F {
+    if a.is_nan() || a == F::INFINITY {
+        return a;
+    } else if a.is_zero() {
+        return F::NEG_INFINITY;
+    } else if a.is_sign_negative() {
+        return F::NAN;
+    }
+    let step = F::TEN.powi(300);
+    let (mut a, mut k) = (a, 0);
+    while a > step {
+        a /= step;
+        k += 300;
+    }
+    while a < F::ONE / step {
+        a *= step;
+        k -= 300;
+    }
+    halley_ln(a - F::ONE, a.to_f64().ln()) + mul(F::LN_10, F::from(k))
+}
+pub fn ln_1p(a: F) -> F {
+    let guess = a.to_f64();
+    if a.is_nan() || a <= -F::ONE || guess.abs() > 1e300 {
+        ln(a + F::ONE)
+    } else {
+        halley_ln(a, guess.ln_1p())
+    }
+}
+fn root(a: F, n: i32) -> F {
+    if a.is_zero() || a.is_infinite() || a.is_nan() {
+        return a;
+    }
+    let step = F::TEN.powi(100);
+    let outer = step.powi(n);
+    let (mut a, mut scale) = (a, F::ONE);
+    while a > outer {
+        a /= outer;
+        scale *= step;
+    }
+    while a < F::ONE / outer {
+        a *= outer;
+        scale /= step;
+    }
+    let (lower, count) = (F::from(n - 1), F::from(n));
+    let mut y = a.to_f64().powf((n as f64).recip()).to_decimal();
+    for _ in 0..16 {
+        let next = (lower * y + a / powi(y, n - 1)) / count;
+        if converged(next, y) {
+            y = next;
+            break;
+        }
+        y = next;
+    }
+    y * scale
+}
+pub fn sqrt(a: F) -> F {
+    if a.is_sign_negative() && !a.is_zero() {
+        F::NAN
+    } else {
+        root(a, 2)
+    }
+}
+pub fn cbrt(a: F) -> F {
+    if a.is_sign_negative() {
+        -root(-a, 3)
+    } else {
+        root(a, 3)
+    }
+}
+pub fn hypot(a: F, b: F) -> F {
+    let (a, b) = (a.abs(), b.abs());
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    if min.is_zero() || max.is_infinite() || max.is_nan() || min.is_nan() {
+        max + min
+    } else {
+        let r = min / max;
+        mul(max, sqrt(F::ONE + mul(r, r)))
+    }
+}
+fn sin_cos_small(a: F) -> (F, F) {
+    let x = a / F::from(1usize << HALVINGS);
+    let sq = mul(x, x);
+    let mut term = x;
+    let (mut sin, mut versin) = (x, F::ZERO);
+    for k in 2usize.. {
+        term = mul(term, x) / F::from(k);
+        if term.abs() <= sq * F::EPSILON {
+            break;
+        }
+        match k % 4 {
+            0 => versin -= term,
+            1 => sin += term,
+            2 => versin += term,
+            _ => sin -= term,
+        }
+    }
+    for _ in 0..HALVINGS {
+        (sin, versin) = (F::TWO * mul(sin, F::ONE - versin), F::TWO * mul(sin, sin));
+    }
+    (sin, F::ONE - versin)
+}
+pub fn sin_cos(a: F) -> (F, F) {
+    if !a.is_finite() {
+        return (F::NAN, F::NAN);
+    }
+    let n = (a / F::FRAC_PI_2).round(0);
+    let (sin, cos) = sin_cos_small(a - mul(n, F::FRAC_PI_2));
+    match to_isize(n % F::from(4)).rem_euclid(4) {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+pub fn sin(a: F) -> F {
+    sin_cos(a).0
+}
+pub fn cos(a: F) -> F {
+    sin_cos(a).1
+}
+pub fn tan(a: F) -> F {
+    let (sin, cos) = sin_cos(a);
+    div(sin, cos)
+}
+pub fn atan(a: F) -> F {
+    if a.is_nan() {
+        a
+    } else if a.abs() > F::ONE {
+        let atan = atan(div(F::ONE, a));
+        if a.is_sign_negative() {
+            -F::FRAC_PI_2 - atan
+        } else {
+            F::FRAC_PI_2 - atan
+        }
+    } else {
+        let mut y = a.to_f64().atan().to_decimal();
+        for _ in 0..16 {
+            let (sin, cos) = sin_cos_small(y);
+            let next = y - mul(cos, sin - mul(a, cos));
+            if converged(next, y) {
+                return next;
+            }
+            y = next;
+        }
+        y
+    }
+}
+pub fn atan2(a: F, b: F) -> F {
+    if a.is_nan() || b.is_nan() {
+        F::NAN
+    } else if b.is_zero() {
+        if a.is_zero() {
+            if b.is_sign_positive() {
+                a
+            } else if a.is_sign_negative() {
+                -F::PI
+            } else {
+                F::PI
+            }
+        } else if a.is_sign_negative() {
+            -F::FRAC_PI_2
+        } else {
+            F::FRAC_PI_2
+        }
+    } else if b.is_sign_positive() {
+        atan(div(a, b))
+    } else if a.is_sign_negative() {
+        atan(div(a, b)) - F::PI
+    } else {
+        atan(div(a, b)) + F::PI
+    }
+}
+pub fn asin(a: F) -> F {
+    if a.abs() > F::ONE {
+        F::NAN
+    } else {
+        atan2(a, sqrt(mul(F::ONE - a, F::ONE + a)))
+    }
+}
+pub fn acos(a: F) -> F {
+    if a.abs() > F::ONE {
+        F::NAN
+    } else {
+        atan2(sqrt(mul(F::ONE - a, F::ONE + a)), a)
+    }
+}
+pub fn sinh(a: F) -> F {
+    if a.abs() <= F::ONE {
+        let s = exp_m1_small(a);
+        mul(s, s + F::TWO) / (F::TWO * (s + F::ONE))
+    } else {
+        let e = exp(a);
+        (e - div(F::ONE, e)) / F::TWO
+    }
+}
+pub fn cosh(a: F) -> F {
+    let e = exp(a.abs());
+    (e + div(F::ONE, e)) / F::TWO
+}
+pub fn tanh(a: F) -> F {
+    if a.abs() > F::from(LN_EPSILON) {
+        if a.is_sign_negative() {
+            -F::ONE
+        } else {
+            F::ONE
+        }
+    } else if a.abs() <= F::HALF {
+        let s = exp_m1_small(F::TWO * a);
+        s / (s + F::TWO)
+    } else {
+        let e = exp(F::TWO * a);
+        (e - F::ONE) / (e + F::ONE)
+    }
+}
+pub fn asinh(a: F) -> F {
+    let x = a.abs();
+    let asinh = if !x.is_finite() {
+        x
+    } else if x > F::TEN.powi(160) {
+        ln(x) + F::LN_2
+    } else {
+        let sq = mul(x, x);
+        ln_1p(x + sq / (F::ONE + sqrt(sq + F::ONE)))
+    };
+    if a.is_sign_negative() { -asinh } else { asinh }
+}
+pub fn acosh(a: F) -> F {
+    if a.is_nan() || a < F::ONE {
+        F::NAN
+    } else if a > F::TEN.powi(160) {
+        ln(a) + F::LN_2
+    } else {
+        let x = a - F::ONE;
+        ln_1p(x + sqrt(mul(x, a + F::ONE)))
+    }
+}
+pub fn atanh(a: F) -> F {
+    let x = a.abs();
+    let atanh = if x.is_nan() || x > F::ONE {
+        F::NAN
+    } else if x == F::ONE {
+        F::INFINITY
+    } else {
+        ln_1p(F::TWO * x / (F::ONE - x)) / F::TWO
+    };
+    if a.is_sign_negative() { -atanh } else { atanh }
+}
+pub fn to_isize(a: F) -> isize {
+    if a.is_infinite() {
+        if a.is_sign_negative() {
+            isize::MIN
+        } else {
+            isize::MAX
+        }
+    } else if a.is_nan() {
+        0
+    } else {
+        isize::try_from(a.trunc()).unwrap_or(if a.is_sign_negative() {
+            isize::MIN
+        } else {
+            isize::MAX
+        })
+    }
+}
+pub fn to_usize(a: F) -> usize {
+    if a.is_sign_negative() || (a.is_nan() && !a.is_infinite()) {
+        0
+    } else if a.is_infinite() {
+        usize::MAX
+    } else {
+        usize::try_from(a.trunc()).unwrap_or(usize::MAX)
+    }
+}
+pub fn to_integer(a: F) -> I {
+    let digits = a.digits().to_string().parse::<I>().unwrap();
+    let scale = a.fractional_digits_count();
+    let n = if scale > 0 {
+        digits / I::from(10).pow(scale as u32)
+    } else {
+        digits * I::from(10).pow(-scale as u32)
+    };
+    if a.is_sign_negative() { -n } else { n }
+}
+fn lower_gamma(x: F) -> F {
+    let n = F::from(LN_EPSILON);
+    let mut term = div(F::ONE, x);
+    let mut sum = term;
+    let mut k = F::ONE;
+    loop {
+        term = mul(term, n) / (x + k);
+        sum += term;
+        if k > n && term < sum * F::EPSILON {
+            break;
+        }
+        k += F::ONE;
+    }
+    mul(sum, exp(mul(x, ln(n)) - n))
+}
+impl DecimalFunctions for F {
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+    fn powf(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            F::NAN
+        } else if self == F::ONE || rhs.is_zero() {
+            F::ONE
+        } else if self.is_zero() && rhs.is_sign_negative() {
+            F::INFINITY
+        } else if self.is_zero() {
+            F::ZERO
+        } else if !rhs.fract().is_zero() {
+            if self.is_sign_negative() {
+                F::NAN
+            } else {
+                exp(mul(rhs, ln(self)))
+            }
+        } else if let Ok(n) = i32::try_from(rhs) {
+            powi(self, n)
+        } else if self.is_sign_negative() && !(rhs / F::TWO).fract().is_zero() {
+            -exp(mul(rhs, ln(-self)))
+        } else {
+            exp(mul(rhs, ln(self.abs())))
+        }
+    }
+    fn gamma(self) -> Self {
+        if self.is_nan() || self == F::NEG_INFINITY {
+            F::NAN
+        } else if self.is_infinite() {
+            self
+        } else if self.is_zero() {
+            div(F::ONE, self)
+        } else if self.is_sign_negative() && self.fract().is_zero() {
+            F::NAN
+        } else if self < F::HALF {
+            F::PI / mul(sin(mul(F::PI, self)), (F::ONE - self).gamma())
+        } else if self > F::from(LN_EPSILON * 16) {
+            F::INFINITY
+        } else {
+            let mut x = self;
+            let mut prod = F::ONE;
+            while x > F::TWO {
+                x -= F::ONE;
+                prod = mul(prod, x);
+            }
+            if x == F::ONE || x == F::TWO {
+                prod
+            } else {
+                mul(prod, lower_gamma(x))
+            }
+        }
+    }
+    fn erf(self) -> Self {
+        if self.is_nan() {
+            return self;
+        }
+        if self.is_sign_negative() {
+            return -(-self).erf();
+        }
+        if self > F::from(28) {
+            return F::ONE;
+        }
+        let sq = mul(self, self);
+        let mut term = self;
+        let mut sum = term;
+        let mut k = F::ONE;
+        while term > sum * F::EPSILON {
+            term = mul(term, F::TWO * sq) / (F::TWO * k + F::ONE);
+            sum += term;
+            k += F::ONE;
+        }
+        mul(mul(sum, exp(-sq)), F::FRAC_2_SQRT_PI)
+    }
+    fn erfc(self) -> Self {
+        if self.is_nan() {
+            return self;
+        }
+        if self < F::TEN {
+            return F::ONE - self.erf();
+        }
+        let half = F::from(LN_EPSILON / 2) / self;
+        let terms = to_usize(half * half / F::TWO) + 16;
+        let mut run = self;
+        for i in (1..=terms).rev() {
+            run = self + F::from(i) / (F::TWO * run);
+        }
+        exp(-mul(self, self)) / mul(run, sqrt(F::PI))
+    }
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.cmp(other),
+        }
+    }
+}
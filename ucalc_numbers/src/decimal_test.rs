@@ -0,0 +1,66 @@
+use crate::float::{Complex, Float};
+use crate::integer::{Integer, UInteger};
+use crate::{ComplexFunctions, Constant, FloatFunctions, FloatTrait, Pow, RealTrait};
+fn assert_close(a: Float, b: Float) {
+    assert!(
+        (a.clone() - b.clone()).abs() < Float::from(10).pow(Float::from(-295)),
+        "{a} {b}"
+    );
+}
+#[test]
+fn test_precision() {
+    assert!(Float::from(Constant::Pi).to_string().starts_with(
+        "3.14159265358979323846264338327950288419716939937510582097494459230781640628620899"
+    ));
+    assert_eq!(
+        Float::from(2).pow(Float::from(200)).to_string(),
+        "1606938044258990275541962092341162602522202993782792835301376"
+    );
+    assert_eq!(Float::from(0.1).to_string(), "0.1");
+    assert_eq!(Float::parse_radix("ff.8", 16).unwrap(), Float::from(255.5));
+    assert_eq!(Float::from(255.5).to_string_radix(16).to_string(), "ff.8");
+    assert_eq!(Float::from(-0.25).to_string_radix(2).to_string(), "-0.01");
+}
+#[test]
+fn test_functions() {
+    let pi = Float::from(Constant::Pi);
+    let third = Float::from(1) / Float::from(3);
+    assert_close(Float::from(1).atan() * Float::from(4), pi.clone());
+    assert_close(Float::from(2).sqrt().pow(Float::from(2)), Float::from(2));
+    assert_close(Float::from(10).ln().exp(), Float::from(10));
+    assert_close(
+        third.clone().sin().pow(Float::from(2)) + third.clone().cos().pow(Float::from(2)),
+        Float::from(1),
+    );
+    assert_close(Float::from(0.5).gamma().pow(Float::from(2)), pi.clone());
+    assert_close(third.clone().asinh().sinh(), third.clone());
+    assert_close(third.clone().atanh().tanh(), third.clone());
+    assert_close(third.clone().acos().cos(), third.clone());
+    assert_close(third.clone().erf() + third.clone().erfc(), Float::from(1));
+    assert_close(Float::from(27).cbrt(), Float::from(3));
+    assert_close((pi * Float::from(100000)).sin(), Float::from(0));
+    assert_eq!(
+        Float::from(1) / Float::from(0),
+        Float::from(Constant::Infinity)
+    );
+    assert!((Float::from(0) / Float::from(0)).is_nan());
+    assert_eq!(Float::from(-100000).exp(), Float::from(0));
+}
+#[test]
+fn test_complex() {
+    assert_eq!(Complex::from(-4).sqrt(), Complex::from((0, 2)));
+    assert_eq!(Complex::from((3, 4)).abs(), Float::from(5));
+    assert_eq!(Complex::from((1, 1)).to_string(), "1+1i");
+    assert_close(
+        Complex::from((0, 1)).arg(),
+        Float::from(Constant::Pi) / Float::from(2),
+    );
+}
+#[test]
+fn test_integer() {
+    let n = UInteger::from(100).factorial().unwrap().0;
+    assert_eq!(Float::from(n.clone()).to_integer(), Some(Integer(n.into())));
+    assert_eq!(Float::from(-1200).to_integer(), Some(Integer::from(-1200)));
+    assert_eq!(Float::from(0.5).to_integer(), None);
+    assert_eq!(Float::from(Constant::Infinity).to_integer(), None);
+}
@@ -0,0 +1,37 @@
+use crate::float::F;
+use std::cell::Cell;
+thread_local! {
+    static PRECISION: Cell<u32> = const { Cell::new(64) };
+}
+pub fn precision() -> u32 {
+    PRECISION.get()
+}
+pub fn set_precision(prec: u32) -> bool {
+    PRECISION.set(match prec {
+        1..=16 => 16,
+        17..=32 => 32,
+        33..=64 => 64,
+        _ => return false,
+    });
+    true
+}
+pub(crate) trait Narrow {
+    fn narrow(self) -> Self;
+}
+impl Narrow for F {
+    fn narrow(self) -> Self {
+        match precision() {
+            16 => self as f16 as F,
+            32 => self as f32 as F,
+            _ => self,
+        }
+    }
+}
+impl Narrow for (F, F) {
+    fn narrow(self) -> Self {
+        (self.0.narrow(), self.1.narrow())
+    }
+}
+pub(crate) fn narrow<T: Narrow>(value: T) -> T {
+    value.narrow()
+}
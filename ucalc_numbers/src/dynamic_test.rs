@@ -0,0 +1,49 @@
+use crate::dynamic::{precision, set_precision};
+use crate::float::{Complex, Float};
+use crate::{Constant, FloatFunctions, FloatTrait, Pow};
+fn show(value: Float) -> String {
+    value.to_string_radix(10).to_string()
+}
+#[test]
+fn test_precision() {
+    assert_eq!(precision(), 64);
+    assert_eq!(
+        show(Float::from(0.1) + Float::from(0.2)),
+        "0.30000000000000004"
+    );
+    set_precision(32);
+    assert_eq!(show(Float::from(0.1) + Float::from(0.2)), "0.3");
+    assert_eq!(
+        show(Float::from(16777216) + Float::from(1)),
+        show(Float::from(16777216))
+    );
+    assert_eq!(Float::parse_radix("0.1", 10).unwrap().0, 0.1f32 as f64);
+    assert_eq!(Float::from(Constant::Pi).0, std::f32::consts::PI as f64);
+    assert_eq!(Float::from(2).sqrt().0, 2f32.sqrt() as f64);
+    assert_eq!(
+        Float::from(10).pow(Float::from(39)),
+        Float::from(Constant::Infinity)
+    );
+    set_precision(24);
+    assert_eq!(precision(), 32);
+    set_precision(16);
+    assert_eq!(show(Float::from(1) / Float::from(3)), "0.3333");
+    assert_eq!(Float::from(2048) + Float::from(1), Float::from(2048));
+    assert_eq!(Float::from(65520), Float::from(Constant::Infinity));
+    assert_eq!(Float::parse_radix("0.1", 2).unwrap().0, 0.5);
+    assert!(!set_precision(128));
+    assert!(!set_precision(0));
+    assert_eq!(precision(), 16);
+    assert!(set_precision(64));
+    assert_eq!(show(Float::from(1) / Float::from(3)), "0.3333333333333333");
+}
+#[test]
+fn test_complex() {
+    set_precision(32);
+    let z = Complex::from((1, 1)).exp();
+    assert_eq!(z.real.0, z.real.0 as f32 as f64);
+    assert_eq!(z.imag.0, z.imag.0 as f32 as f64);
+    assert_eq!(Complex::from((0.1, 0.2)).to_string(), "0.1+0.2i");
+    set_precision(64);
+    assert_eq!(Complex::from((0.1, 0.2)).to_string(), "0.1+0.2i");
+}
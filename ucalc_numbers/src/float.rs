@@ -1,11 +1,15 @@
-use crate::integer::Integer;
+#[cfg(feature = "fastnum")]
+use crate::decimal::{self, DecimalFunctions, ToDecimal, consts};
+#[cfg(feature = "dynamic")]
+use crate::dynamic::{self, narrow};
+use crate::integer::{I, Integer, U, UInteger};
 use crate::{
     ComplexFunctions, ComplexFunctionsMut, ComplexTrait, ComplexType, Constant, FloatFunctions,
     FloatFunctionsMut, FloatTrait, FloatType, NegAssign, Pow, RealTrait, RealType,
 };
 #[cfg(feature = "float_rand")]
 use rand::RngExt;
-#[cfg(feature = "float_rand")]
+#[cfg(all(feature = "float_rand", not(feature = "fastnum")))]
 use rand::distr::uniform::SampleRange;
 #[cfg(feature = "float_rand")]
 use rand::rngs::ThreadRng;
@@ -25,6 +29,16 @@ use std::ops::{
 };
 use std::{fmt, mem};
 #[cfg(feature = "f16")]
+const MAX_FACTORIAL: usize = 8;
+#[cfg(feature = "f32")]
+const MAX_FACTORIAL: usize = 34;
+#[cfg(feature = "f64")]
+const MAX_FACTORIAL: usize = 170;
+#[cfg(feature = "f128")]
+const MAX_FACTORIAL: usize = 1754;
+#[cfg(feature = "fastnum")]
+const MAX_FACTORIAL: usize = 4096;
+#[cfg(feature = "f16")]
 pub type F = f16;
 #[cfg(feature = "f32")]
 pub type F = f32;
@@ -34,6 +48,22 @@ pub type F = f64;
 pub type F = f128;
 #[cfg(feature = "fastnum")]
 pub type F = fastnum::D1024;
+#[cfg(not(feature = "dynamic"))]
+fn narrow<T>(value: T) -> T {
+    value
+}
+macro_rules! math {
+    ($fun:ident, $value:expr $(, $arg:expr)?) => {{
+        #[cfg(not(feature = "fastnum"))]
+        {
+            narrow($value.$fun($($arg)?))
+        }
+        #[cfg(feature = "fastnum")]
+        {
+            decimal::$fun($value $(, $arg)?)
+        }
+    }};
+}
 #[derive(Clone, Default, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct Float(pub F);
@@ -48,13 +78,35 @@ impl ComplexType<Float> for Complex {}
 impl RealType for Float {}
 impl Debug for Float {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.0)
+        #[cfg(not(feature = "fastnum"))]
+        {
+            write!(f, "{:?}", self.0)
+        }
+        #[cfg(feature = "fastnum")]
+        {
+            write!(f, "{}", self.0)
+        }
     }
 }
-#[cfg(feature = "float_rand")]
+impl Float {
+    pub fn to_integer(&self) -> Option<Integer> {
+        if !self.0.is_finite() || !self.clone().fract().is_zero() {
+            return None;
+        }
+        #[cfg(not(feature = "fastnum"))]
+        {
+            format!("{:.0}", self.0).parse().ok().map(Integer)
+        }
+        #[cfg(feature = "fastnum")]
+        {
+            Some(Integer(decimal::to_integer(self.0)))
+        }
+    }
+}
+#[cfg(all(feature = "float_rand", not(feature = "fastnum")))]
 impl Float {
     pub fn random_range(thread_rng: &mut ThreadRng, range: impl SampleRange<F>) -> Self {
-        Self(thread_rng.random_range(range))
+        Self(narrow(thread_rng.random_range(range)))
     }
     pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
         if *self != b {
@@ -62,7 +114,16 @@ impl Float {
         }
     }
 }
-#[cfg(feature = "float_rand")]
+#[cfg(all(feature = "float_rand", feature = "fastnum"))]
+impl Float {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
+        if *self != b {
+            let t = Float::from(thread_rng.random_range(0.0..=1.0f64));
+            *self += (b - self.clone()) * t;
+        }
+    }
+}
+#[cfg(all(feature = "float_rand", not(feature = "fastnum")))]
 impl Complex {
     pub fn random_range(
         thread_rng: &mut ThreadRng,
@@ -83,6 +144,13 @@ impl Complex {
         }
     }
 }
+#[cfg(all(feature = "float_rand", feature = "fastnum"))]
+impl Complex {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
+        self.real.random_range_mut(b.real, thread_rng);
+        self.imag.random_range_mut(b.imag, thread_rng);
+    }
+}
 impl Debug for Complex {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
@@ -103,6 +171,11 @@ impl Debug for Integer {
         write!(f, "{:?}", self.0)
     }
 }
+impl Debug for UInteger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
 impl Display for Complex {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match (
@@ -120,6 +193,12 @@ impl Display for Complex {
 }
 impl Display for Float {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "dynamic")]
+        match dynamic::precision() {
+            16 => return write!(f, "{}", self.0 as f16),
+            32 => return write!(f, "{}", self.0 as f32),
+            _ => {}
+        }
         write!(f, "{}", self.0)
     }
 }
@@ -150,18 +229,46 @@ impl RealTrait<Float> for Float {
     fn is_sign_positive(&self) -> bool {
         self.0.is_sign_positive()
     }
+    fn is_nan(&self) -> bool {
+        self.0.is_nan()
+    }
     fn into_isize(self) -> isize {
-        self.0 as isize
+        #[cfg(not(feature = "fastnum"))]
+        {
+            self.0 as isize
+        }
+        #[cfg(feature = "fastnum")]
+        {
+            decimal::to_isize(self.0)
+        }
     }
     fn into_usize(self) -> usize {
-        self.0 as usize
+        #[cfg(not(feature = "fastnum"))]
+        {
+            self.0 as usize
+        }
+        #[cfg(feature = "fastnum")]
+        {
+            decimal::to_usize(self.0)
+        }
     }
     fn closest_fraction(&self) -> Option<(bool, usize, usize)> {
         if !self.0.is_finite() {
             return None;
         }
         let is_positive = self.is_sign_positive();
+        #[cfg(not(feature = "fastnum"))]
         let orig = self.0.abs();
+        #[cfg(feature = "fastnum")]
+        let orig = self.0.abs().to_f64();
+        #[cfg(not(feature = "dynamic"))]
+        let tolerance = 1e-8;
+        #[cfg(feature = "dynamic")]
+        let tolerance = match dynamic::precision() {
+            16 => 1e-2,
+            32 => 1e-4,
+            _ => 1e-8,
+        };
         let mut num = orig;
         let mut mult = 1.0;
         for _ in 0..64 {
@@ -169,7 +276,7 @@ impl RealTrait<Float> for Float {
             let fract = recip.fract();
             mult *= recip;
             num = fract;
-            if fract < 1e-8 {
+            if fract < tolerance {
                 let numerator = (orig * mult) as usize;
                 let denominator = mult as usize;
                 if denominator <= 1 {
@@ -183,52 +290,52 @@ impl RealTrait<Float> for Float {
 }
 impl FloatFunctionsMut<Float> for Float {
     fn sin_mut(&mut self) {
-        self.0 = self.0.sin();
+        self.0 = math!(sin, self.0);
     }
     fn cos_mut(&mut self) {
-        self.0 = self.0.cos();
+        self.0 = math!(cos, self.0);
     }
     fn asin_mut(&mut self) {
-        self.0 = self.0.asin();
+        self.0 = math!(asin, self.0);
     }
     fn acos_mut(&mut self) {
-        self.0 = self.0.acos();
+        self.0 = math!(acos, self.0);
     }
     fn sinh_mut(&mut self) {
-        self.0 = self.0.sinh();
+        self.0 = math!(sinh, self.0);
     }
     fn cosh_mut(&mut self) {
-        self.0 = self.0.cosh();
+        self.0 = math!(cosh, self.0);
     }
     fn asinh_mut(&mut self) {
-        self.0 = self.0.asinh();
+        self.0 = math!(asinh, self.0);
     }
     fn acosh_mut(&mut self) {
-        self.0 = self.0.acosh();
+        self.0 = math!(acosh, self.0);
     }
     fn tan_mut(&mut self) {
-        self.0 = self.0.tan()
+        self.0 = math!(tan, self.0);
     }
     fn tanh_mut(&mut self) {
-        self.0 = self.0.tanh()
+        self.0 = math!(tanh, self.0);
     }
     fn atan_mut(&mut self) {
-        self.0 = self.0.atan()
+        self.0 = math!(atan, self.0);
     }
     fn atanh_mut(&mut self) {
-        self.0 = self.0.atanh()
+        self.0 = math!(atanh, self.0);
     }
     fn ln_mut(&mut self) {
-        self.0 = self.0.ln();
+        self.0 = math!(ln, self.0);
     }
     fn exp_mut(&mut self) {
-        self.0 = self.0.exp();
+        self.0 = math!(exp, self.0);
     }
     fn hypot_mut(&mut self, other: &Self) {
-        self.0 = self.0.hypot(other.0);
+        self.0 = math!(hypot, self.0, other.0);
     }
     fn atan2_mut(&mut self, other: &Self) {
-        self.0 = self.0.atan2(other.0);
+        self.0 = math!(atan2, self.0, other.0);
     }
     fn min_mut(&mut self, other: &Self) {
         self.0 = self.0.min(other.0);
@@ -237,28 +344,40 @@ impl FloatFunctionsMut<Float> for Float {
         self.0 = self.0.max(other.0);
     }
     fn recip_mut(&mut self) {
-        self.0 = self.0.recip();
+        self.0 = math!(recip, self.0);
     }
     fn sqrt_mut(&mut self) {
-        self.0 = self.0.sqrt();
+        self.0 = math!(sqrt, self.0);
     }
     fn cbrt_mut(&mut self) {
-        self.0 = self.0.cbrt();
+        self.0 = math!(cbrt, self.0);
     }
     fn abs_mut(&mut self) {
         self.0 = self.0.abs();
     }
     fn gamma_mut(&mut self) {
-        self.0 = self.0.gamma();
+        if *self > Float::default()
+            && self.clone().fract().is_zero()
+            && self.clone().into_usize() <= MAX_FACTORIAL
+        {
+            *self = Self::from(
+                UInteger::from(self.clone().into_usize() - 1)
+                    .factorial()
+                    .unwrap()
+                    .0,
+            )
+        } else {
+            self.0 = narrow(self.0.gamma());
+        }
     }
     fn erf_mut(&mut self) {
-        self.0 = self.0.erf();
+        self.0 = narrow(self.0.erf());
     }
     fn erfc_mut(&mut self) {
-        self.0 = self.0.erfc();
+        self.0 = narrow(self.0.erfc());
     }
     fn round_mut(&mut self) {
-        self.0 = self.0.round();
+        self.0 = math!(round, self.0);
     }
     fn ceil_mut(&mut self) {
         self.0 = self.0.ceil();
@@ -275,7 +394,7 @@ impl FloatFunctionsMut<Float> for Float {
     fn tetration_mut(&mut self, other: &Self) {
         fn tetration(a: Float, other: &Float) -> Float {
             let other = other.clone().round();
-            if other.0 <= 0.0 {
+            if other <= Float::default() {
                 Float::from(1)
             } else {
                 a.clone().pow(a.tetration(&(other - Float::from(1))))
@@ -304,13 +423,27 @@ impl FloatTrait<Float> for Float {
         self
     }
     fn is_zero(&self) -> bool {
-        self.0 == 0.0
+        self.0 == F::default()
     }
     fn parse_radix(src: &str, base: u8) -> Option<Self> {
-        float_base::parse_radix(src, base).map(Self)
+        #[cfg(not(feature = "dynamic"))]
+        {
+            float_base::parse_radix(src, base).map(Self)
+        }
+        #[cfg(feature = "dynamic")]
+        {
+            float_base::parse_radix_bits(src, base, dynamic::precision()).map(Self)
+        }
     }
     fn to_string_radix(&self, base: u8) -> impl Display {
-        float_base::to_string_radix(self.0, base)
+        #[cfg(not(feature = "dynamic"))]
+        {
+            float_base::to_string_radix(self.0, base)
+        }
+        #[cfg(feature = "dynamic")]
+        {
+            float_base::to_string_radix_bits(self.0, base, dynamic::precision())
+        }
     }
     fn get_closest_fraction(&self, base: u8) -> impl Display {
         fmt::from_fn(move |fmt| {
@@ -346,7 +479,7 @@ impl ComplexFunctionsMut<Float> for Complex {
         self.imag.neg_assign();
     }
     fn norm_mut(&mut self) {
-        self.real.0 = self.real.0.powi(2) + self.imag.0.powi(2);
+        self.real.0 = narrow(math!(powi, self.real.0, 2) + math!(powi, self.imag.0, 2));
         self.zero_imag();
     }
 }
@@ -373,19 +506,19 @@ impl ComplexTrait<Float> for Complex {
 }
 impl FloatFunctionsMut<Float> for Complex {
     fn sin_mut(&mut self) {
-        let (a, b) = self.real.0.sin_cos();
-        let (c, d) = (self.imag.0.sinh(), self.imag.0.cosh());
+        let (a, b) = math!(sin_cos, self.real.0);
+        let (c, d) = (math!(sinh, self.imag.0), math!(cosh, self.imag.0));
         *self = Self {
-            real: Float(a * d),
-            imag: Float(b * c),
+            real: Float(narrow(a * d)),
+            imag: Float(narrow(b * c)),
         }
     }
     fn cos_mut(&mut self) {
-        let (a, b) = self.real.0.sin_cos();
-        let (c, d) = (self.imag.0.sinh(), self.imag.0.cosh());
+        let (a, b) = math!(sin_cos, self.real.0);
+        let (c, d) = (math!(sinh, self.imag.0), math!(cosh, self.imag.0));
         *self = Self {
-            real: Float(b * d),
-            imag: Float(-a * c),
+            real: Float(narrow(b * d)),
+            imag: Float(narrow(-a * c)),
         }
     }
     fn asin_mut(&mut self) {
@@ -445,10 +578,10 @@ impl FloatFunctionsMut<Float> for Complex {
         }
     }
     fn exp_mut(&mut self) {
-        let (imag, real) = self.imag.clone().0.sin_cos();
-        let e = self.real.0.exp();
-        self.real = Float(real * e);
-        self.imag = Float(imag * e);
+        let (imag, real) = math!(sin_cos, self.imag.0);
+        let e = self.real.clone().exp().0;
+        self.real = Float(narrow(real * e));
+        self.imag = Float(narrow(imag * e));
     }
     fn hypot_mut(&mut self, other: &Self) {
         if self.imag.is_zero() && other.imag.is_zero() {
@@ -520,14 +653,14 @@ impl FloatFunctionsMut<Float> for Complex {
         } else {
             let r = self.clone().abs().cbrt();
             let theta = self.clone().arg() / Float::from(3);
-            let (sin, cos) = theta.0.sin_cos();
+            let (sin, cos) = math!(sin_cos, theta.0);
             self.real = Float(cos) * &r;
             self.imag = Float(sin) * r;
         }
     }
     fn abs_mut(&mut self) {
         self.real.hypot_mut(&self.imag);
-        self.imag = Float(0.0);
+        self.imag = Float::default();
     }
     fn gamma_mut(&mut self) {
         if self.imag.is_zero() {
@@ -581,7 +714,7 @@ impl FloatFunctionsMut<Float> for Complex {
         sq.neg_assign();
         sq.exp_mut();
         sq *= self.deref();
-        sq *= Float::from(consts::FRAC_2_SQRT_PI / 2.0);
+        sq *= Float::from(consts::FRAC_2_SQRT_PI) / Float::from(2);
         fn inner<const N: usize>(s: &Complex) -> Complex {
             let mut run =
                 ((Complex::from(N) + Float::from(0.5) + s) + Float::from(1)) / Float::from(N);
@@ -623,7 +756,7 @@ impl FloatFunctionsMut<Float> for Complex {
     fn tetration_mut(&mut self, other: &Self) {
         fn tetration(a: Complex, other: &Complex) -> Complex {
             let other = Complex::from(other.real.clone().round());
-            if other.real.0 <= 0.0 {
+            if other.real <= Float::default() {
                 Complex::from(1)
             } else {
                 a.clone().pow(a.tetration(&(other - Float::from(1))))
@@ -657,7 +790,7 @@ impl FloatTrait<Float> for Complex {
     fn parse_radix(src: &str, base: u8) -> Option<Self> {
         Some(Self {
             real: Float::parse_radix(src, base)?,
-            imag: Float(0.0),
+            imag: Float::default(),
         })
     }
     fn to_string_radix(&self, base: u8) -> impl Display {
@@ -756,13 +889,13 @@ macro_rules! ops_assign {
         }
         impl<'a> $assign<&'a $ty> for $ty {
             default fn $assign_fun(&mut self, rhs: &'a $ty) {
-                $assign::$assign_fun(&mut self.0, rhs.0);
+                $assign::$assign_fun(&mut self.0, rhs.clone().0);
             }
         }
         impl<'a> $orig<&'a $ty> for $ty {
             type Output = $ty;
             default fn $orig_fun(self, rhs: &'a $ty) -> $ty {
-                Self($orig::$orig_fun(self.0, rhs.0))
+                Self($orig::$orig_fun(self.0, rhs.clone().0))
             }
         }
     };
@@ -778,13 +911,111 @@ macro_rules! ops_assign_for {
         )*
     }
 }
+#[cfg(not(any(feature = "fastnum", feature = "dynamic")))]
 ops_assign_for!(Float, Integer);
+#[cfg(any(feature = "fastnum", feature = "dynamic"))]
+ops_assign_for!(Integer);
+#[cfg(feature = "fastnum")]
+ops_assign!(Float, SubAssign, Sub, sub_assign, sub);
+#[cfg(feature = "fastnum")]
+ops_assign!(Float, AddAssign, Add, add_assign, add);
+#[cfg(feature = "fastnum")]
+macro_rules! decimal_ops {
+    ($assign:ident, $orig:ident, $assign_fun:ident, $orig_fun:ident) => {
+        impl $assign<Float> for Float {
+            default fn $assign_fun(&mut self, rhs: Float) {
+                self.0 = decimal::$orig_fun(self.0, rhs.0);
+            }
+        }
+        impl $orig<Float> for Float {
+            type Output = Float;
+            default fn $orig_fun(self, rhs: Float) -> Float {
+                Self(decimal::$orig_fun(self.0, rhs.0))
+            }
+        }
+        impl<'a> $assign<&'a Float> for Float {
+            default fn $assign_fun(&mut self, rhs: &'a Float) {
+                self.0 = decimal::$orig_fun(self.0, rhs.0);
+            }
+        }
+        impl<'a> $orig<&'a Float> for Float {
+            type Output = Float;
+            default fn $orig_fun(self, rhs: &'a Float) -> Float {
+                Self(decimal::$orig_fun(self.0, rhs.0))
+            }
+        }
+    };
+}
+#[cfg(feature = "fastnum")]
+decimal_ops!(MulAssign, Mul, mul_assign, mul);
+#[cfg(feature = "fastnum")]
+decimal_ops!(DivAssign, Div, div_assign, div);
+#[cfg(feature = "fastnum")]
+decimal_ops!(RemAssign, Rem, rem_assign, rem);
+#[cfg(feature = "dynamic")]
+macro_rules! narrow_ops {
+    ($assign:ident, $orig:ident, $assign_fun:ident, $orig_fun:ident) => {
+        impl $assign<Float> for Float {
+            default fn $assign_fun(&mut self, rhs: Float) {
+                self.0 = narrow($orig::$orig_fun(self.0, rhs.0));
+            }
+        }
+        impl $orig<Float> for Float {
+            type Output = Float;
+            default fn $orig_fun(self, rhs: Float) -> Float {
+                Self(narrow($orig::$orig_fun(self.0, rhs.0)))
+            }
+        }
+        impl<'a> $assign<&'a Float> for Float {
+            default fn $assign_fun(&mut self, rhs: &'a Float) {
+                self.0 = narrow($orig::$orig_fun(self.0, rhs.0));
+            }
+        }
+        impl<'a> $orig<&'a Float> for Float {
+            type Output = Float;
+            default fn $orig_fun(self, rhs: &'a Float) -> Float {
+                Self(narrow($orig::$orig_fun(self.0, rhs.0)))
+            }
+        }
+    };
+}
+#[cfg(feature = "dynamic")]
+narrow_ops!(MulAssign, Mul, mul_assign, mul);
+#[cfg(feature = "dynamic")]
+narrow_ops!(DivAssign, Div, div_assign, div);
+#[cfg(feature = "dynamic")]
+narrow_ops!(SubAssign, Sub, sub_assign, sub);
+#[cfg(feature = "dynamic")]
+narrow_ops!(AddAssign, Add, add_assign, add);
+#[cfg(feature = "dynamic")]
+narrow_ops!(RemAssign, Rem, rem_assign, rem);
+#[cfg(feature = "fastnum")]
+impl From<F> for Float {
+    fn from(value: F) -> Self {
+        Self(value)
+    }
+}
+impl From<U> for Float {
+    fn from(value: U) -> Self {
+        Self::parse_radix(&value.to_string(), 10).unwrap()
+    }
+}
+impl From<I> for Float {
+    fn from(value: I) -> Self {
+        Self::parse_radix(&value.to_string(), 10).unwrap()
+    }
+}
+impl From<Integer> for Float {
+    fn from(value: Integer) -> Self {
+        Self::from(value.0)
+    }
+}
 impl From<Constant> for Float {
     fn from(value: Constant) -> Self {
         match value {
-            Constant::Pi => Self(consts::PI),
-            Constant::Tau => Self(consts::TAU),
-            Constant::E => Self(consts::E),
+            Constant::Pi => Self(narrow(consts::PI)),
+            Constant::Tau => Self(narrow(consts::TAU)),
+            Constant::E => Self(narrow(consts::E)),
             Constant::Infinity => Self(F::INFINITY),
             Constant::NegInfinity => Self(F::NEG_INFINITY),
             Constant::Nan => Self(F::NAN),
@@ -803,7 +1034,7 @@ where
     fn from(value: T) -> Self {
         Complex {
             real: value.into(),
-            imag: Float(0.0),
+            imag: Float::default(),
         }
     }
 }
@@ -829,9 +1060,11 @@ macro_rules! with_val {
                     #[cfg(feature = "f32")]
                     {Self(value as f32)}
                     #[cfg(feature = "f64")]
-                    {Self(value as f64)}
+                    {Self(narrow(value as f64))}
                     #[cfg(feature = "f128")]
                     {Self(value as f128)}
+                    #[cfg(feature = "fastnum")]
+                    {Self(value.to_decimal())}
                 }
             }
         )*
@@ -849,16 +1082,16 @@ impl Pow<Float> for Float {
 impl Pow<&Float> for Float {
     type Output = Float;
     default fn pow(self, rhs: &Float) -> Self {
-        if rhs.0.fract() == 0.0
-            && let Ok(rhs) = (rhs.0 as i64).try_into()
+        if rhs.clone().fract().is_zero()
+            && let Ok(rhs) = rhs.clone().into_isize().try_into()
         {
             if rhs == 0 && self.is_zero() {
-                Self(f64::NAN)
+                Self::from(Constant::Nan)
             } else {
-                Self(self.0.powi(rhs))
+                Self(math!(powi, self.0, rhs))
             }
         } else {
-            Self(self.0.powf(rhs.0))
+            Self(narrow(self.0.powf(rhs.0)))
         }
     }
 }
@@ -922,7 +1155,7 @@ impl Pow<Float> for Complex {
                 let fract = rhs.clone().fract();
                 if fract.is_zero() {
                     self.real.pow(rhs).into()
-                } else if fract.0 == 0.5 {
+                } else if fract == Float::from(0.5) {
                     Complex::from(self.real.abs().pow(rhs)).mul_i(false)
                 } else {
                     (self.ln() * rhs).exp()
@@ -931,10 +1164,10 @@ impl Pow<Float> for Complex {
                 self.real.pow(rhs).into()
             }
         } else if self.real.is_zero()
-            && rhs.0.fract() == 0.0
-            && let Ok(rhs) = (rhs.0 as i64).try_into()
+            && rhs.clone().fract().is_zero()
+            && let Ok(rhs) = rhs.clone().into_isize().try_into()
         {
-            let f = self.imag.0.powi(rhs);
+            let f = math!(powi, self.imag.0, rhs);
             match rhs.rem_euclid(4) {
                 0 => Self {
                     real: Float(f),
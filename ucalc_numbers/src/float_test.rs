@@ -1,6 +1,6 @@
 use crate::float::{Complex, Float};
-use crate::integer::Integer;
-use crate::{ComplexFunctions, FloatFunctions, Pow};
+use crate::integer::{Integer, U, UInteger};
+use crate::{ComplexFunctions, FloatFunctions, FloatTrait, Pow};
 fn res<T>(f: T) -> Complex
 where
     Complex: From<T>,
@@ -10,9 +10,32 @@ where
 fn approx(a: Complex, b: Complex) -> bool {
     (a - b).abs().0 < 1e-4
 }
+fn int(n: isize) -> Integer {
+    Integer::from(n)
+}
+fn ints(v: &[isize]) -> Vec<Integer> {
+    v.iter().map(|n| int(*n)).collect()
+}
 #[test]
 fn test_factorial() {
-    assert_eq!(Integer(5).factorial(), Integer(120));
+    assert_eq!(Integer::from(5).factorial(), Some(Integer::from(120)));
+    assert_eq!(Integer::from(-3).factorial(), Some(Integer::from(1)));
+    assert_eq!(UInteger(U::from(u64::MAX) + U::from(1u8)).factorial(), None);
+    assert_eq!(
+        UInteger::from(50).factorial().unwrap().0.to_string(),
+        "30414093201713378043612608166064768844377641568960512000000000000"
+    );
+    #[cfg(feature = "f64")]
+    {
+        assert_eq!(Float::from(21).gamma(), Float::from(2432902008176640000u64));
+        assert_eq!(
+            Float::from(51).gamma(),
+            Float::from(UInteger::from(50).factorial().unwrap().0)
+        );
+        assert!(Float::from(171).gamma().0.is_finite());
+        assert!(Float::from(172).gamma().0.is_infinite());
+        assert!((Float::from(0.5).gamma() - Float::from(std::f64::consts::PI).sqrt()).0 < 1e-15);
+    }
 }
 #[test]
 fn test_binomial() {
@@ -20,17 +43,112 @@ fn test_binomial() {
         .into_iter()
         .enumerate()
     {
-        assert_eq!(Integer(10).binomial(Integer(i as isize)), Integer(r));
+        assert_eq!(
+            Integer::from(10).binomial(Integer::from(i as isize)),
+            Integer::from(r)
+        );
     }
     for (i, r) in [1, 11, 55, 165, 330, 462, 462, 330, 165, 55, 11, 1]
         .into_iter()
         .enumerate()
     {
-        assert_eq!(Integer(11).binomial(Integer(i as isize)), Integer(r));
+        assert_eq!(
+            Integer::from(11).binomial(Integer::from(i as isize)),
+            Integer::from(r)
+        );
     }
-    assert_eq!(Integer(0).binomial(Integer(0)), Integer(1));
-    assert_eq!(Integer(1).binomial(Integer(0)), Integer(1));
-    assert_eq!(Integer(1).binomial(Integer(1)), Integer(1));
+    assert_eq!(
+        Integer::from(0).binomial(Integer::from(0)),
+        Integer::from(1)
+    );
+    assert_eq!(
+        Integer::from(1).binomial(Integer::from(0)),
+        Integer::from(1)
+    );
+    assert_eq!(
+        Integer::from(1).binomial(Integer::from(1)),
+        Integer::from(1)
+    );
+    assert_eq!(
+        Integer::from(-1).binomial(Integer::from(1)),
+        Integer::from(-1)
+    );
+    assert_eq!(
+        Integer::from(-1).binomial(Integer::from(2)),
+        Integer::from(1)
+    );
+    assert_eq!(
+        Integer::from(-4).binomial(Integer::from(3)),
+        Integer::from(-20)
+    );
+    assert_eq!(
+        Integer::from(3).binomial(Integer::from(-1)),
+        Integer::from(0)
+    );
+    assert_eq!(
+        UInteger::from(100)
+            .binomial(UInteger::from(50))
+            .0
+            .to_string(),
+        "100891344545564193334812497256"
+    );
+    #[cfg(feature = "f64")]
+    assert_eq!(
+        Float::from(UInteger::from(1000).binomial(UInteger::from(500)).0)
+            .to_string_radix(10)
+            .to_string(),
+        "2.7028824094543655e299"
+    );
+}
+#[test]
+fn test_number_theory() {
+    assert_eq!(int(12).gcd(int(-18)), int(6));
+    assert_eq!(int(4).lcm(int(6)), int(12));
+    assert!(int(2305843009213693951).is_prime());
+    assert!(!int(561).is_prime());
+    assert!(!int(1).is_prime());
+    assert_eq!(int(13).next_prime(), int(17));
+    assert_eq!(int(-5).next_prime(), int(2));
+    assert_eq!(int(600851475143).factor(), ints(&[71, 839, 1471, 6857]));
+    assert_eq!(int(-12).factor(), ints(&[-1, 2, 2, 3]));
+    assert_eq!(int(1000006000009).factor(), ints(&[1000003, 1000003]));
+    assert_eq!(
+        Integer(int(2147483647).0 * int(2305843009213693951).0).factor(),
+        ints(&[2147483647, 2305843009213693951])
+    );
+    assert_eq!(int(4).modpow(int(13), int(497)), Some(int(445)));
+    assert_eq!(int(3).modpow(int(-1), int(7)), Some(int(5)));
+    assert_eq!(int(2).modpow(int(-1), int(4)), None);
+    assert_eq!(int(3).modinv(int(11)), Some(int(4)));
+    assert_eq!(int(-3).modinv(int(11)), Some(int(7)));
+    assert_eq!(int(2).modinv(int(4)), None);
+    assert_eq!(int(36).totient(), int(12));
+    assert_eq!(int(28).divisors(), ints(&[1, 2, 4, 7, 14, 28]));
+    assert_eq!(int(28).sigma(), int(56));
+}
+#[test]
+fn test_combinatorics() {
+    assert_eq!(int(10).perm(int(3)), int(720));
+    assert_eq!(int(3).perm(int(4)), int(0));
+    assert_eq!(int(-1).perm(int(2)), int(2));
+    assert_eq!(int(-3).perm(int(3)), int(-60));
+    assert_eq!(int(3).perm(int(-1)), int(0));
+    assert_eq!(Integer::multinomial(ints(&[2, 3, 4])), int(1260));
+    assert_eq!(Integer::multinomial(ints(&[2, -1])), int(0));
+    assert_eq!(int(5).stirling_first(int(2)), Some(int(-50)));
+    assert_eq!(int(0).stirling_first(int(0)), Some(int(1)));
+    assert_eq!(int(5).stirling_second(int(2)), Some(int(15)));
+    assert_eq!(int(3).stirling_second(int(4)), Some(int(0)));
+    assert_eq!(int(0).bell(), Some(int(1)));
+    assert_eq!(int(10).bell(), Some(int(115975)));
+    assert_eq!(int(10).catalan(), Some(int(16796)));
+    assert_eq!(int(100).partitions(), Some(int(190569292)));
+    assert_eq!(int(-1).partitions(), Some(int(0)));
+    assert_eq!(int(1000000).bell(), None);
+    assert_eq!(int(100000000).partitions(), None);
+    assert_eq!(int(5000).stirling_first(int(5000)), None);
+    assert_eq!(int(5000).stirling_second(int(6000)), Some(int(0)));
+    assert_eq!(int(5000).catalan(), None);
 }
 #[test]
 fn test_norm() {
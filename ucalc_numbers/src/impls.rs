@@ -1,26 +1,177 @@
+#[cfg(not(feature = "rational"))]
+use crate::Complex;
 #[cfg(feature = "units")]
 use crate::Quantity;
-use crate::{Complex, Float, HalfUsize, Matrix, NegAssign, Number, Vector};
-use crate::{Pow, PowAssign};
-use std::fmt::Display;
-use std::fmt::Formatter;
+use crate::{
+    ComplexFunctionsMut, ComplexTrait, Constant, FloatFunctionsMut, FloatTrait, Pow, PowAssign,
+};
+use crate::{Float, HalfUsize, Matrix, NegAssign, Number, Vector};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
 use std::iter::{Product, Sum};
 use std::ops::Neg;
 use std::ops::{
-    Add, AddAssign, Deref, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Range, Rem, RemAssign,
-    Sub, SubAssign,
+    Add, AddAssign, Deref, DerefMut, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Range, Rem,
+    RemAssign, Sub, SubAssign,
 };
-use ucalc_numbers_macros::{generate_lower, generate_types};
+use std::{ptr, slice};
+#[cfg(not(feature = "rational"))]
+use ucalc_numbers_macros::generate_lower;
+use ucalc_numbers_macros::generate_types;
 impl<T> Deref for Vector<T> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
+impl<T> DerefMut for Vector<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+impl<T> Vector<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+impl<T: Display> Display for Vector<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, a) in self.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?
+            }
+            write!(f, "{}", a)?
+        }
+        write!(f, "]")
+    }
+}
+impl<T> Matrix<T> {
+    pub fn new(mut vec: Vec<T>, width: HalfUsize) -> Self {
+        let height = vec.len().checked_div(width as usize).unwrap_or(0) as HalfUsize;
+        vec.truncate(width as usize * height as usize);
+        let vec = Box::into_raw(vec.into_boxed_slice()).cast::<T>();
+        Self {
+            vec,
+            capacity_width: width,
+            capacity_height: height,
+            width,
+            height,
+        }
+    }
+    pub fn width(&self) -> HalfUsize {
+        self.width
+    }
+    pub fn height(&self) -> HalfUsize {
+        self.height
+    }
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.vec, self.width as usize * self.height as usize) }
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.vec, self.width as usize * self.height as usize) }
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|i| &self[i])
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        let len = self.width as usize * self.height as usize;
+        let cap = self.capacity_width as usize * self.capacity_height as usize;
+        let vec = self.vec;
+        std::mem::forget(self);
+        unsafe { Vec::from_raw_parts(vec, len, cap) }
+    }
+}
+impl<T> Matrix<T>
+where
+    T: Clone + Default + AddAssign + Mul<Output = T>,
+{
+    pub fn mul_matrix(&self, other: &Self) -> Option<Self> {
+        if self.width != other.height {
+            return None;
+        }
+        let mut vec = Vec::with_capacity(self.height as usize * other.width as usize);
+        for row in self.rows() {
+            for j in 0..other.width {
+                let mut sum = T::default();
+                for (k, a) in row.iter().enumerate() {
+                    sum += a.clone() * other[(k as HalfUsize, j)].clone();
+                }
+                vec.push(sum)
+            }
+        }
+        Some(Self::new(vec, other.width))
+    }
+    pub fn mul_vector(&self, other: &Vector<T>) -> Option<Vector<T>> {
+        if self.width as usize != other.len() {
+            return None;
+        }
+        Some(Vector(
+            self.rows()
+                .map(|row| {
+                    let mut sum = T::default();
+                    for (a, b) in row.iter().zip(other.iter()) {
+                        sum += a.clone() * b.clone();
+                    }
+                    sum
+                })
+                .collect(),
+        ))
+    }
+}
+impl<T> Drop for Matrix<T> {
+    fn drop(&mut self) {
+        let cap = self.capacity_width as usize * self.capacity_height as usize;
+        unsafe { drop(Box::from_raw(ptr::slice_from_raw_parts_mut(self.vec, cap))) }
+    }
+}
+impl<T: Clone> Clone for Matrix<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.as_slice().to_vec(), self.width)
+    }
+}
+impl<T: PartialEq> PartialEq for Matrix<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.as_slice() == other.as_slice()
+    }
+}
+impl<T: Debug> Debug for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+impl<T: Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, row) in self.rows().enumerate() {
+            if i != 0 {
+                write!(f, ",")?
+            }
+            write!(f, "[")?;
+            for (j, a) in row.iter().enumerate() {
+                if j != 0 {
+                    write!(f, ",")?
+                }
+                write!(f, "{}", a)?
+            }
+            write!(f, "]")?
+        }
+        write!(f, "]")
+    }
+}
+unsafe impl<T: Send> Send for Matrix<T> {}
+unsafe impl<T: Sync> Sync for Matrix<T> {}
 impl<T> Index<HalfUsize> for Matrix<T> {
     type Output = [T];
     fn index(&self, index: HalfUsize) -> &Self::Output {
-        let start = index as usize * self.height as usize;
+        let start = index as usize * self.width as usize;
         unsafe {
             self.vec
                 .add(start)
@@ -32,7 +183,7 @@ impl<T> Index<HalfUsize> for Matrix<T> {
 }
 impl<T> IndexMut<HalfUsize> for Matrix<T> {
     fn index_mut(&mut self, index: HalfUsize) -> &mut Self::Output {
-        let start = index as usize * self.height as usize;
+        let start = index as usize * self.width as usize;
         unsafe {
             self.vec
                 .add(start)
@@ -45,13 +196,13 @@ impl<T> IndexMut<HalfUsize> for Matrix<T> {
 impl<T> Index<(HalfUsize, HalfUsize)> for Matrix<T> {
     type Output = T;
     fn index(&self, (i, j): (HalfUsize, HalfUsize)) -> &Self::Output {
-        let start = i as usize * self.height as usize;
+        let start = i as usize * self.width as usize;
         unsafe { self.vec.add(start).add(j as usize).as_ref().unwrap() }
     }
 }
 impl<T> IndexMut<(HalfUsize, HalfUsize)> for Matrix<T> {
     fn index_mut(&mut self, (i, j): (HalfUsize, HalfUsize)) -> &mut Self::Output {
-        let start = i as usize * self.height as usize;
+        let start = i as usize * self.width as usize;
         unsafe { self.vec.add(start).add(j as usize).as_mut().unwrap() }
     }
 }
@@ -111,6 +262,287 @@ impl<T> Number<T> {
         Some(val)
     }
 }
+#[cfg(not(feature = "units"))]
+impl<T: Clone + From<Constant>> Number<T> {
+    pub fn map_mut(&mut self, f: &mut impl FnMut(&mut T)) {
+        match self {
+            Self::Value(a) => f(a),
+            #[cfg(feature = "vector")]
+            Self::Vector(a) => a.iter_mut().for_each(f),
+            #[cfg(feature = "matrix")]
+            Self::Matrix(a) => a.as_mut_slice().iter_mut().for_each(f),
+            #[cfg(feature = "list")]
+            Self::List(a) => a.iter_mut().for_each(|a| a.map_mut(f)),
+        }
+    }
+    pub fn zip_mut(&mut self, other: &Self, f: &mut impl FnMut(&mut T, &T)) {
+        match (self, other) {
+            (Self::Value(a), Self::Value(b)) => f(a, b),
+            #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+            (s, Self::Value(b)) => s.map_mut(&mut |a| f(a, b)),
+            #[cfg(feature = "vector")]
+            (Self::Vector(a), Self::Vector(b)) if a.len() == b.len() => {
+                a.iter_mut().zip(b.iter()).for_each(|(a, b)| f(a, b))
+            }
+            #[cfg(feature = "matrix")]
+            (Self::Matrix(a), Self::Matrix(b))
+                if a.width() == b.width() && a.height() == b.height() =>
+            {
+                a.as_mut_slice()
+                    .iter_mut()
+                    .zip(b.as_slice())
+                    .for_each(|(a, b)| f(a, b))
+            }
+            #[cfg(feature = "list")]
+            (Self::List(a), Self::List(b)) => a
+                .iter_mut()
+                .zip(b.iter())
+                .for_each(|(a, b)| a.zip_mut(b, f)),
+            #[cfg(all(feature = "list", any(feature = "vector", feature = "matrix")))]
+            (Self::List(a), b) => a.iter_mut().for_each(|a| a.zip_mut(b, f)),
+            #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+            (s @ Self::Value(_), b) => {
+                let a = s.value_ref().clone();
+                *s = b.clone();
+                s.map_mut(&mut |b| {
+                    let mut c = a.clone();
+                    f(&mut c, b);
+                    *b = c
+                })
+            }
+            #[cfg(any(feature = "vector", feature = "matrix"))]
+            (s, _) => *s = Self::Value(T::from(Constant::Nan)),
+        }
+    }
+    fn as_slice(&self) -> Option<&[T]> {
+        match self {
+            Self::Value(a) => Some(slice::from_ref(a)),
+            #[cfg(feature = "vector")]
+            Self::Vector(a) => Some(a),
+            #[cfg(feature = "matrix")]
+            Self::Matrix(a) => Some(a.as_slice()),
+            #[cfg(feature = "list")]
+            Self::List(_) => None,
+        }
+    }
+    fn display_with(&self, f: &impl Fn(&T) -> String) -> String {
+        #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+        let join = |iter: &mut dyn Iterator<Item = String>| {
+            format!("[{}]", iter.collect::<Vec<_>>().join(","))
+        };
+        match self {
+            Self::Value(a) => f(a),
+            #[cfg(feature = "vector")]
+            Self::Vector(a) => join(&mut a.iter().map(f)),
+            #[cfg(feature = "matrix")]
+            Self::Matrix(a) => join(&mut a.rows().map(|row| join(&mut row.iter().map(f)))),
+            #[cfg(feature = "list")]
+            Self::List(a) => join(&mut a.iter().map(|a| a.display_with(f))),
+        }
+    }
+}
+#[cfg(not(feature = "units"))]
+impl<F, T: FloatFunctionsMut<F> + Clone + From<Constant>> FloatFunctionsMut<F> for Number<T> {
+    fn sin_mut(&mut self) {
+        self.map_mut(&mut |a| a.sin_mut())
+    }
+    fn cos_mut(&mut self) {
+        self.map_mut(&mut |a| a.cos_mut())
+    }
+    fn asin_mut(&mut self) {
+        self.map_mut(&mut |a| a.asin_mut())
+    }
+    fn acos_mut(&mut self) {
+        self.map_mut(&mut |a| a.acos_mut())
+    }
+    fn sinh_mut(&mut self) {
+        self.map_mut(&mut |a| a.sinh_mut())
+    }
+    fn cosh_mut(&mut self) {
+        self.map_mut(&mut |a| a.cosh_mut())
+    }
+    fn asinh_mut(&mut self) {
+        self.map_mut(&mut |a| a.asinh_mut())
+    }
+    fn acosh_mut(&mut self) {
+        self.map_mut(&mut |a| a.acosh_mut())
+    }
+    fn tan_mut(&mut self) {
+        self.map_mut(&mut |a| a.tan_mut())
+    }
+    fn tanh_mut(&mut self) {
+        self.map_mut(&mut |a| a.tanh_mut())
+    }
+    fn atan_mut(&mut self) {
+        self.map_mut(&mut |a| a.atan_mut())
+    }
+    fn atanh_mut(&mut self) {
+        self.map_mut(&mut |a| a.atanh_mut())
+    }
+    fn ln_mut(&mut self) {
+        self.map_mut(&mut |a| a.ln_mut())
+    }
+    fn exp_mut(&mut self) {
+        self.map_mut(&mut |a| a.exp_mut())
+    }
+    fn hypot_mut(&mut self, other: &Self) {
+        self.zip_mut(other, &mut |a, b| a.hypot_mut(b))
+    }
+    fn atan2_mut(&mut self, other: &Self) {
+        self.zip_mut(other, &mut |a, b| a.atan2_mut(b))
+    }
+    fn min_mut(&mut self, other: &Self) {
+        self.zip_mut(other, &mut |a, b| a.min_mut(b))
+    }
+    fn max_mut(&mut self, other: &Self) {
+        self.zip_mut(other, &mut |a, b| a.max_mut(b))
+    }
+    fn recip_mut(&mut self) {
+        self.map_mut(&mut |a| a.recip_mut())
+    }
+    fn sqrt_mut(&mut self) {
+        self.map_mut(&mut |a| a.sqrt_mut())
+    }
+    fn cbrt_mut(&mut self) {
+        self.map_mut(&mut |a| a.cbrt_mut())
+    }
+    fn abs_mut(&mut self) {
+        self.map_mut(&mut |a| a.abs_mut())
+    }
+    fn gamma_mut(&mut self) {
+        self.map_mut(&mut |a| a.gamma_mut())
+    }
+    fn erf_mut(&mut self) {
+        self.map_mut(&mut |a| a.erf_mut())
+    }
+    fn erfc_mut(&mut self) {
+        self.map_mut(&mut |a| a.erfc_mut())
+    }
+    fn round_mut(&mut self) {
+        self.map_mut(&mut |a| a.round_mut())
+    }
+    fn ceil_mut(&mut self) {
+        self.map_mut(&mut |a| a.ceil_mut())
+    }
+    fn floor_mut(&mut self) {
+        self.map_mut(&mut |a| a.floor_mut())
+    }
+    fn trunc_mut(&mut self) {
+        self.map_mut(&mut |a| a.trunc_mut())
+    }
+    fn fract_mut(&mut self) {
+        self.map_mut(&mut |a| a.fract_mut())
+    }
+    fn tetration_mut(&mut self, other: &Self) {
+        self.zip_mut(other, &mut |a, b| a.tetration_mut(b))
+    }
+    fn subfactorial_mut(&mut self) {
+        self.map_mut(&mut |a| a.subfactorial_mut())
+    }
+}
+#[cfg(not(feature = "units"))]
+impl<F: From<Constant>, T: FloatTrait<F> + Clone + From<Constant>> FloatTrait<F> for Number<T> {
+    fn to_real(self) -> F {
+        match self {
+            Self::Value(a) => a.to_real(),
+            #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+            _ => F::from(Constant::Nan),
+        }
+    }
+    fn real(&self) -> &F {
+        self.value_ref().real()
+    }
+    fn real_mut(&mut self) -> &mut F {
+        self.value_mut().real_mut()
+    }
+    fn is_zero(&self) -> bool {
+        match self {
+            #[cfg(feature = "list")]
+            Self::List(a) => a.iter().all(|a| a.is_zero()),
+            _ => self.as_slice().unwrap().iter().all(|a| a.is_zero()),
+        }
+    }
+    fn parse_radix(src: &str, base: u8) -> Option<Self> {
+        T::parse_radix(src, base).map(Self::Value)
+    }
+    fn to_string_radix(&self, base: u8) -> impl Display {
+        self.display_with(&|a| a.to_string_radix(base).to_string())
+    }
+    fn get_closest_fraction(&self, base: u8) -> impl Display {
+        self.display_with(&|a| a.get_closest_fraction(base).to_string())
+    }
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self.as_slice(), other.as_slice()) {
+            (Some(a), Some(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.total_cmp(b))
+                .find(|o| o.is_ne())
+                .unwrap_or(a.len().cmp(&b.len())),
+            _ => Ordering::Equal,
+        }
+    }
+}
+#[cfg(not(feature = "units"))]
+impl<F, T: ComplexFunctionsMut<F> + Clone + From<Constant>> ComplexFunctionsMut<F> for Number<T> {
+    fn arg_mut(&mut self) {
+        self.map_mut(&mut |a| a.arg_mut())
+    }
+    fn mul_i_mut(&mut self, negative: bool) {
+        self.map_mut(&mut |a| a.mul_i_mut(negative))
+    }
+    fn conj_mut(&mut self) {
+        self.map_mut(&mut |a| a.conj_mut())
+    }
+    fn norm_mut(&mut self) {
+        self.map_mut(&mut |a| a.norm_mut())
+    }
+}
+#[cfg(not(feature = "units"))]
+impl<F: From<Constant>, T: ComplexTrait<F> + Clone + From<Constant>> ComplexTrait<F> for Number<T> {
+    fn to_imag(self) -> F {
+        match self {
+            Self::Value(a) => a.to_imag(),
+            #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+            _ => F::from(Constant::Nan),
+        }
+    }
+    fn to_real_imag(self) -> (F, F) {
+        match self {
+            Self::Value(a) => a.to_real_imag(),
+            #[cfg(any(feature = "vector", feature = "matrix", feature = "list"))]
+            _ => (F::from(Constant::Nan), F::from(Constant::Nan)),
+        }
+    }
+    fn imag(&self) -> &F {
+        self.value_ref().imag()
+    }
+    fn imag_mut(&mut self) -> &mut F {
+        self.value_mut().imag_mut()
+    }
+    fn zero_real(&mut self) {
+        self.map_mut(&mut |a| a.zero_real())
+    }
+    fn zero_imag(&mut self) {
+        self.map_mut(&mut |a| a.zero_imag())
+    }
+}
+#[cfg(all(
+    feature = "float_rand",
+    not(feature = "units"),
+    not(feature = "rational")
+))]
+impl Number<Complex> {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut rand::rngs::ThreadRng) {
+        self.zip_mut(&b, &mut |a, b| a.random_range_mut(b.clone(), thread_rng))
+    }
+}
+#[cfg(all(feature = "float_rand", not(feature = "units")))]
+impl Number<Float> {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut rand::rngs::ThreadRng) {
+        self.zip_mut(&b, &mut |a, b| a.random_range_mut(b.clone(), thread_rng))
+    }
+}
 #[allow(irrefutable_let_patterns)]
 #[cfg(feature = "units")]
 impl<T, K, const N: usize> Number<T, K, N> {
@@ -145,6 +577,8 @@ impl<T, K, const N: usize> Number<T, K, N> {
         Some(val)
     }
 }
+#[cfg(not(feature = "rational"))]
 generate_lower!(Complex, Float);
+#[cfg(not(feature = "rational"))]
 generate_types!(Complex);
 generate_types!(Float);
@@ -1,66 +1,380 @@
-pub type U = usize;
-pub type I = isize;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer as _;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::mem;
+pub type U = BigUint;
+pub type I = BigInt;
 #[derive(Clone, Default, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct Integer(pub I);
 #[derive(Clone, Default, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct UInteger(pub U);
+const WITNESSES: [u8; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const TRIAL_LIMIT: u32 = 1024;
+const RHO_BATCH: usize = 128;
+const MAX_COMBINATORIAL: usize = 1 << 10;
+fn is_prime(n: &U) -> bool {
+    if *n < U::from(2u8) {
+        return false;
+    }
+    for p in WITNESSES {
+        if *n == U::from(p) {
+            return true;
+        }
+        if (n % p).is_zero() {
+            return false;
+        }
+    }
+    let m = n - 1u8;
+    let s = m.trailing_zeros().unwrap();
+    let d = &m >> s;
+    'outer: for a in WITNESSES {
+        let mut x = U::from(a).modpow(&d, n);
+        if x.is_one() || x == m {
+            continue;
+        }
+        for _ in 1..s {
+            x = &x * &x % n;
+            if x == m {
+                continue 'outer;
+            }
+        }
+        return false;
+    }
+    true
+}
+fn pollard_rho(n: &U) -> U {
+    let diff = |a: &U, b: &U| if a > b { a - b } else { b - a };
+    let mut c = U::one();
+    loop {
+        let f = |x: &U| (x * x + &c) % n;
+        let mut y = U::from(2u8);
+        let mut x = y.clone();
+        let mut ys = y.clone();
+        let mut q = U::one();
+        let mut d = U::one();
+        let mut r = 1;
+        while d.is_one() {
+            x.clone_from(&y);
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && d.is_one() {
+                ys.clone_from(&y);
+                for _ in 0..RHO_BATCH.min(r - k) {
+                    y = f(&y);
+                    q = q * diff(&x, &y) % n;
+                }
+                d = q.gcd(n);
+                k += RHO_BATCH;
+            }
+            r *= 2;
+        }
+        if d == *n {
+            d = loop {
+                ys = f(&ys);
+                let d = diff(&x, &ys).gcd(n);
+                if !d.is_one() {
+                    break d;
+                }
+            }
+        }
+        if d != *n {
+            return d;
+        }
+        c += 1u8;
+    }
+}
+fn factor_into(n: U, factors: &mut Vec<U>) {
+    if is_prime(&n) {
+        factors.push(n)
+    } else if !n.is_one() {
+        let d = pollard_rho(&n);
+        factor_into(&n / &d, factors);
+        factor_into(d, factors);
+    }
+}
+fn factor(mut n: U) -> Vec<U> {
+    let mut factors = Vec::new();
+    let mut p = 2u32;
+    while p < TRIAL_LIMIT && U::from(p * p) <= n {
+        while (&n % p).is_zero() {
+            n /= p;
+            factors.push(U::from(p));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > U::from(TRIAL_LIMIT * TRIAL_LIMIT) {
+        let start = factors.len();
+        factor_into(n, &mut factors);
+        factors[start..].sort();
+    } else if !n.is_one() {
+        factors.push(n);
+    }
+    factors
+}
+fn prime_powers(n: U) -> Vec<(U, u32)> {
+    let mut powers: Vec<(U, u32)> = Vec::new();
+    for p in factor(n) {
+        match powers.last_mut() {
+            Some((q, k)) if *q == p => *k += 1,
+            _ => powers.push((p, 1)),
+        }
+    }
+    powers
+}
 impl Integer {
+    pub fn gcd(self, other: Self) -> Self {
+        Self(self.0.gcd(&other.0))
+    }
+    pub fn lcm(self, other: Self) -> Self {
+        Self(self.0.lcm(&other.0))
+    }
+    pub fn is_prime(&self) -> bool {
+        self.0.to_biguint().is_some_and(|n| is_prime(&n))
+    }
+    pub fn next_prime(self) -> Self {
+        let Some(mut n) = self.0.to_biguint().filter(|n| *n >= U::from(2u8)) else {
+            return Self(I::from(2));
+        };
+        n += if n.is_even() { 1u8 } else { 2u8 };
+        while !is_prime(&n) {
+            n += 2u8;
+        }
+        Self(n.into())
+    }
+    pub fn factor(self) -> Vec<Self> {
+        if self.0.is_zero() || self.0.is_one() {
+            return vec![self];
+        }
+        let mut factors = Vec::new();
+        if self.0.is_negative() {
+            factors.push(Self(-I::one()))
+        }
+        factors.extend(
+            factor(self.0.magnitude().clone())
+                .into_iter()
+                .map(|p| Self(p.into())),
+        );
+        factors
+    }
+    pub fn modpow(self, exp: Self, modulus: Self) -> Option<Self> {
+        let m = modulus.0.abs();
+        if m.is_zero() {
+            return None;
+        }
+        let base = if exp.0.is_negative() {
+            self.modinv(Self(m.clone()))?.0
+        } else {
+            self.0
+        };
+        Some(Self(base.modpow(&exp.0.abs(), &m)))
+    }
+    pub fn modinv(self, modulus: Self) -> Option<Self> {
+        let m = modulus.0.abs();
+        if m.is_zero() {
+            return None;
+        }
+        let e = self.0.mod_floor(&m).extended_gcd(&m);
+        e.gcd.is_one().then(|| Self(e.x.mod_floor(&m)))
+    }
+    pub fn totient(self) -> Self {
+        if !self.0.is_positive() {
+            return Self(I::zero());
+        }
+        Self(
+            prime_powers(self.0.magnitude().clone())
+                .into_iter()
+                .map(|(p, k)| p.pow(k - 1) * (p - 1u8))
+                .product::<U>()
+                .into(),
+        )
+    }
+    pub fn divisors(self) -> Vec<Self> {
+        if self.0.is_zero() {
+            return Vec::new();
+        }
+        let mut divisors = vec![U::one()];
+        for (p, k) in prime_powers(self.0.magnitude().clone()) {
+            let len = divisors.len();
+            let mut pow = U::one();
+            for _ in 0..k {
+                pow *= &p;
+                for i in 0..len {
+                    divisors.push(&divisors[i] * &pow);
+                }
+            }
+        }
+        divisors.sort();
+        divisors.into_iter().map(|d| Self(d.into())).collect()
+    }
+    pub fn sigma(self) -> Self {
+        if self.0.is_zero() {
+            return self;
+        }
+        Self(
+            prime_powers(self.0.magnitude().clone())
+                .into_iter()
+                .map(|(p, k)| (p.pow(k + 1) - 1u8) / (p - 1u8))
+                .product::<U>()
+                .into(),
+        )
+    }
     pub fn binomial(self, k: Self) -> Self {
-        if k.0 < 0 || self.0 < 0 {
-            return Self(0);
+        match (self.0.to_biguint(), k.0.to_biguint()) {
+            (Some(n), Some(k)) => Self(UInteger(n).binomial(UInteger(k)).0.into()),
+            (None, Some(k)) => {
+                let odd = k.is_odd();
+                let n = (&k - 1u8 + self.0.magnitude()).clone();
+                let r = UInteger(n).binomial(UInteger(k)).0;
+                Self(if odd { -I::from(r) } else { r.into() })
+            }
+            _ => Self(I::zero()),
         }
-        let n = self.0 as U;
-        let k = k.0 as U;
-        Self(UInteger(n).binomial(UInteger(k)).0 as I)
     }
-    pub fn factorial(self) -> Self {
-        Self((1..=self.0).product())
+    pub fn factorial(self) -> Option<Self> {
+        match self.0.to_biguint() {
+            Some(n) => UInteger(n).factorial().map(|n| Self(n.0.into())),
+            None => Some(Self(I::one())),
+        }
+    }
+    pub fn perm(self, k: Self) -> Self {
+        match k.0.to_biguint() {
+            Some(k) if self.0.is_negative() || k <= *self.0.magnitude() => {
+                let mut result = I::one();
+                let mut i = &self.0 - I::from(k) + 1u8;
+                while i <= self.0 {
+                    result *= &i;
+                    i += 1u8;
+                }
+                Self(result)
+            }
+            _ => Self(I::zero()),
+        }
+    }
+    pub fn multinomial(ks: Vec<Self>) -> Self {
+        let mut result = Self(I::one());
+        let mut n = I::zero();
+        for k in ks {
+            if k.0.is_negative() {
+                return Self(I::zero());
+            }
+            n += &k.0;
+            result.0 *= Self(n.clone()).binomial(k).0;
+        }
+        result
+    }
+    pub fn stirling_first(self, k: Self) -> Option<Self> {
+        if self.0.is_negative() || k.0.is_negative() || k.0 > self.0 {
+            return Some(Self(I::zero()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let k = k.0.to_usize().unwrap();
+        let mut row = vec![I::zero(); k + 1];
+        row[0] = I::one();
+        for i in 0..n {
+            for j in (1..=k.min(i + 1)).rev() {
+                row[j] = &row[j - 1] - &row[j] * i;
+            }
+            row[0] = I::zero();
+        }
+        Some(Self(mem::take(&mut row[k])))
+    }
+    pub fn stirling_second(self, k: Self) -> Option<Self> {
+        if self.0.is_negative() || k.0.is_negative() || k.0 > self.0 {
+            return Some(Self(I::zero()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let k = k.0.to_usize().unwrap();
+        let mut row = vec![I::zero(); k + 1];
+        row[0] = I::one();
+        for i in 0..n {
+            for j in (1..=k.min(i + 1)).rev() {
+                row[j] = &row[j] * j + &row[j - 1];
+            }
+            row[0] = I::zero();
+        }
+        Some(Self(mem::take(&mut row[k])))
+    }
+    pub fn bell(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(I::zero()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let mut row = vec![I::one()];
+        for _ in 0..n {
+            let mut next = Vec::with_capacity(row.len() + 1);
+            next.push(row.last().unwrap().clone());
+            for x in row {
+                next.push(next.last().unwrap() + x);
+            }
+            row = next;
+        }
+        Some(Self(mem::take(&mut row[0])))
+    }
+    pub fn catalan(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(I::zero()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let c = UInteger::from(2 * n).binomial(UInteger::from(n)).0 / (n + 1);
+        Some(Self(c.into()))
+    }
+    pub fn partitions(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(I::zero()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let mut p = vec![I::one()];
+        for m in 1..=n {
+            let mut sum = I::zero();
+            for k in 1.. {
+                let g = k * (3 * k - 1) / 2;
+                if g > m {
+                    break;
+                }
+                let mut term = p[m - g].clone();
+                if g + k <= m {
+                    term += &p[m - g - k];
+                }
+                if k % 2 == 1 { sum += term } else { sum -= term }
+            }
+            p.push(sum);
+        }
+        Some(Self(mem::take(&mut p[n])))
     }
 }
 impl UInteger {
-    pub const fn binomial(self, k: Self) -> Self {
+    pub fn binomial(self, k: Self) -> Self {
         if k.0 > self.0 {
-            return Self(0);
+            return Self(U::zero());
         }
         let n = self.0;
-        let mut k = k.0;
-        if k > n - k {
-            k = n - k;
-        }
-        let r = n - k;
-        const fn gcd(mut a: U, mut b: U) -> U {
-            while b != 0 {
-                (a, b) = (b, a % b);
-            }
-            a
-        }
-        let mut result = 1;
-        let mut i = 1;
+        let k = (&n - &k.0).min(k.0);
+        let r = &n - &k;
+        let mut result = U::one();
+        let mut i = U::one();
         while i <= k {
-            let num = r + i;
-            let den = i;
-            let g = gcd(num, den);
-            result *= g;
-            result /= den;
-            result *= num;
-            result /= g;
-            i += 1;
+            result *= &r + &i;
+            result /= &i;
+            i += 1u8;
         }
-        Self(result as U)
+        Self(result)
     }
-    pub fn factorial(self) -> Self {
-        Self((1..=self.0).product())
+    pub fn factorial(self) -> Option<Self> {
+        let n = self.0.to_u64()?;
+        Some(Self((1..=n).map(U::from).product()))
     }
 }
-impl From<U> for UInteger {
-    fn from(value: U) -> Self {
-        Self(value)
+impl From<usize> for UInteger {
+    fn from(value: usize) -> Self {
+        Self(U::from(value))
     }
 }
-impl From<I> for Integer {
-    fn from(value: I) -> Self {
-        Self(value)
+impl From<isize> for Integer {
+    fn from(value: isize) -> Self {
+        Self(I::from(value))
     }
 }
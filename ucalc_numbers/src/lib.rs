@@ -1,7 +1,8 @@
 #![feature(f16)]
 #![feature(f128)]
-#![feature(float_gamma)]
-#![feature(float_erf)]
+#![cfg_attr(all(feature = "float", not(feature = "fastnum")), feature(float_gamma))]
+#![cfg_attr(all(feature = "float", not(feature = "fastnum")), feature(float_erf))]
+#![cfg_attr(feature = "fastnum", allow(long_running_const_eval))]
 #![feature(min_specialization)]
 #![allow(internal_features)]
 #![feature(rustc_attrs)]
@@ -11,13 +12,32 @@
 mod float;
 #[cfg(feature = "float_rand")]
 pub use rand;
+#[cfg(feature = "fastnum")]
+mod decimal;
+#[cfg(feature = "fastnum")]
+#[cfg(test)]
+mod decimal_test;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+#[cfg(feature = "dynamic")]
+#[cfg(test)]
+mod dynamic_test;
 #[cfg(feature = "float")]
+#[cfg(not(feature = "fastnum"))]
 #[cfg(test)]
 mod float_test;
 #[cfg(feature = "float")]
 mod integer;
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "rational")]
+#[cfg(test)]
+mod rational_test;
 #[cfg(feature = "rug")]
 pub mod rug;
+#[cfg(feature = "rug")]
+#[cfg(test)]
+mod rug_test;
 pub use traits::*;
 pub use types::*;
 mod impls;
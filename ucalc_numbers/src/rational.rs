@@ -0,0 +1,547 @@
+use crate::float::{F, Float};
+use crate::integer::{Integer, UInteger};
+use crate::{
+    Constant, FloatFunctions, FloatFunctionsMut, FloatTrait, FloatType, Pow, RealTrait, RealType,
+};
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+#[cfg(feature = "float_rand")]
+use rand::rngs::ThreadRng;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+const MAX_BITS: u64 = 1 << 16;
+const MAX_DENOM_BITS: u64 = 1 << 7;
+const MAX_ROOT: u32 = 64;
+const MAX_FACTORIAL: usize = 4096;
+#[derive(Clone)]
+pub enum Rational {
+    Exact(BigRational),
+    Float(Float),
+}
+impl FloatType<Self> for Rational {}
+impl RealType for Rational {}
+impl Default for Rational {
+    fn default() -> Self {
+        Self::Exact(BigRational::zero())
+    }
+}
+impl Rational {
+    pub fn to_float(&self) -> Float {
+        match self {
+            Self::Exact(r) => Float(r.to_f64().unwrap_or(F::NAN)),
+            Self::Float(f) => f.clone(),
+        }
+    }
+    fn float(&mut self, fun: impl FnOnce(&mut Float)) {
+        let mut f = self.to_float();
+        fun(&mut f);
+        *self = Self::from(f.0);
+    }
+    fn float_with(&mut self, other: &Self, fun: impl FnOnce(&mut Float, &Float)) {
+        let mut f = self.to_float();
+        fun(&mut f, &other.to_float());
+        *self = Self::from(f.0);
+    }
+    fn bound(&mut self) {
+        if let Self::Exact(r) = self
+            && (r.denom().bits() > MAX_DENOM_BITS || r.numer().bits() > MAX_BITS)
+        {
+            *self = Self::Float(self.to_float())
+        }
+    }
+    pub fn to_integer(&self) -> Option<Integer> {
+        match self {
+            Self::Exact(r) if r.is_integer() => Some(Integer(r.to_integer())),
+            Self::Exact(_) => None,
+            Self::Float(f) => f.to_integer(),
+        }
+    }
+    fn integer(&self) -> Option<usize> {
+        match self {
+            Self::Exact(r) if r.is_integer() => r.to_integer().to_usize(),
+            _ => None,
+        }
+    }
+}
+#[cfg(feature = "float_rand")]
+impl Rational {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
+        self.float_with(&b, |a, b| a.random_range_mut(b.clone(), thread_rng))
+    }
+}
+fn root(r: &BigRational, n: u32) -> Option<BigRational> {
+    if r.is_negative() && n.is_multiple_of(2) {
+        return None;
+    }
+    let (numer, denom) = (r.numer().nth_root(n), r.denom().nth_root(n));
+    (numer.pow(n) == *r.numer() && denom.pow(n) == *r.denom())
+        .then(|| BigRational::new_raw(numer, denom))
+}
+fn pow(base: &BigRational, exp: &BigRational) -> Option<BigRational> {
+    let q = exp.denom().to_u32().filter(|q| *q <= MAX_ROOT)?;
+    let p = exp.numer().to_i32()?;
+    let base = if q == 1 { base.clone() } else { root(base, q)? };
+    if (base.is_zero() && p <= 0)
+        || (base.numer().bits() + base.denom().bits()) * p.unsigned_abs() as u64 > MAX_BITS
+    {
+        return None;
+    }
+    Some(base.pow(p))
+}
+impl Debug for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(r) => write!(f, "{r}"),
+            Self::Float(a) => write!(f, "{a:?}"),
+        }
+    }
+}
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(r) => write!(f, "{r}"),
+            Self::Float(a) => write!(f, "{a}"),
+        }
+    }
+}
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, s| sum + s)
+    }
+}
+impl Product for Rational {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from(1), |sum, s| sum * s)
+    }
+}
+impl PartialEq for Rational {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => a == b,
+            _ => self.to_float() == other.to_float(),
+        }
+    }
+}
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => a.partial_cmp(b),
+            _ => self.to_float().partial_cmp(&other.to_float()),
+        }
+    }
+}
+impl RealTrait<Rational> for Rational {
+    fn is_sign_negative(&self) -> bool {
+        match self {
+            Self::Exact(r) => r.is_negative(),
+            Self::Float(f) => f.is_sign_negative(),
+        }
+    }
+    fn is_sign_positive(&self) -> bool {
+        match self {
+            Self::Exact(r) => !r.is_negative(),
+            Self::Float(f) => f.is_sign_positive(),
+        }
+    }
+    fn is_nan(&self) -> bool {
+        match self {
+            Self::Exact(_) => false,
+            Self::Float(f) => f.is_nan(),
+        }
+    }
+    fn into_isize(self) -> isize {
+        match self {
+            Self::Exact(r) => r.to_integer().to_isize().unwrap_or(if r.is_negative() {
+                isize::MIN
+            } else {
+                isize::MAX
+            }),
+            Self::Float(f) => f.into_isize(),
+        }
+    }
+    fn into_usize(self) -> usize {
+        match self {
+            Self::Exact(r) => {
+                r.to_integer()
+                    .to_usize()
+                    .unwrap_or(if r.is_negative() { 0 } else { usize::MAX })
+            }
+            Self::Float(f) => f.into_usize(),
+        }
+    }
+    fn closest_fraction(&self) -> Option<(bool, usize, usize)> {
+        match self {
+            Self::Exact(r) if r.is_integer() => None,
+            Self::Exact(r) => Some((
+                !r.is_negative(),
+                r.numer().abs().to_usize()?,
+                r.denom().to_usize()?,
+            )),
+            Self::Float(f) => f.closest_fraction(),
+        }
+    }
+}
+impl FloatFunctionsMut<Rational> for Rational {
+    fn sin_mut(&mut self) {
+        self.float(|a| a.sin_mut())
+    }
+    fn cos_mut(&mut self) {
+        self.float(|a| a.cos_mut())
+    }
+    fn asin_mut(&mut self) {
+        self.float(|a| a.asin_mut())
+    }
+    fn acos_mut(&mut self) {
+        self.float(|a| a.acos_mut())
+    }
+    fn sinh_mut(&mut self) {
+        self.float(|a| a.sinh_mut())
+    }
+    fn cosh_mut(&mut self) {
+        self.float(|a| a.cosh_mut())
+    }
+    fn asinh_mut(&mut self) {
+        self.float(|a| a.asinh_mut())
+    }
+    fn acosh_mut(&mut self) {
+        self.float(|a| a.acosh_mut())
+    }
+    fn tan_mut(&mut self) {
+        self.float(|a| a.tan_mut())
+    }
+    fn tanh_mut(&mut self) {
+        self.float(|a| a.tanh_mut())
+    }
+    fn atan_mut(&mut self) {
+        self.float(|a| a.atan_mut())
+    }
+    fn atanh_mut(&mut self) {
+        self.float(|a| a.atanh_mut())
+    }
+    fn ln_mut(&mut self) {
+        self.float(|a| a.ln_mut())
+    }
+    fn exp_mut(&mut self) {
+        self.float(|a| a.exp_mut())
+    }
+    fn hypot_mut(&mut self, other: &Self) {
+        if let (Self::Exact(a), Self::Exact(b)) = (&*self, other)
+            && let Some(r) = root(&(a * a + b * b), 2)
+        {
+            *self = Self::Exact(r)
+        } else {
+            self.float_with(other, |a, b| a.hypot_mut(b))
+        }
+    }
+    fn atan2_mut(&mut self, other: &Self) {
+        self.float_with(other, |a, b| a.atan2_mut(b))
+    }
+    fn min_mut(&mut self, other: &Self) {
+        match (&*self, other) {
+            (Self::Exact(a), Self::Exact(b)) => {
+                if b < a {
+                    *self = other.clone()
+                }
+            }
+            _ => self.float_with(other, |a, b| a.min_mut(b)),
+        }
+    }
+    fn max_mut(&mut self, other: &Self) {
+        match (&*self, other) {
+            (Self::Exact(a), Self::Exact(b)) => {
+                if b > a {
+                    *self = other.clone()
+                }
+            }
+            _ => self.float_with(other, |a, b| a.max_mut(b)),
+        }
+    }
+    fn recip_mut(&mut self) {
+        match self {
+            Self::Exact(r) if !r.is_zero() => *r = r.recip(),
+            _ => self.float(|a| a.recip_mut()),
+        }
+    }
+    fn sqrt_mut(&mut self) {
+        if let Self::Exact(r) = self
+            && let Some(r) = root(r, 2)
+        {
+            *self = Self::Exact(r)
+        } else {
+            self.float(|a| a.sqrt_mut())
+        }
+    }
+    fn cbrt_mut(&mut self) {
+        if let Self::Exact(r) = self
+            && let Some(r) = root(r, 3)
+        {
+            *self = Self::Exact(r)
+        } else {
+            self.float(|a| a.cbrt_mut())
+        }
+    }
+    fn abs_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.abs(),
+            Self::Float(f) => f.abs_mut(),
+        }
+    }
+    fn gamma_mut(&mut self) {
+        match self.integer() {
+            Some(n) if (1..=MAX_FACTORIAL).contains(&n) => {
+                *self = Self::from(UInteger::from(n - 1).factorial().unwrap().0)
+            }
+            _ => self.float(|a| a.gamma_mut()),
+        }
+    }
+    fn erf_mut(&mut self) {
+        self.float(|a| a.erf_mut())
+    }
+    fn erfc_mut(&mut self) {
+        self.float(|a| a.erfc_mut())
+    }
+    fn round_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.round(),
+            Self::Float(f) => f.round_mut(),
+        }
+    }
+    fn ceil_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.ceil(),
+            Self::Float(f) => f.ceil_mut(),
+        }
+    }
+    fn floor_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.floor(),
+            Self::Float(f) => f.floor_mut(),
+        }
+    }
+    fn trunc_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.trunc(),
+            Self::Float(f) => f.trunc_mut(),
+        }
+    }
+    fn fract_mut(&mut self) {
+        match self {
+            Self::Exact(r) => *r = r.fract(),
+            Self::Float(f) => f.fract_mut(),
+        }
+    }
+    fn tetration_mut(&mut self, other: &Self) {
+        let mut ret = Self::from(1);
+        for _ in 0..other.clone().round().into_usize() {
+            ret = self.clone().pow(ret);
+            if let Self::Float(f) = &ret
+                && !f.0.is_finite()
+            {
+                break;
+            }
+        }
+        *self = ret
+    }
+    fn subfactorial_mut(&mut self) {
+        match self.integer() {
+            Some(n) if n <= MAX_FACTORIAL => {
+                let mut ret = BigInt::one();
+                for i in 1..=n {
+                    ret *= i;
+                    if i.is_multiple_of(2) {
+                        ret += 1
+                    } else {
+                        ret -= 1
+                    }
+                }
+                *self = Self::Exact(BigRational::from_integer(ret))
+            }
+            _ => self.float(|a| a.subfactorial_mut()),
+        }
+    }
+}
+impl FloatTrait<Rational> for Rational {
+    fn to_real(self) -> Self {
+        self
+    }
+    fn real(&self) -> &Self {
+        self
+    }
+    fn real_mut(&mut self) -> &mut Self {
+        self
+    }
+    fn is_zero(&self) -> bool {
+        match self {
+            Self::Exact(r) => r.is_zero(),
+            Self::Float(f) => f.is_zero(),
+        }
+    }
+    fn parse_radix(src: &str, base: u8) -> Option<Self> {
+        let (int, fract) = src.split_once('.').unwrap_or((src, ""));
+        if int.len() + fract.len() != 0
+            && int
+                .chars()
+                .chain(fract.chars())
+                .all(|c| c.is_digit(base as u32))
+        {
+            let numer = BigInt::parse_bytes([int, fract].concat().as_bytes(), base as u32)?;
+            let denom = BigInt::from(base).pow(fract.len() as u32);
+            Some(Self::Exact(BigRational::new(numer, denom)))
+        } else {
+            Float::parse_radix(src, base).map(|f| Self::from(f.0))
+        }
+    }
+    fn to_string_radix(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| match self {
+            Self::Exact(r) if r.is_integer() => {
+                write!(fmt, "{}", r.numer().to_str_radix(base as u32))
+            }
+            Self::Exact(r) => write!(
+                fmt,
+                "{}/{}",
+                r.numer().to_str_radix(base as u32),
+                r.denom().to_str_radix(base as u32)
+            ),
+            Self::Float(f) => write!(fmt, "{}", f.to_string_radix(base)),
+        })
+    }
+    fn get_closest_fraction(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| match self {
+            Self::Exact(_) => Ok(()),
+            Self::Float(f) => write!(fmt, "{}", f.get_closest_fraction(base)),
+        })
+    }
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => a.cmp(b),
+            _ => self.to_float().total_cmp(&other.to_float()),
+        }
+    }
+}
+macro_rules! ops_assign {
+    ($assign:ident, $orig:ident, $assign_fun:ident, $orig_fun:ident, $zero:literal) => {
+        impl<'a> $assign<&'a Rational> for Rational {
+            fn $assign_fun(&mut self, rhs: &'a Rational) {
+                match (&mut *self, rhs) {
+                    (Self::Exact(a), Self::Exact(b)) if $zero || !b.is_zero() => {
+                        $assign::$assign_fun(a, b);
+                        self.bound()
+                    }
+                    _ => self.float_with(rhs, |a, b| $assign::$assign_fun(a, b)),
+                }
+            }
+        }
+        impl $assign<Rational> for Rational {
+            fn $assign_fun(&mut self, rhs: Rational) {
+                $assign::$assign_fun(self, &rhs)
+            }
+        }
+        impl<'a> $orig<&'a Rational> for Rational {
+            type Output = Rational;
+            fn $orig_fun(mut self, rhs: &'a Rational) -> Rational {
+                $assign::$assign_fun(&mut self, rhs);
+                self
+            }
+        }
+        impl $orig<Rational> for Rational {
+            type Output = Rational;
+            fn $orig_fun(mut self, rhs: Rational) -> Rational {
+                $assign::$assign_fun(&mut self, &rhs);
+                self
+            }
+        }
+    };
+}
+ops_assign!(AddAssign, Add, add_assign, add, true);
+ops_assign!(SubAssign, Sub, sub_assign, sub, true);
+ops_assign!(MulAssign, Mul, mul_assign, mul, true);
+ops_assign!(DivAssign, Div, div_assign, div, false);
+ops_assign!(RemAssign, Rem, rem_assign, rem, false);
+impl Pow<Rational> for Rational {
+    type Output = Rational;
+    fn pow(self, rhs: Rational) -> Self {
+        self.pow(&rhs)
+    }
+}
+impl Pow<&Rational> for Rational {
+    type Output = Rational;
+    fn pow(mut self, rhs: &Rational) -> Self {
+        if let (Self::Exact(a), Self::Exact(b)) = (&self, rhs)
+            && let Some(r) = pow(a, b)
+        {
+            Self::Exact(r)
+        } else {
+            self.float_with(rhs, |a, b| *a = a.clone().pow(b));
+            self
+        }
+    }
+}
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        match self {
+            Self::Exact(r) => Self::Exact(-r),
+            Self::Float(f) => Self::Float(-f),
+        }
+    }
+}
+impl From<Constant> for Rational {
+    fn from(value: Constant) -> Self {
+        Self::Float(Float::from(value))
+    }
+}
+impl<'a> From<&'a Rational> for Rational {
+    fn from(value: &'a Rational) -> Self {
+        value.clone()
+    }
+}
+impl From<F> for Rational {
+    fn from(value: F) -> Self {
+        match BigRational::from_float(value) {
+            Some(r) if r.denom().bits() <= 32 && r.numer().bits() <= 64 => Self::Exact(r),
+            _ => Self::Float(Float(value)),
+        }
+    }
+}
+impl From<bool> for Rational {
+    fn from(value: bool) -> Self {
+        Self::from(value as u8)
+    }
+}
+macro_rules! from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Rational {
+                fn from(value: $ty) -> Self {
+                    Self::Exact(BigRational::from_integer(BigInt::from(value)))
+                }
+            }
+        )*
+    };
+}
+from_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, BigInt, BigUint
+);
+impl From<Integer> for Rational {
+    fn from(value: Integer) -> Self {
+        Self::from(value.0)
+    }
+}
+macro_rules! from_float {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Rational {
+                fn from(value: $ty) -> Self {
+                    Self::from(value as F)
+                }
+            }
+        )*
+    };
+}
+from_float!(f16, f32, f128);
@@ -0,0 +1,41 @@
+use crate::rational::Rational;
+use crate::{FloatFunctions, FloatTrait, Pow};
+fn parse(src: &str) -> Rational {
+    Rational::parse_radix(src, 10).unwrap()
+}
+#[test]
+fn test_exact() {
+    let a = Rational::from(1) / Rational::from(3) + Rational::from(1) / Rational::from(6);
+    assert!(matches!(a, Rational::Exact(_)));
+    assert_eq!(a.to_string(), "1/2");
+    assert_eq!(a.to_string_radix(2).to_string(), "1/10");
+    assert_eq!((parse("0.1") + parse("0.2")).to_string(), "3/10");
+    assert_eq!(parse("1.25").to_string(), "5/4");
+}
+#[test]
+fn test_pow() {
+    assert_eq!(
+        Rational::from(2).pow(Rational::from(200)).to_string(),
+        "1606938044258990275541962092341162602522202993782792835301376"
+    );
+    assert_eq!(
+        parse("0.25").pow(Rational::from(-1) / Rational::from(2)),
+        Rational::from(2)
+    );
+    assert_eq!(parse("2.25").sqrt().to_string(), "3/2");
+    assert_eq!(Rational::from(-27).cbrt(), Rational::from(-3));
+    assert!(matches!(Rational::from(2).sqrt(), Rational::Float(_)));
+}
+#[test]
+fn test_functions() {
+    assert_eq!(Rational::from(6).gamma(), Rational::from(120));
+    assert_eq!(
+        Rational::from(51).gamma().to_string(),
+        "30414093201713378043612608166064768844377641568960512000000000000"
+    );
+    assert_eq!(Rational::from(4).subfactorial(), Rational::from(9));
+    assert_eq!(parse("-2.5").round(), Rational::from(-3));
+    assert_eq!(parse("-2.5").fract().to_string(), "-1/2");
+    assert_eq!(Rational::from(0).exp(), Rational::from(1));
+    assert_eq!((Rational::from(1) / Rational::from(0)).to_string(), "inf");
+}
@@ -1 +1,1352 @@
-
+use crate::{
+    ComplexFunctions, ComplexFunctionsMut, ComplexTrait, ComplexType, Constant, FloatFunctions,
+    FloatFunctionsMut, FloatTrait, FloatType, NegAssign, Pow, RealTrait, RealType,
+};
+#[cfg(feature = "float_rand")]
+use rand::RngExt;
+#[cfg(feature = "float_rand")]
+use rand::rngs::ThreadRng;
+use rug::Assign;
+use rug::float::Special;
+use rug::integer::IsPrime;
+use rug::ops::PowAssign;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::sync::atomic::{AtomicU32, Ordering as AtomicOrdering};
+use std::{fmt, mem};
+static PRECISION: AtomicU32 = AtomicU32::new(256);
+pub fn precision() -> u32 {
+    PRECISION.load(AtomicOrdering::Relaxed)
+}
+pub fn set_precision(prec: u32) -> bool {
+    if !(rug::float::prec_min()..=rug::float::prec_max()).contains(&prec) {
+        return false;
+    }
+    PRECISION.store(prec, AtomicOrdering::Relaxed);
+    true
+}
+#[derive(Clone, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Float(pub rug::Float);
+#[derive(Clone, PartialEq)]
+#[repr(transparent)]
+pub struct Complex(pub rug::Complex);
+#[derive(Clone, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Integer(pub rug::Integer);
+#[derive(Clone, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct UInteger(pub rug::Integer);
+impl FloatType<Float> for Complex {}
+impl FloatType<Self> for Float {}
+impl ComplexType<Float> for Complex {}
+impl RealType for Float {}
+fn cast(f: &rug::Float) -> &Float {
+    unsafe { &*(f as *const rug::Float as *const Float) }
+}
+fn cast_mut(f: &mut rug::Float) -> &mut Float {
+    unsafe { &mut *(f as *mut rug::Float as *mut Float) }
+}
+impl Default for Float {
+    fn default() -> Self {
+        Self(rug::Float::new(precision()))
+    }
+}
+impl Default for Complex {
+    fn default() -> Self {
+        Self(rug::Complex::new(precision()))
+    }
+}
+const TRIAL_LIMIT: u32 = 1024;
+const RHO_BATCH: usize = 128;
+const MAX_COMBINATORIAL: usize = 1 << 10;
+fn is_prime(n: &rug::Integer) -> bool {
+    n.is_probably_prime(32) != IsPrime::No
+}
+fn pollard_rho(n: &rug::Integer) -> rug::Integer {
+    let diff = |a: &rug::Integer, b: &rug::Integer| rug::Integer::from(a - b).abs();
+    let mut c = 1u32;
+    loop {
+        let f = |x: &rug::Integer| (x.clone().square() + c) % n;
+        let mut y = rug::Integer::from(2);
+        let mut x = y.clone();
+        let mut ys = y.clone();
+        let mut q = rug::Integer::from(1);
+        let mut d = rug::Integer::from(1);
+        let mut r = 1;
+        while d == 1 {
+            x.clone_from(&y);
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && d == 1 {
+                ys.clone_from(&y);
+                for _ in 0..RHO_BATCH.min(r - k) {
+                    y = f(&y);
+                    q = q * diff(&x, &y) % n;
+                }
+                d = q.clone().gcd(n);
+                k += RHO_BATCH;
+            }
+            r *= 2;
+        }
+        if d == *n {
+            d = loop {
+                ys = f(&ys);
+                let d = diff(&x, &ys).gcd(n);
+                if d != 1 {
+                    break d;
+                }
+            }
+        }
+        if d != *n {
+            return d;
+        }
+        c += 1;
+    }
+}
+fn factor_into(n: rug::Integer, factors: &mut Vec<rug::Integer>) {
+    if is_prime(&n) {
+        factors.push(n)
+    } else if n != 1 {
+        let d = pollard_rho(&n);
+        factor_into(rug::Integer::from(n.div_exact_ref(&d)), factors);
+        factor_into(d, factors);
+    }
+}
+fn factor(mut n: rug::Integer) -> Vec<rug::Integer> {
+    let mut factors = Vec::new();
+    let mut p = 2u32;
+    while p < TRIAL_LIMIT && n >= p * p {
+        while n.is_divisible_u(p) {
+            n.div_exact_u_mut(p);
+            factors.push(rug::Integer::from(p));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > TRIAL_LIMIT * TRIAL_LIMIT {
+        let start = factors.len();
+        factor_into(n, &mut factors);
+        factors[start..].sort();
+    } else if n != 1 {
+        factors.push(n);
+    }
+    factors
+}
+fn prime_powers(n: rug::Integer) -> Vec<(rug::Integer, u32)> {
+    let mut powers: Vec<(rug::Integer, u32)> = Vec::new();
+    for p in factor(n) {
+        match powers.last_mut() {
+            Some((q, k)) if *q == p => *k += 1,
+            _ => powers.push((p, 1)),
+        }
+    }
+    powers
+}
+fn prime_power(p: &rug::Integer, k: u32) -> rug::Integer {
+    let mut pow = p.clone();
+    pow.pow_assign(k);
+    pow
+}
+impl Integer {
+    pub fn gcd(self, other: Self) -> Self {
+        Self(self.0.gcd(&other.0))
+    }
+    pub fn lcm(self, other: Self) -> Self {
+        Self(self.0.lcm(&other.0))
+    }
+    pub fn is_prime(&self) -> bool {
+        is_prime(&self.0)
+    }
+    pub fn next_prime(self) -> Self {
+        Self(self.0.next_prime())
+    }
+    pub fn factor(self) -> Vec<Self> {
+        if self.0 == 0 || self.0 == 1 {
+            return vec![self];
+        }
+        let mut factors = Vec::new();
+        if self.0.is_negative() {
+            factors.push(Self(rug::Integer::from(-1)))
+        }
+        factors.extend(factor(self.0.abs()).into_iter().map(Self));
+        factors
+    }
+    pub fn modpow(self, exp: Self, modulus: Self) -> Option<Self> {
+        let m = modulus.0.abs();
+        if m == 0 {
+            return None;
+        }
+        self.0.pow_mod(&exp.0, &m).ok().map(Self)
+    }
+    pub fn modinv(self, modulus: Self) -> Option<Self> {
+        let m = modulus.0.abs();
+        if m == 0 {
+            return None;
+        }
+        self.0.invert(&m).ok().map(Self)
+    }
+    pub fn totient(self) -> Self {
+        if self.0 <= 0 {
+            return Self(rug::Integer::new());
+        }
+        Self(
+            prime_powers(self.0)
+                .into_iter()
+                .map(|(p, k)| prime_power(&p, k - 1) * (p - 1u32))
+                .product(),
+        )
+    }
+    pub fn divisors(self) -> Vec<Self> {
+        if self.0 == 0 {
+            return Vec::new();
+        }
+        let mut divisors = vec![rug::Integer::from(1)];
+        for (p, k) in prime_powers(self.0.abs()) {
+            let len = divisors.len();
+            let mut pow = rug::Integer::from(1);
+            for _ in 0..k {
+                pow *= &p;
+                for i in 0..len {
+                    divisors.push(rug::Integer::from(&divisors[i] * &pow));
+                }
+            }
+        }
+        divisors.sort();
+        divisors.into_iter().map(Self).collect()
+    }
+    pub fn sigma(self) -> Self {
+        if self.0 == 0 {
+            return self;
+        }
+        Self(
+            prime_powers(self.0.abs())
+                .into_iter()
+                .map(|(p, k)| (prime_power(&p, k + 1) - 1u32) / (p - 1u32))
+                .product(),
+        )
+    }
+    pub fn binomial(self, k: Self) -> Self {
+        match k.0.to_u32() {
+            Some(k) => Self(self.0.binomial(k)),
+            _ => Self(rug::Integer::new()),
+        }
+    }
+    pub fn factorial(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(rug::Integer::from(1)));
+        }
+        Some(Self(rug::Integer::from(rug::Integer::factorial(
+            self.0.to_u32()?,
+        ))))
+    }
+    pub fn perm(self, k: Self) -> Self {
+        if k.0 < 0 || (self.0 >= 0 && k.0 > self.0) {
+            return Self(rug::Integer::new());
+        }
+        let mut result = rug::Integer::from(1);
+        let mut i = self.0.clone() - k.0 + 1u32;
+        while i <= self.0 {
+            result *= &i;
+            i += 1u32;
+        }
+        Self(result)
+    }
+    pub fn multinomial(ks: Vec<Self>) -> Self {
+        let mut result = rug::Integer::from(1);
+        let mut n = rug::Integer::new();
+        for k in ks {
+            if k.0 < 0 {
+                return Self(rug::Integer::new());
+            }
+            n += &k.0;
+            result *= Self(n.clone()).binomial(k).0;
+        }
+        Self(result)
+    }
+    pub fn stirling_first(self, k: Self) -> Option<Self> {
+        if self.0.is_negative() || k.0.is_negative() || k.0 > self.0 {
+            return Some(Self(rug::Integer::new()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let k = k.0.to_usize().unwrap();
+        let mut row = vec![rug::Integer::new(); k + 1];
+        row[0] = rug::Integer::from(1);
+        for i in 0..n {
+            for j in (1..=k.min(i + 1)).rev() {
+                row[j] = &row[j - 1] - rug::Integer::from(&row[j] * i);
+            }
+            row[0] = rug::Integer::new();
+        }
+        Some(Self(mem::take(&mut row[k])))
+    }
+    pub fn stirling_second(self, k: Self) -> Option<Self> {
+        if self.0.is_negative() || k.0.is_negative() || k.0 > self.0 {
+            return Some(Self(rug::Integer::new()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let k = k.0.to_usize().unwrap();
+        let mut row = vec![rug::Integer::new(); k + 1];
+        row[0] = rug::Integer::from(1);
+        for i in 0..n {
+            for j in (1..=k.min(i + 1)).rev() {
+                row[j] = rug::Integer::from(&row[j] * j) + &row[j - 1];
+            }
+            row[0] = rug::Integer::new();
+        }
+        Some(Self(mem::take(&mut row[k])))
+    }
+    pub fn bell(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(rug::Integer::new()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let mut row = vec![rug::Integer::from(1)];
+        for _ in 0..n {
+            let mut next = Vec::with_capacity(row.len() + 1);
+            next.push(row.last().unwrap().clone());
+            for x in row {
+                next.push(x + next.last().unwrap());
+            }
+            row = next;
+        }
+        Some(Self(mem::take(&mut row[0])))
+    }
+    pub fn catalan(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(rug::Integer::new()));
+        }
+        let n = self
+            .0
+            .to_u32()
+            .filter(|n| *n as usize <= MAX_COMBINATORIAL)?;
+        Some(Self(rug::Integer::from(2 * n).binomial(n) / (n + 1)))
+    }
+    pub fn partitions(self) -> Option<Self> {
+        if self.0.is_negative() {
+            return Some(Self(rug::Integer::new()));
+        }
+        let n = self.0.to_usize().filter(|n| *n <= MAX_COMBINATORIAL)?;
+        let mut p = vec![rug::Integer::from(1)];
+        for m in 1..=n {
+            let mut sum = rug::Integer::new();
+            for k in 1.. {
+                let g = k * (3 * k - 1) / 2;
+                if g > m {
+                    break;
+                }
+                let mut term = p[m - g].clone();
+                if g + k <= m {
+                    term += &p[m - g - k];
+                }
+                if k % 2 == 1 { sum += term } else { sum -= term }
+            }
+            p.push(sum);
+        }
+        Some(Self(mem::take(&mut p[n])))
+    }
+}
+impl UInteger {
+    pub fn binomial(self, k: Self) -> Self {
+        if k.0 > self.0 {
+            return Self(rug::Integer::new());
+        }
+        Self(self.0.binomial(k.0.to_u32().unwrap_or(u32::MAX)))
+    }
+    pub fn factorial(self) -> Option<Self> {
+        Some(Self(rug::Integer::from(rug::Integer::factorial(
+            self.0.to_u32()?,
+        ))))
+    }
+}
+impl From<usize> for UInteger {
+    fn from(value: usize) -> Self {
+        Self(rug::Integer::from(value))
+    }
+}
+impl From<isize> for Integer {
+    fn from(value: isize) -> Self {
+        Self(rug::Integer::from(value))
+    }
+}
+impl From<Integer> for Float {
+    fn from(value: Integer) -> Self {
+        Self::from(value.0)
+    }
+}
+impl From<rug::Integer> for Float {
+    fn from(value: rug::Integer) -> Self {
+        Self(rug::Float::with_val(precision(), value))
+    }
+}
+impl Float {
+    pub fn to_integer(&self) -> Option<Integer> {
+        self.0
+            .to_integer()
+            .filter(|_| self.0.is_integer())
+            .map(Integer)
+    }
+}
+#[cfg(feature = "float_rand")]
+impl Float {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
+        if *self != b {
+            let t = thread_rng.random_range(0.0..=1.0f64);
+            *self += (b - &*self) * Float::from(t);
+        }
+    }
+}
+#[cfg(feature = "float_rand")]
+impl Complex {
+    pub fn random_range_mut(&mut self, b: Self, thread_rng: &mut ThreadRng) {
+        let (real, imag) = b.to_real_imag();
+        self.real_mut().random_range_mut(real, thread_rng);
+        self.imag_mut().random_range_mut(imag, thread_rng);
+    }
+}
+fn positional(s: String) -> String {
+    let (sign, s) = s.split_at(usize::from(s.starts_with('-')));
+    let (mantissa, exp) = s
+        .find(['e', '@'])
+        .map(|i| (&s[..i], s[i + 1..].parse::<isize>().unwrap_or(0)))
+        .unwrap_or((s, 0));
+    let point = mantissa.find('.').unwrap_or(mantissa.len()) as isize + exp;
+    let digits = mantissa.replace('.', "");
+    let digits = digits.trim_end_matches('0');
+    let digits = if digits.is_empty() { "0" } else { digits };
+    if point <= -8 || point > digits.len().max(precision() as usize / 4) as isize {
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{sign}{first}e{}", point - 1)
+        } else {
+            format!("{sign}{first}.{rest}e{}", point - 1)
+        }
+    } else if point <= 0 {
+        format!("{sign}0.{}{digits}", "0".repeat(-point as usize))
+    } else if point as usize >= digits.len() {
+        format!(
+            "{sign}{digits}{}",
+            "0".repeat(point as usize - digits.len())
+        )
+    } else {
+        let (int, fract) = digits.split_at(point as usize);
+        format!("{sign}{int}.{fract}")
+    }
+}
+fn digits(base: u8) -> usize {
+    (precision() as f64 / (base as f64).log2()).floor() as usize
+}
+impl Debug for Float {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+impl Debug for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}{}{:?}i",
+            self.real(),
+            if self.imag().is_sign_positive() {
+                "+"
+            } else {
+                ""
+            },
+            self.imag()
+        )
+    }
+}
+impl Debug for Integer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+impl Debug for UInteger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(10))
+    }
+}
+impl Display for Float {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(10))
+    }
+}
+impl Sum for Complex {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, s| sum + s)
+    }
+}
+impl Product for Complex {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from(1), |sum, s| sum * s)
+    }
+}
+impl Sum for Float {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, s| sum + s)
+    }
+}
+impl Product for Float {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from(1), |sum, s| sum * s)
+    }
+}
+fn saturate(value: &rug::Float, min: rug::Integer, max: rug::Integer) -> rug::Integer {
+    if value.is_nan() {
+        rug::Integer::new()
+    } else if let Some(i) = value.clone().trunc().to_integer() {
+        Ord::clamp(i, min, max)
+    } else if value.is_sign_negative() {
+        min
+    } else {
+        max
+    }
+}
+impl RealTrait<Float> for Float {
+    fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+    fn is_sign_positive(&self) -> bool {
+        self.0.is_sign_positive()
+    }
+    fn is_nan(&self) -> bool {
+        self.0.is_nan()
+    }
+    fn into_isize(self) -> isize {
+        saturate(&self.0, isize::MIN.into(), isize::MAX.into())
+            .to_isize()
+            .unwrap()
+    }
+    fn into_usize(self) -> usize {
+        saturate(&self.0, usize::MIN.into(), usize::MAX.into())
+            .to_usize()
+            .unwrap()
+    }
+    fn closest_fraction(&self) -> Option<(bool, usize, usize)> {
+        if !self.0.is_finite() {
+            return None;
+        }
+        let is_positive = self.is_sign_positive();
+        let orig = self.0.to_f64().abs();
+        let mut num = orig;
+        let mut mult = 1.0;
+        for _ in 0..64 {
+            let recip = num.recip();
+            let fract = recip.fract();
+            mult *= recip;
+            num = fract;
+            if fract < 1e-8 {
+                let numerator = (orig * mult) as usize;
+                let denominator = mult as usize;
+                if denominator <= 1 || mult >= usize::MAX as f64 {
+                    return None;
+                }
+                return Some((is_positive, numerator, denominator));
+            }
+        }
+        None
+    }
+}
+impl FloatFunctionsMut<Float> for Float {
+    fn sin_mut(&mut self) {
+        self.0.sin_mut();
+    }
+    fn cos_mut(&mut self) {
+        self.0.cos_mut();
+    }
+    fn asin_mut(&mut self) {
+        self.0.asin_mut();
+    }
+    fn acos_mut(&mut self) {
+        self.0.acos_mut();
+    }
+    fn sinh_mut(&mut self) {
+        self.0.sinh_mut();
+    }
+    fn cosh_mut(&mut self) {
+        self.0.cosh_mut();
+    }
+    fn asinh_mut(&mut self) {
+        self.0.asinh_mut();
+    }
+    fn acosh_mut(&mut self) {
+        self.0.acosh_mut();
+    }
+    fn tan_mut(&mut self) {
+        self.0.tan_mut()
+    }
+    fn tanh_mut(&mut self) {
+        self.0.tanh_mut()
+    }
+    fn atan_mut(&mut self) {
+        self.0.atan_mut()
+    }
+    fn atanh_mut(&mut self) {
+        self.0.atanh_mut()
+    }
+    fn ln_mut(&mut self) {
+        self.0.ln_mut();
+    }
+    fn exp_mut(&mut self) {
+        self.0.exp_mut();
+    }
+    fn hypot_mut(&mut self, other: &Self) {
+        self.0.hypot_mut(&other.0);
+    }
+    fn atan2_mut(&mut self, other: &Self) {
+        self.0.atan2_mut(&other.0);
+    }
+    fn min_mut(&mut self, other: &Self) {
+        self.0.min_mut(&other.0);
+    }
+    fn max_mut(&mut self, other: &Self) {
+        self.0.max_mut(&other.0);
+    }
+    fn recip_mut(&mut self) {
+        self.0.recip_mut();
+    }
+    fn sqrt_mut(&mut self) {
+        self.0.sqrt_mut();
+    }
+    fn cbrt_mut(&mut self) {
+        self.0.cbrt_mut();
+    }
+    fn abs_mut(&mut self) {
+        self.0.abs_mut();
+    }
+    fn gamma_mut(&mut self) {
+        self.0.gamma_mut();
+    }
+    fn erf_mut(&mut self) {
+        self.0.erf_mut();
+    }
+    fn erfc_mut(&mut self) {
+        self.0.erfc_mut();
+    }
+    fn round_mut(&mut self) {
+        self.0.round_mut();
+    }
+    fn ceil_mut(&mut self) {
+        self.0.ceil_mut();
+    }
+    fn floor_mut(&mut self) {
+        self.0.floor_mut();
+    }
+    fn trunc_mut(&mut self) {
+        self.0.trunc_mut();
+    }
+    fn fract_mut(&mut self) {
+        self.0.fract_mut();
+    }
+    fn tetration_mut(&mut self, other: &Self) {
+        fn tetration(a: Float, other: &Float) -> Float {
+            let other = other.clone().round();
+            if other.0 <= 0 {
+                Float::from(1)
+            } else {
+                a.clone().pow(a.tetration(&(other - Float::from(1))))
+            }
+        }
+        //TODO
+        *self = tetration(self.clone(), other)
+    }
+    fn subfactorial_mut(&mut self) {
+        //TODO
+        if self.is_zero() {
+            *self = Self::from(1);
+        } else {
+            *self = ((self.clone() + Float::from(1)).gamma() / Float::from(Constant::E)).round()
+        }
+    }
+}
+impl FloatTrait<Float> for Float {
+    fn to_real(self) -> Self {
+        self
+    }
+    fn real(&self) -> &Self {
+        self
+    }
+    fn real_mut(&mut self) -> &mut Self {
+        self
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+    fn parse_radix(src: &str, base: u8) -> Option<Self> {
+        rug::Float::parse_radix(src, base as i32)
+            .ok()
+            .map(|f| Self(rug::Float::with_val(precision(), f)))
+    }
+    fn to_string_radix(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| {
+            if self.0.is_finite() && !self.0.is_zero() {
+                write!(
+                    fmt,
+                    "{}",
+                    positional(self.0.to_string_radix(base as i32, Some(digits(base))))
+                )
+            } else if self.0.is_nan() {
+                write!(fmt, "NaN")
+            } else if self.0.is_infinite() {
+                write!(fmt, "{}inf", if self.is_sign_negative() { "-" } else { "" })
+            } else {
+                write!(fmt, "0")
+            }
+        })
+    }
+    fn get_closest_fraction(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| {
+            if let Some((sign, _, num, den)) = closest_fraction(self, base) {
+                if sign {
+                    writeln!(fmt, "{num}/{den}")?;
+                } else {
+                    writeln!(fmt, "-{num}/{den}")?;
+                }
+            }
+            Ok(())
+        })
+    }
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+impl ComplexFunctionsMut<Float> for Complex {
+    fn arg_mut(&mut self) {
+        self.0.arg_mut();
+    }
+    fn mul_i_mut(&mut self, negative: bool) {
+        self.0.mul_i_mut(negative);
+    }
+    fn conj_mut(&mut self) {
+        self.0.conj_mut();
+    }
+    fn norm_mut(&mut self) {
+        self.0.norm_mut();
+    }
+}
+impl ComplexTrait<Float> for Complex {
+    fn to_imag(self) -> Float {
+        Float(self.0.into_real_imag().1)
+    }
+    fn to_real_imag(self) -> (Float, Float) {
+        let (real, imag) = self.0.into_real_imag();
+        (Float(real), Float(imag))
+    }
+    fn imag(&self) -> &Float {
+        cast(self.0.imag())
+    }
+    fn imag_mut(&mut self) -> &mut Float {
+        cast_mut(self.0.mut_imag())
+    }
+    fn zero_real(&mut self) {
+        let (real, imag) = self.0.as_mut_real_imag();
+        real.assign(0);
+        mem::swap(real, imag);
+    }
+    fn zero_imag(&mut self) {
+        self.0.mut_imag().assign(0);
+    }
+}
+impl FloatFunctionsMut<Float> for Complex {
+    fn sin_mut(&mut self) {
+        self.0.sin_mut();
+    }
+    fn cos_mut(&mut self) {
+        self.0.cos_mut();
+    }
+    fn asin_mut(&mut self) {
+        self.0.asin_mut();
+    }
+    fn acos_mut(&mut self) {
+        self.0.acos_mut();
+    }
+    fn sinh_mut(&mut self) {
+        self.0.sinh_mut();
+    }
+    fn cosh_mut(&mut self) {
+        self.0.cosh_mut();
+    }
+    fn asinh_mut(&mut self) {
+        self.0.asinh_mut();
+    }
+    fn acosh_mut(&mut self) {
+        self.0.acosh_mut();
+    }
+    fn tan_mut(&mut self) {
+        self.0.tan_mut();
+    }
+    fn tanh_mut(&mut self) {
+        self.0.tanh_mut();
+    }
+    fn atan_mut(&mut self) {
+        self.0.atan_mut();
+    }
+    fn atanh_mut(&mut self) {
+        self.0.atanh_mut();
+    }
+    fn ln_mut(&mut self) {
+        self.0.ln_mut();
+    }
+    fn exp_mut(&mut self) {
+        self.0.exp_mut();
+    }
+    fn hypot_mut(&mut self, other: &Self) {
+        if self.imag().is_zero() && other.imag().is_zero() {
+            self.real_mut().hypot_mut(other.real())
+        } else {
+            *self = self.clone() * self.clone() + other.clone() * other.clone();
+            self.sqrt_mut();
+        }
+    }
+    fn atan2_mut(&mut self, other: &Self) {
+        if self.imag().is_zero() && other.imag().is_zero() {
+            self.real_mut().atan2_mut(other.real());
+        } else {
+            let d = self.clone().hypot(other);
+            *self = self.clone().mul_i(false) + other;
+            *self /= d;
+            self.ln_mut();
+            self.mul_i_mut(true)
+        }
+    }
+    fn min_mut(&mut self, other: &Self) {
+        self.real_mut().min_mut(other.real());
+        self.imag_mut().min_mut(other.imag());
+    }
+    fn max_mut(&mut self, other: &Self) {
+        self.real_mut().max_mut(other.real());
+        self.imag_mut().max_mut(other.imag());
+    }
+    fn recip_mut(&mut self) {
+        self.0.recip_mut();
+    }
+    fn sqrt_mut(&mut self) {
+        if self.imag().is_zero() {
+            if self.real().is_sign_positive() {
+                self.real_mut().sqrt_mut()
+            } else {
+                self.real_mut().abs_mut();
+                self.real_mut().sqrt_mut();
+                self.mul_i_mut(false);
+            }
+        } else {
+            self.0.sqrt_mut();
+        }
+    }
+    fn cbrt_mut(&mut self) {
+        if self.imag().is_zero() {
+            self.real_mut().cbrt_mut()
+        } else if self.real().is_zero() {
+            self.imag_mut().cbrt_mut();
+            self.imag_mut().neg_assign();
+        } else {
+            let r = self.clone().abs().cbrt();
+            let theta = self.clone().arg() / Float::from(3);
+            let (sin, cos) = theta.0.sin_cos(rug::Float::new(precision()));
+            *self.real_mut() = Float(cos) * &r;
+            *self.imag_mut() = Float(sin) * r;
+        }
+    }
+    fn abs_mut(&mut self) {
+        self.0.abs_mut();
+    }
+    fn gamma_mut(&mut self) {
+        if self.imag().is_zero() {
+            self.real_mut().gamma_mut();
+            return;
+        }
+        fn inner1<const N: usize>(s: &Complex) -> Complex {
+            let mut run = Complex::from(1);
+            for i in (1..N).rev() {
+                run.recip_mut();
+                run *= Complex::from(-(i as isize)) + s;
+                run *= Float::from(i);
+                run -= s;
+                run += Float::from(2 * i);
+            }
+            run.recip()
+        }
+        fn inner2<const N: usize>(s: &Complex) -> Complex {
+            let mut run = Complex::from(1);
+            for i in (1..N).rev() {
+                run.recip_mut();
+                run *= Complex::from(i);
+                run += s;
+                run += Float::from(2 * i - 1);
+                run.recip_mut();
+                run *= Complex::from(i - 1) + s;
+                run.neg_assign();
+                run += s;
+                run += Float::from(2 * (i - 1));
+            }
+            run.recip()
+        }
+        *self = (inner1::<64>(self) + inner2::<64>(self)) / Float::from(Constant::E)
+    }
+    fn erf_mut(&mut self) {
+        if self.imag().is_zero() {
+            self.real_mut().erf_mut();
+            return;
+        }
+        self.erfc_mut();
+        self.neg_assign();
+        *self += Float::from(1);
+    }
+    fn erfc_mut(&mut self) {
+        if self.imag().is_zero() {
+            self.real_mut().erfc_mut();
+            return;
+        }
+        let mut sq = self.clone() * self.deref();
+        let s = sq.clone();
+        sq.neg_assign();
+        sq.exp_mut();
+        sq *= self.deref();
+        sq *= Float::from(Constant::Pi).sqrt().recip();
+        fn inner<const N: usize>(s: &Complex) -> Complex {
+            let mut run =
+                ((Complex::from(N) + Float::from(0.5) + s) + Float::from(1)) / Float::from(N);
+            for i in (1..N).rev() {
+                run *= Float::from(i) + Float::from(0.5);
+                run += s;
+                run.recip_mut();
+                run *= Float::from(i);
+                run += Float::from(1);
+                run.recip_mut();
+            }
+            run *= Float::from(0.5);
+            run += s;
+            run
+        }
+        sq /= inner::<64>(&s);
+        *self = sq;
+    }
+    fn round_mut(&mut self) {
+        self.real_mut().round_mut();
+        self.imag_mut().round_mut();
+    }
+    fn ceil_mut(&mut self) {
+        self.real_mut().ceil_mut();
+        self.imag_mut().ceil_mut();
+    }
+    fn floor_mut(&mut self) {
+        self.real_mut().floor_mut();
+        self.imag_mut().floor_mut();
+    }
+    fn trunc_mut(&mut self) {
+        self.real_mut().trunc_mut();
+        self.imag_mut().trunc_mut();
+    }
+    fn fract_mut(&mut self) {
+        self.real_mut().fract_mut();
+        self.imag_mut().fract_mut();
+    }
+    fn tetration_mut(&mut self, other: &Self) {
+        fn tetration(a: Complex, other: &Complex) -> Complex {
+            let other = Complex::from(other.real().clone().round());
+            if other.real().0 <= 0 {
+                Complex::from(1)
+            } else {
+                a.clone().pow(a.tetration(&(other - Float::from(1))))
+            }
+        }
+        //TODO
+        *self = tetration(self.clone(), other)
+    }
+    fn subfactorial_mut(&mut self) {
+        //TODO
+        if self.is_zero() {
+            *self = Self::from(1);
+        } else {
+            *self = ((self.clone() + Float::from(1)).gamma() / Float::from(Constant::E)).round()
+        }
+    }
+}
+impl FloatTrait<Float> for Complex {
+    fn to_real(self) -> Float {
+        Float(self.0.into_real_imag().0)
+    }
+    fn real(&self) -> &Float {
+        cast(self.0.real())
+    }
+    fn real_mut(&mut self) -> &mut Float {
+        cast_mut(self.0.mut_real())
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+    fn parse_radix(src: &str, base: u8) -> Option<Self> {
+        Float::parse_radix(src, base).map(Self::from)
+    }
+    fn to_string_radix(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| {
+            match (
+                self.real().is_zero(),
+                self.imag().is_zero(),
+                self.imag().is_sign_positive(),
+            ) {
+                (false, false, true) => write!(
+                    fmt,
+                    "{}+{}i",
+                    self.real().to_string_radix(base),
+                    self.imag().to_string_radix(base)
+                ),
+                (false, false, false) => write!(
+                    fmt,
+                    "{}{}i",
+                    self.real().to_string_radix(base),
+                    self.imag().to_string_radix(base)
+                ),
+                (false, true, _) => write!(fmt, "{}", self.real().to_string_radix(base)),
+                (true, false, _) => write!(fmt, "{}i", self.imag().to_string_radix(base)),
+                (true, true, _) => write!(fmt, "0"),
+            }
+        })
+    }
+    fn get_closest_fraction(&self, base: u8) -> impl Display {
+        fmt::from_fn(move |fmt| {
+            match (
+                closest_fraction(self.real(), base),
+                closest_fraction(self.imag(), base),
+            ) {
+                (Some((true, _, num, den)), Some((true, 1, _, deni))) => {
+                    writeln!(fmt, "{num}/{den}+i/{deni}")
+                }
+                (Some((true, _, num, den)), Some((false, 1, _, deni))) => {
+                    writeln!(fmt, "{num}/{den}-i/{deni}")
+                }
+                (Some((false, _, num, den)), Some((true, 1, _, deni))) => {
+                    writeln!(fmt, "-{num}/{den}+i/{deni}")
+                }
+                (Some((false, _, num, den)), Some((false, 1, _, deni))) => {
+                    writeln!(fmt, "-{num}/{den}-i/{deni}")
+                }
+                (Some((true, _, num, den)), Some((true, _, numi, deni))) => {
+                    writeln!(fmt, "{num}/{den}+{numi}i/{deni}")
+                }
+                (Some((true, _, num, den)), Some((false, _, numi, deni))) => {
+                    writeln!(fmt, "{num}/{den}-{numi}i/{deni}")
+                }
+                (Some((false, _, num, den)), Some((true, _, numi, deni))) => {
+                    writeln!(fmt, "-{num}/{den}+{numi}i/{deni}")
+                }
+                (Some((false, _, num, den)), Some((false, _, numi, deni))) => {
+                    writeln!(fmt, "-{num}/{den}-{numi}i/{deni}")
+                }
+                (Some((true, _, num, den)), None) => writeln!(fmt, "{num}/{den}"),
+                (Some((false, _, num, den)), None) => writeln!(fmt, "-{num}/{den}"),
+                (None, Some((true, 1, _, deni))) => writeln!(fmt, "i/{deni}"),
+                (None, Some((false, 1, _, deni))) => writeln!(fmt, "-i/{deni}"),
+                (None, Some((true, _, numi, deni))) => writeln!(fmt, "{numi}i/{deni}"),
+                (None, Some((false, _, numi, deni))) => writeln!(fmt, "-{numi}i/{deni}"),
+                (None, None) => Ok(()),
+            }
+        })
+    }
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+fn closest_fraction(value: &Float, base: u8) -> Option<(bool, usize, impl Display, impl Display)> {
+    value.closest_fraction().map(|(b, n, d)| {
+        (
+            b,
+            n,
+            float_base::to_string_radix_usize(n, base),
+            float_base::to_string_radix_usize(d, base),
+        )
+    })
+}
+macro_rules! ops_assign {
+    ($ty:ty, $rhs:ty, $assign:ident, $orig:ident, $assign_fun:ident, $orig_fun:ident) => {
+        impl $assign<$rhs> for $ty {
+            fn $assign_fun(&mut self, rhs: $rhs) {
+                $assign::$assign_fun(&mut self.0, rhs.0);
+            }
+        }
+        impl $orig<$rhs> for $ty {
+            type Output = $ty;
+            fn $orig_fun(mut self, rhs: $rhs) -> $ty {
+                $assign::$assign_fun(&mut self.0, rhs.0);
+                self
+            }
+        }
+        impl<'a> $assign<&'a $rhs> for $ty {
+            fn $assign_fun(&mut self, rhs: &'a $rhs) {
+                $assign::$assign_fun(&mut self.0, &rhs.0);
+            }
+        }
+        impl<'a> $orig<&'a $rhs> for $ty {
+            type Output = $ty;
+            fn $orig_fun(mut self, rhs: &'a $rhs) -> $ty {
+                $assign::$assign_fun(&mut self.0, &rhs.0);
+                self
+            }
+        }
+    };
+}
+macro_rules! ops_assign_for {
+    ($(($ty:ty, $rhs:ty)),*) => {
+        $(
+            ops_assign!($ty, $rhs, MulAssign, Mul, mul_assign, mul);
+            ops_assign!($ty, $rhs, DivAssign, Div, div_assign, div);
+            ops_assign!($ty, $rhs, SubAssign, Sub, sub_assign, sub);
+            ops_assign!($ty, $rhs, AddAssign, Add, add_assign, add);
+        )*
+    }
+}
+ops_assign_for!((Float, Float), (Complex, Complex), (Complex, Float));
+ops_assign!(Float, Float, RemAssign, Rem, rem_assign, rem);
+macro_rules! float_complex_ops {
+    ($($orig:ident, $orig_fun:ident),*) => {
+        $(
+            impl $orig<Complex> for Float {
+                type Output = Complex;
+                fn $orig_fun(self, rhs: Complex) -> Complex {
+                    $orig::$orig_fun(Complex::from(self), rhs)
+                }
+            }
+            impl<'a> $orig<&'a Complex> for Float {
+                type Output = Complex;
+                fn $orig_fun(self, rhs: &'a Complex) -> Complex {
+                    $orig::$orig_fun(Complex::from(self), rhs)
+                }
+            }
+        )*
+    }
+}
+float_complex_ops!(Add, add, Sub, sub, Mul, mul, Div, div, Rem, rem);
+impl Rem<&Float> for Complex {
+    type Output = Self;
+    fn rem(mut self, rhs: &Float) -> Self::Output {
+        *self.real_mut() %= rhs;
+        *self.imag_mut() %= rhs;
+        self
+    }
+}
+impl Rem<Float> for Complex {
+    type Output = Self;
+    fn rem(self, rhs: Float) -> Self::Output {
+        self % &rhs
+    }
+}
+impl RemAssign<&Float> for Complex {
+    fn rem_assign(&mut self, rhs: &Float) {
+        *self.real_mut() %= rhs;
+        *self.imag_mut() %= rhs;
+    }
+}
+impl RemAssign<Float> for Complex {
+    fn rem_assign(&mut self, rhs: Float) {
+        *self %= &rhs;
+    }
+}
+impl Rem<&Self> for Complex {
+    type Output = Self;
+    fn rem(self, rhs: &Self) -> Self::Output {
+        self % rhs.real()
+    }
+}
+impl Rem<Self> for Complex {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self::Output {
+        self % rhs.real()
+    }
+}
+impl RemAssign<&Self> for Complex {
+    fn rem_assign(&mut self, rhs: &Self) {
+        *self %= rhs.real();
+    }
+}
+impl RemAssign<Self> for Complex {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self %= rhs.real();
+    }
+}
+impl From<Constant> for Float {
+    fn from(value: Constant) -> Self {
+        let prec = precision();
+        Self(match value {
+            Constant::Pi => rug::Float::with_val(prec, rug::float::Constant::Pi),
+            Constant::Tau => rug::Float::with_val(prec, rug::float::Constant::Pi) * 2,
+            Constant::E => rug::Float::with_val(prec, 1).exp(),
+            Constant::Infinity => rug::Float::with_val(prec, Special::Infinity),
+            Constant::NegInfinity => rug::Float::with_val(prec, Special::NegInfinity),
+            Constant::Nan => rug::Float::with_val(prec, Special::Nan),
+        })
+    }
+}
+impl<'a> From<&'a Float> for Float {
+    fn from(value: &'a Float) -> Self {
+        value.clone()
+    }
+}
+impl<T> From<T> for Complex
+where
+    Float: From<T>,
+{
+    fn from(value: T) -> Self {
+        let real = Float::from(value).0;
+        let prec = real.prec();
+        Self(rug::Complex::with_val(prec, (real, rug::Float::new(prec))))
+    }
+}
+impl<T, K> From<(T, K)> for Complex
+where
+    Float: From<T> + From<K>,
+{
+    fn from(value: (T, K)) -> Self {
+        Self(rug::Complex::with_val(
+            precision(),
+            (Float::from(value.0).0, Float::from(value.1).0),
+        ))
+    }
+}
+macro_rules! with_val {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Float
+            {
+                fn from(value: $ty) -> Self {
+                    Self(rug::Float::with_val(precision(), value))
+                }
+            }
+        )*
+    };
+}
+with_val!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+impl From<f16> for Float {
+    fn from(value: f16) -> Self {
+        Self::from(value as f32)
+    }
+}
+impl From<f128> for Float {
+    fn from(value: f128) -> Self {
+        Self::from(value as f64)
+    }
+}
+impl From<bool> for Float {
+    fn from(value: bool) -> Self {
+        Self::from(value as u8)
+    }
+}
+impl Pow<Float> for Float {
+    type Output = Float;
+    fn pow(self, rhs: Float) -> Self {
+        self.pow(&rhs)
+    }
+}
+impl Pow<&Float> for Float {
+    type Output = Float;
+    fn pow(mut self, rhs: &Float) -> Self {
+        if rhs.is_zero() && self.is_zero() {
+            Self::from(Constant::Nan)
+        } else {
+            self.0.pow_assign(&rhs.0);
+            self
+        }
+    }
+}
+impl Neg for Float {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(self.0.neg())
+    }
+}
+impl Neg for Integer {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(self.0.neg())
+    }
+}
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(self.0.neg())
+    }
+}
+impl Pow<&Complex> for Float {
+    type Output = Complex;
+    fn pow(self, rhs: &Complex) -> Complex {
+        self.pow(rhs.clone())
+    }
+}
+impl Pow<Complex> for Float {
+    type Output = Complex;
+    fn pow(self, rhs: Complex) -> Complex {
+        if rhs.imag().is_zero() {
+            self.pow(rhs.to_real()).into()
+        } else {
+            (rhs * self.ln()).exp()
+        }
+    }
+}
+impl Pow<&Self> for Complex {
+    type Output = Complex;
+    fn pow(self, rhs: &Self) -> Self {
+        self.pow(rhs.clone())
+    }
+}
+impl Pow<Self> for Complex {
+    type Output = Complex;
+    fn pow(self, rhs: Self) -> Self {
+        if rhs.imag().is_zero() {
+            self.pow(rhs.to_real())
+        } else if self.imag().is_zero() {
+            (rhs * self.to_real().ln()).exp()
+        } else {
+            (self.ln() * rhs).exp()
+        }
+    }
+}
+impl Pow<&Float> for Complex {
+    type Output = Complex;
+    fn pow(self, rhs: &Float) -> Self {
+        self.pow(rhs.clone())
+    }
+}
+impl Pow<Float> for Complex {
+    type Output = Complex;
+    fn pow(self, rhs: Float) -> Self {
+        if self.imag().is_zero() {
+            if self.real().is_sign_negative() {
+                let fract = rhs.clone().fract();
+                if fract.is_zero() {
+                    self.to_real().pow(rhs).into()
+                } else if fract.0 == 0.5 {
+                    Complex::from(self.to_real().abs().pow(rhs)).mul_i(false)
+                } else {
+                    (self.ln() * rhs).exp()
+                }
+            } else {
+                self.to_real().pow(rhs).into()
+            }
+        } else if self.real().is_zero()
+            && rhs.0.is_integer()
+            && let Some(rhs) = rhs.0.to_integer().and_then(|i| i.to_i32())
+        {
+            let f = rug::ops::Pow::pow(self.to_imag().0, rhs);
+            match rhs.rem_euclid(4) {
+                0 => Self::from(Float(f)),
+                1 => Self::from((Float::default(), Float(f))),
+                2 => Self::from(Float(-f)),
+                3 => Self::from((Float::default(), Float(-f))),
+                _ => unreachable!(),
+            }
+        } else {
+            (self.ln() * rhs).exp()
+        }
+    }
+}
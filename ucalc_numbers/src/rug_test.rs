@@ -0,0 +1,103 @@
+use crate::rug::{Complex, Float, Integer, UInteger, precision, set_precision};
+use crate::{ComplexFunctions, Constant, FloatFunctions, FloatTrait, Pow};
+fn int(n: isize) -> Integer {
+    Integer::from(n)
+}
+fn ints(v: &[isize]) -> Vec<Integer> {
+    v.iter().map(|n| int(*n)).collect()
+}
+#[test]
+fn test_precision() {
+    assert!(!set_precision(0));
+    assert_eq!(precision(), 256);
+    assert_eq!(
+        Float::from(Constant::Pi).to_string(),
+        "3.1415926535897932384626433832795028841971693993751058209749445923078164062862"
+    );
+    assert_eq!(
+        Float::from(2).pow(Float::from(200)).to_string(),
+        "1606938044258990275541962092341162602522202993782792835301376"
+    );
+    assert_eq!(Float::from(0.5).to_string(), "0.5");
+    assert_eq!(
+        Float::parse_radix("-1e-20", 10).unwrap().to_string(),
+        "-1e-20"
+    );
+    assert_eq!(
+        Float::parse_radix("0.1", 10)
+            .unwrap()
+            .to_string_radix(10)
+            .to_string(),
+        "0.1"
+    );
+}
+#[test]
+fn test_integer() {
+    assert_eq!(Integer::from(5).factorial(), Some(Integer::from(120)));
+    assert_eq!(
+        UInteger::from(10).binomial(UInteger::from(3)),
+        UInteger::from(120)
+    );
+    assert_eq!(
+        UInteger::from(30).factorial().unwrap().0.to_string(),
+        "265252859812191058636308480000000"
+    );
+}
+#[test]
+fn test_number_theory() {
+    assert_eq!(int(12).gcd(int(-18)), int(6));
+    assert_eq!(int(4).lcm(int(6)), int(12));
+    assert!(int(2305843009213693951).is_prime());
+    assert!(!int(561).is_prime());
+    assert!(!int(1).is_prime());
+    assert_eq!(int(13).next_prime(), int(17));
+    assert_eq!(int(-5).next_prime(), int(2));
+    assert_eq!(int(600851475143).factor(), ints(&[71, 839, 1471, 6857]));
+    assert_eq!(int(-12).factor(), ints(&[-1, 2, 2, 3]));
+    assert_eq!(int(1000006000009).factor(), ints(&[1000003, 1000003]));
+    assert_eq!(
+        Integer(int(2147483647).0 * int(2305843009213693951).0).factor(),
+        ints(&[2147483647, 2305843009213693951])
+    );
+    assert_eq!(int(4).modpow(int(13), int(497)), Some(int(445)));
+    assert_eq!(int(3).modpow(int(-1), int(7)), Some(int(5)));
+    assert_eq!(int(2).modpow(int(-1), int(4)), None);
+    assert_eq!(int(3).modinv(int(11)), Some(int(4)));
+    assert_eq!(int(-3).modinv(int(11)), Some(int(7)));
+    assert_eq!(int(2).modinv(int(4)), None);
+    assert_eq!(int(36).totient(), int(12));
+    assert_eq!(int(28).divisors(), ints(&[1, 2, 4, 7, 14, 28]));
+    assert_eq!(int(28).sigma(), int(56));
+}
+#[test]
+fn test_combinatorics() {
+    assert_eq!(int(10).perm(int(3)), int(720));
+    assert_eq!(int(3).perm(int(4)), int(0));
+    assert_eq!(int(-1).perm(int(2)), int(2));
+    assert_eq!(int(-1).binomial(int(2)), int(1));
+    assert_eq!(int(-4).binomial(int(3)), int(-20));
+    assert_eq!(Integer::multinomial(ints(&[2, 3, 4])), int(1260));
+    assert_eq!(Integer::multinomial(ints(&[2, -1])), int(0));
+    assert_eq!(int(5).stirling_first(int(2)), Some(int(-50)));
+    assert_eq!(int(0).stirling_first(int(0)), Some(int(1)));
+    assert_eq!(int(5).stirling_second(int(2)), Some(int(15)));
+    assert_eq!(int(3).stirling_second(int(4)), Some(int(0)));
+    assert_eq!(int(0).bell(), Some(int(1)));
+    assert_eq!(int(10).bell(), Some(int(115975)));
+    assert_eq!(int(10).catalan(), Some(int(16796)));
+    assert_eq!(int(100).partitions(), Some(int(190569292)));
+    assert_eq!(int(-1).partitions(), Some(int(0)));
+    assert_eq!(int(1000000).bell(), None);
+    assert_eq!(int(100000000).partitions(), None);
+    assert_eq!(int(5000).stirling_first(int(5000)), None);
+    assert_eq!(int(5000).stirling_second(int(6000)), Some(int(0)));
+    assert_eq!(int(5000).catalan(), None);
+}
+#[test]
+fn test_complex() {
+    assert_eq!(Complex::from(-4).sqrt(), Complex::from((0, 2)));
+    assert_eq!(Complex::from((0, 1)).pow(Float::from(2)), Complex::from(-1));
+    assert_eq!(Complex::from((3, 4)).abs(), Float::from(5));
+    assert_eq!(Complex::from((1, 1)).to_string(), "1+1i");
+    assert!((Complex::from((0, 1)).arg() - Float::from(Constant::Pi) / Float::from(2)).is_zero());
+}
@@ -276,6 +276,7 @@ pub trait ComplexTrait<F>: FloatTrait<F> {
 pub trait RealTrait<F>: FloatTrait<F> {
     fn is_sign_negative(&self) -> bool;
     fn is_sign_positive(&self) -> bool;
+    fn is_nan(&self) -> bool;
     fn into_isize(self) -> isize;
     fn into_usize(self) -> usize;
     fn closest_fraction(&self) -> Option<(bool, usize, usize)>;
@@ -1,12 +1,27 @@
-use crate::{float, integer};
+#[cfg(all(feature = "float", not(feature = "rational")))]
+use crate::float;
+#[cfg(feature = "float")]
+use crate::integer;
+#[cfg(feature = "rational")]
+use crate::rational;
 #[cfg(feature = "float")]
 pub type Integer = integer::Integer;
 #[cfg(feature = "float")]
 pub type UInteger = integer::UInteger;
-#[cfg(feature = "float")]
+#[cfg(all(feature = "float", not(feature = "rational")))]
 pub type Float = float::Float;
-#[cfg(feature = "float")]
+#[cfg(feature = "rational")]
+pub type Float = rational::Rational;
+#[cfg(all(feature = "float", not(feature = "rational")))]
 pub type Complex = float::Complex;
+#[cfg(feature = "rug")]
+pub type Integer = crate::rug::Integer;
+#[cfg(feature = "rug")]
+pub type UInteger = crate::rug::UInteger;
+#[cfg(feature = "rug")]
+pub type Float = crate::rug::Float;
+#[cfg(feature = "rug")]
+pub type Complex = crate::rug::Complex;
 #[derive(Copy, Clone)]
 pub enum Constant {
     Pi,
@@ -50,7 +65,6 @@ pub enum Number<T> {
 #[derive(Debug, PartialEq, Clone)]
 #[repr(transparent)]
 pub struct Vector<T>(pub(crate) Vec<T>);
-#[derive(Debug, PartialEq, Clone)]
 pub struct Matrix<T> {
     pub(crate) vec: *mut T,
     pub(crate) capacity_width: HalfUsize,
@@ -1,6 +1,8 @@
+#[cfg(not(feature = "rational"))]
+use crate::{Complex, ComplexType};
 use crate::{
-    Complex, ComplexFunctionsMut, ComplexTrait, ComplexType, Float, FloatFunctionsMut, FloatTrait,
-    FloatType, NegAssign, Pow, PowAssign, Quantity, Units,
+    ComplexFunctionsMut, ComplexTrait, Float, FloatFunctionsMut, FloatTrait, FloatType, NegAssign,
+    Pow, PowAssign, Quantity, Units,
 };
 use std::array;
 use std::cmp::Ordering;
@@ -10,8 +12,10 @@ use std::ops::{
     Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
     SubAssign,
 };
+#[cfg(not(feature = "rational"))]
 impl<const N: usize> FloatType<Float> for Quantity<Complex, f32, N> {}
 impl<const N: usize> FloatType<Float> for Quantity<Float, f32, N> {}
+#[cfg(not(feature = "rational"))]
 impl<const N: usize> ComplexType<Float> for Quantity<Complex, f32, N> {}
 impl<N, const K: usize> Default for Units<N, K> {
     fn default() -> Self {
@@ -43,6 +47,7 @@ impl<T: Default, N, const K: usize> Default for Quantity<T, N, K> {
         }
     }
 }
+#[cfg(not(feature = "rational"))]
 impl<T, N, const K: usize> From<T> for Quantity<Complex, N, K>
 where
     Complex: From<T>,
@@ -75,6 +80,7 @@ impl<N: AddAssign + Clone, const K: usize> Product for Quantity<Float, N, K> {
         iter.fold(Self::from(1), |sum, s| sum * s)
     }
 }
+#[cfg(not(feature = "rational"))]
 impl<N: AddAssign + Clone, const K: usize> Product for Quantity<Complex, N, K> {
     fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::from(1), |sum, s| sum * s)
@@ -157,6 +163,7 @@ macro_rules! impl_ops {
                 rhs
             }
         }
+        #[cfg(not(feature = "rational"))]
         impl<N, const K: usize> $op<Quantity<Complex, N, K>> for Float {
             type Output = Quantity<Complex, N, K>;
             fn $fun(self, mut rhs: Quantity<Complex, N, K>) -> Self::Output {
@@ -244,6 +251,7 @@ macro_rules! impl_ops_simple {
                 rhs
             }
         }
+        #[cfg(not(feature = "rational"))]
         impl<N, const K: usize> $op<Quantity<Complex, N, K>> for Float {
             type Output = Quantity<Complex, N, K>;
             fn $fun(self, mut rhs: Quantity<Complex, N, K>) -> Self::Output {
@@ -85,19 +85,33 @@ fn impl_lower_ops(ty: TokenStream, oty: TokenStream, ops: Op) -> TokenStream {
     let (op, op_assign, fun, fun_assign) = ops.get();
     let i = get_impl();
     let t = get_type(ty.clone());
-    let assign = if matches!(ops, Op::Pow) {
+    let ref_assign = if matches!(ops, Op::Pow) {
         quote! {}
     } else {
         quote! {
-            #i #op<#oty> for #t {
-                type Output = Self;
-                fn #fun(mut self, rhs: #oty) -> Self::Output {
-                    #op_assign::#fun_assign(&mut self, rhs);
-                    self
+            #i #op_assign<&#oty> for #t {
+                fn #fun_assign(&mut self, rhs: &#oty) {
+                    #op_assign::#fun_assign(self, rhs.clone())
                 }
             }
         }
     };
+    let assign = quote! {
+        #i #op<#oty> for #t {
+            type Output = Self;
+            fn #fun(mut self, rhs: #oty) -> Self::Output {
+                #op_assign::#fun_assign(&mut self, rhs);
+                self
+            }
+        }
+        #i #op<&#oty> for #t {
+            type Output = Self;
+            fn #fun(self, rhs: &#oty) -> Self::Output {
+                #op::#fun(self, rhs.clone())
+            }
+        }
+        #ref_assign
+    };
     quote! {
         #assign
         #i #op<#t> for #oty {
@@ -113,6 +127,22 @@ fn impl_lower_ops(ty: TokenStream, oty: TokenStream, ops: Op) -> TokenStream {
                         });
                         Number::List(b)
                     }
+                    #[cfg(feature = "vector")]
+                    Number::Vector(mut b) => {
+                        b.iter_mut().for_each(|b| {
+                            let old = std::mem::replace(b, <#ty>::from(0).into());
+                            *b = #op::#fun(self.clone(), old);
+                        });
+                        Number::Vector(b)
+                    }
+                    #[cfg(feature = "matrix")]
+                    Number::Matrix(mut b) => {
+                        b.as_mut_slice().iter_mut().for_each(|b| {
+                            let old = std::mem::replace(b, <#ty>::from(0).into());
+                            *b = #op::#fun(self.clone(), old);
+                        });
+                        Number::Matrix(b)
+                    }
                     #[cfg(feature = "units")]
                     Number::Units(u) => {todo!()}
                 }
@@ -126,6 +156,15 @@ fn impl_lower_ops(ty: TokenStream, oty: TokenStream, ops: Op) -> TokenStream {
                     Self::List(a) => a
                         .iter_mut()
                         .for_each(|a| #op_assign::#fun_assign(a, rhs.clone())),
+                    #[cfg(feature = "vector")]
+                    Self::Vector(a) => a
+                        .iter_mut()
+                        .for_each(|a| #op_assign::#fun_assign(a, rhs.clone())),
+                    #[cfg(feature = "matrix")]
+                    Self::Matrix(a) => a
+                        .as_mut_slice()
+                        .iter_mut()
+                        .for_each(|a| #op_assign::#fun_assign(a, rhs.clone())),
                     #[cfg(feature = "units")]
                     Self::Units(u) => {todo!()}
                 }
@@ -138,19 +177,63 @@ fn impl_ops(ty: TokenStream, ops: Op) -> TokenStream {
     let (op, op_assign, fun, fun_assign) = ops.get();
     let i = get_impl();
     let t = get_type(ty.clone());
-    let assign = if matches!(ops, Op::Pow) {
+    let ref_assign = if matches!(ops, Op::Pow) {
         quote! {}
     } else {
         quote! {
-            #i #op<Self> for #t {
-                type Output = Self;
-                fn #fun(mut self, rhs: Self) -> Self::Output {
-                    #op_assign::#fun_assign(&mut self, rhs);
-                    self
+            #i #op_assign<&#t> for #t {
+                fn #fun_assign(&mut self, rhs: &#t) {
+                    #op_assign::#fun_assign(self, rhs.clone())
                 }
             }
         }
     };
+    let assign = quote! {
+        #i #op<Self> for #t {
+            type Output = Self;
+            fn #fun(mut self, rhs: Self) -> Self::Output {
+                #op_assign::#fun_assign(&mut self, rhs);
+                self
+            }
+        }
+        #i #op<&#t> for #t {
+            type Output = Self;
+            fn #fun(self, rhs: &#t) -> Self::Output {
+                #op::#fun(self, rhs.clone())
+            }
+        }
+        #ref_assign
+    };
+    let matrix = if matches!(ops, Op::Mul) {
+        quote! {
+            #[cfg(feature = "matrix")]
+            (s @ Self::Matrix(_), Self::Matrix(b)) => {
+                let Self::Matrix(a) = s else { unreachable!() };
+                *s = match a.mul_matrix(&b) {
+                    Some(m) => Self::Matrix(m),
+                    None => Self::Value(#ty::from(crate::Constant::Nan).into()),
+                }
+            }
+            #[cfg(all(feature = "vector", feature = "matrix"))]
+            (s @ Self::Matrix(_), Self::Vector(b)) => {
+                let Self::Matrix(a) = s else { unreachable!() };
+                *s = match a.mul_vector(&b) {
+                    Some(v) => Self::Vector(v),
+                    None => Self::Value(#ty::from(crate::Constant::Nan).into()),
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[cfg(feature = "matrix")]
+            (Self::Matrix(a), Self::Matrix(b))
+                if a.width() == b.width() && a.height() == b.height() => a
+                .as_mut_slice()
+                .iter_mut()
+                .zip(b.into_vec())
+                .for_each(|(a, b)| #op_assign::#fun_assign(a, b)),
+        }
+    };
     quote! {
         #assign
         #i #op_assign<Self> for #t {
@@ -177,7 +260,46 @@ fn impl_ops(ty: TokenStream, ops: Op) -> TokenStream {
                         .iter_mut()
                         .zip(b.into_iter())
                         .for_each(|(a, b)| #op_assign::#fun_assign(a, b)),
-                    #[cfg(feature = "units")]
+                    #[cfg(feature = "vector")]
+                    (Self::Vector(a), Self::Value(b)) => a
+                        .iter_mut()
+                        .for_each(|a| #op_assign::#fun_assign(a, b.clone())),
+                    #[cfg(feature = "vector")]
+                    (s @ Self::Value(_), mut r @ Self::Vector(_)) => {
+                        std::mem::swap(s, &mut r);
+                        let (Self::Vector(a), Self::Value(b)) = (s, r) else {
+                            unreachable!()
+                        };
+                        a.iter_mut().for_each(|a| {
+                            let old = std::mem::replace(a, #ty::from(0).into());
+                            *a = #op::#fun(b.clone(), old)
+                        })
+                    }
+                    #[cfg(feature = "vector")]
+                    (Self::Vector(a), Self::Vector(b)) if a.len() == b.len() => a
+                        .iter_mut()
+                        .zip(b.0)
+                        .for_each(|(a, b)| #op_assign::#fun_assign(a, b)),
+                    #[cfg(feature = "matrix")]
+                    (Self::Matrix(a), Self::Value(b)) => a
+                        .as_mut_slice()
+                        .iter_mut()
+                        .for_each(|a| #op_assign::#fun_assign(a, b.clone())),
+                    #[cfg(feature = "matrix")]
+                    (s @ Self::Value(_), mut r @ Self::Matrix(_)) => {
+                        std::mem::swap(s, &mut r);
+                        let (Self::Matrix(a), Self::Value(b)) = (s, r) else {
+                            unreachable!()
+                        };
+                        a.as_mut_slice().iter_mut().for_each(|a| {
+                            let old = std::mem::replace(a, #ty::from(0).into());
+                            *a = #op::#fun(b.clone(), old)
+                        })
+                    }
+                    #matrix
+                    #[cfg(any(feature = "vector", feature = "matrix"))]
+                    (s, _) => *s = Self::Value(#ty::from(crate::Constant::Nan).into()),
+                    #[cfg(all(feature = "units", not(any(feature = "vector", feature = "matrix"))))]
                     _ => {todo!()}
                 }
             }
@@ -253,6 +375,10 @@ pub fn generate_types(ty: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     Self::Value(a) => a.neg_assign(),
                     #[cfg(feature = "list")]
                     Self::List(a) => a.iter_mut().for_each(|a| a.neg_assign()),
+                    #[cfg(feature = "vector")]
+                    Self::Vector(a) => a.iter_mut().for_each(|a| a.neg_assign()),
+                    #[cfg(feature = "matrix")]
+                    Self::Matrix(a) => a.as_mut_slice().iter_mut().for_each(|a| a.neg_assign()),
                     #[cfg(feature = "units")]
                     Self::Units(u) => {todo!()}
                 }
@@ -283,6 +409,10 @@ pub fn generate_types(ty: proc_macro::TokenStream) -> proc_macro::TokenStream {
                         }
                         write!(f, "]")
                     }
+                    #[cfg(feature = "vector")]
+                    Self::Vector(a) => write!(f, "{}", a),
+                    #[cfg(feature = "matrix")]
+                    Self::Matrix(a) => write!(f, "{}", a),
                     #[cfg(feature = "units")]
                     Self::Units(u) => {todo!()}
                 }